@@ -0,0 +1,13 @@
+//! Feed arbitrary input into the parsers. Malformed input must be reported as
+//! an error, never panic.
+#![no_main]
+
+use kicad_files::{pcb::PCB, symbol_lib::SymbolLib};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+	if let Ok(s) = std::str::from_utf8(data) {
+		let _ = s.parse::<PCB>();
+		let _ = s.parse::<SymbolLib>();
+	}
+});
@@ -0,0 +1,28 @@
+//! Write boards made of arbitrary nets and segments and assert that parsing the
+//! output yields the same board.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use kicad_files::{
+	board::graphic::Segment,
+	pcb::{Net, PCBContent, PCB}
+};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+struct Board {
+	nets: Vec<Net>,
+	segments: Vec<Segment>
+}
+
+fuzz_target!(|board: Board| {
+	let mut pcb = PCB::builder().build();
+	pcb.pcb_content
+		.extend(board.nets.into_iter().map(PCBContent::Net));
+	pcb.pcb_content
+		.extend(board.segments.into_iter().map(PCBContent::Segment));
+
+	let written = serde_sexpr::to_string(&pcb).expect("Failed to write board");
+	let parsed: PCB = written.parse().expect("Failed to parse written board");
+	assert_eq!(parsed, pcb);
+});
@@ -0,0 +1,298 @@
+use super::pcb::{self, LayerType};
+use std::{
+	convert::Infallible,
+	fmt::{self, Display, Formatter},
+	str::FromStr
+};
+
+/// The canonical board layers of KiCad together with their conventional layer
+/// numbers. Layers that are not known to KiCad are represented by
+/// [`CanonicalLayer::Other`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum CanonicalLayer {
+	FCu,
+	/// An inner copper layer, numbered 1 through 30.
+	InCu(u8),
+	BCu,
+	BAdhes,
+	FAdhes,
+	BPaste,
+	FPaste,
+	BSilkS,
+	FSilkS,
+	BMask,
+	FMask,
+	DwgsUser,
+	CmtsUser,
+	Eco1User,
+	Eco2User,
+	EdgeCuts,
+	Margin,
+	BCrtYd,
+	FCrtYd,
+	BFab,
+	FFab,
+	/// A user defined layer, numbered 1 through 9.
+	User(u8),
+	Other(String)
+}
+
+/// Layers with a fixed name, ordinal and user-visible alias.
+const FIXED: [(CanonicalLayer, &str, u32, &str); 20] = [
+	(CanonicalLayer::FCu, "F.Cu", 0, "F.Cu"),
+	(CanonicalLayer::BCu, "B.Cu", 31, "B.Cu"),
+	(CanonicalLayer::BAdhes, "B.Adhes", 32, "B.Adhesive"),
+	(CanonicalLayer::FAdhes, "F.Adhes", 33, "F.Adhesive"),
+	(CanonicalLayer::BPaste, "B.Paste", 34, "B.Paste"),
+	(CanonicalLayer::FPaste, "F.Paste", 35, "F.Paste"),
+	(CanonicalLayer::BSilkS, "B.SilkS", 36, "B.Silkscreen"),
+	(CanonicalLayer::FSilkS, "F.SilkS", 37, "F.Silkscreen"),
+	(CanonicalLayer::BMask, "B.Mask", 38, "B.Mask"),
+	(CanonicalLayer::FMask, "F.Mask", 39, "F.Mask"),
+	(CanonicalLayer::DwgsUser, "Dwgs.User", 40, "User.Drawings"),
+	(CanonicalLayer::CmtsUser, "Cmts.User", 41, "User.Comments"),
+	(CanonicalLayer::Eco1User, "Eco1.User", 42, "User.Eco1"),
+	(CanonicalLayer::Eco2User, "Eco2.User", 43, "User.Eco2"),
+	(CanonicalLayer::EdgeCuts, "Edge.Cuts", 44, "Edge.Cuts"),
+	(CanonicalLayer::Margin, "Margin", 45, "Margin"),
+	(CanonicalLayer::BCrtYd, "B.CrtYd", 46, "B.Courtyard"),
+	(CanonicalLayer::FCrtYd, "F.CrtYd", 47, "F.Courtyard"),
+	(CanonicalLayer::BFab, "B.Fab", 48, "B.Fab"),
+	(CanonicalLayer::FFab, "F.Fab", 49, "F.Fab")
+];
+
+const USER_ORDINAL: u32 = 49;
+
+/// What a board layer is used for, see [`CanonicalLayer::role`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LayerRole {
+	Copper,
+	Silkscreen,
+	SolderMask,
+	SolderPaste,
+	Adhesive,
+	Courtyard,
+	Fabrication,
+	/// The board outline `Edge.Cuts` and the `Margin` layer.
+	BoardOutline,
+	/// Drawing, comment and user layers, as well as unknown layers.
+	User
+}
+
+impl CanonicalLayer {
+	/// Parse a layer name. Both the canonical name (e.g. `F.SilkS`) and the
+	/// user-visible name (e.g. `F.Silkscreen`) are accepted.
+	pub fn from_name(name: &str) -> Self {
+		if let Some((layer, ..)) = FIXED
+			.iter()
+			.find(|(_, canonical, _, alias)| name == *canonical || name == *alias)
+		{
+			return layer.clone();
+		}
+
+		let numbered = |prefix: &str, suffix: &str, max: u8| {
+			name.strip_prefix(prefix)
+				.and_then(|n| n.strip_suffix(suffix))
+				.and_then(|n| n.parse::<u8>().ok())
+				.filter(|n| (1..=max).contains(n))
+		};
+		if let Some(n) = numbered("In", ".Cu", 30) {
+			return Self::InCu(n);
+		}
+		if let Some(n) = numbered("User.", "", 9) {
+			return Self::User(n);
+		}
+		Self::Other(name.to_owned())
+	}
+
+	/// Return the layer with the given conventional layer number.
+	pub fn from_ordinal(ordinal: u32) -> Option<Self> {
+		match ordinal {
+			1..=30 => Some(Self::InCu(ordinal as u8)),
+			50..=58 => Some(Self::User((ordinal - USER_ORDINAL) as u8)),
+			_ => FIXED
+				.iter()
+				.find(|(_, _, n, _)| *n == ordinal)
+				.map(|(layer, ..)| layer.clone())
+		}
+	}
+
+	/// Return the conventional layer number, or `None` for unknown layers.
+	pub fn ordinal(&self) -> Option<u32> {
+		match self {
+			Self::InCu(n) => Some(*n as u32),
+			Self::User(n) => Some(USER_ORDINAL + *n as u32),
+			Self::Other(_) => None,
+			layer => FIXED
+				.iter()
+				.find(|(l, ..)| l == layer)
+				.map(|(_, _, n, _)| *n)
+		}
+	}
+
+	/// Return the canonical name of this layer.
+	pub fn name(&self) -> String {
+		self.to_string()
+	}
+
+	/// Return the layer on the other side of the board, e.g. `B.SilkS` for
+	/// `F.SilkS`. Inner and unsided layers are returned unchanged.
+	pub fn flipped(&self) -> Self {
+		match self {
+			Self::FCu => Self::BCu,
+			Self::BCu => Self::FCu,
+			Self::FAdhes => Self::BAdhes,
+			Self::BAdhes => Self::FAdhes,
+			Self::FPaste => Self::BPaste,
+			Self::BPaste => Self::FPaste,
+			Self::FSilkS => Self::BSilkS,
+			Self::BSilkS => Self::FSilkS,
+			Self::FMask => Self::BMask,
+			Self::BMask => Self::FMask,
+			Self::FCrtYd => Self::BCrtYd,
+			Self::BCrtYd => Self::FCrtYd,
+			Self::FFab => Self::BFab,
+			Self::BFab => Self::FFab,
+			layer => layer.clone()
+		}
+	}
+
+	/// Return what this layer is used for.
+	pub fn role(&self) -> LayerRole {
+		match self {
+			Self::FCu | Self::InCu(_) | Self::BCu => LayerRole::Copper,
+			Self::FSilkS | Self::BSilkS => LayerRole::Silkscreen,
+			Self::FMask | Self::BMask => LayerRole::SolderMask,
+			Self::FPaste | Self::BPaste => LayerRole::SolderPaste,
+			Self::FAdhes | Self::BAdhes => LayerRole::Adhesive,
+			Self::FCrtYd | Self::BCrtYd => LayerRole::Courtyard,
+			Self::FFab | Self::BFab => LayerRole::Fabrication,
+			Self::EdgeCuts | Self::Margin => LayerRole::BoardOutline,
+			Self::DwgsUser
+			| Self::CmtsUser
+			| Self::Eco1User
+			| Self::Eco2User
+			| Self::User(_)
+			| Self::Other(_) => LayerRole::User
+		}
+	}
+
+	pub fn is_copper(&self) -> bool {
+		matches!(self, Self::FCu | Self::InCu(_) | Self::BCu)
+	}
+
+	/// Return the layer definition KiCad uses for this layer, or `None` for
+	/// unknown layers.
+	pub fn definition(&self) -> Option<pcb::Layer> {
+		let ordinal = self.ordinal()?;
+		let layer_type = match self.is_copper() {
+			true => LayerType::Signal,
+			false => LayerType::User
+		};
+		Some(pcb::Layer::new(ordinal, self.name(), layer_type))
+	}
+}
+
+impl Display for CanonicalLayer {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::InCu(n) => write!(f, "In{n}.Cu"),
+			Self::User(n) => write!(f, "User.{n}"),
+			Self::Other(name) => f.write_str(name),
+			layer => {
+				let (_, name, ..) = FIXED.iter().find(|(l, ..)| l == layer).unwrap();
+				f.write_str(name)
+			}
+		}
+	}
+}
+
+impl FromStr for CanonicalLayer {
+	type Err = Infallible;
+
+	fn from_str(s: &str) -> Result<Self, Infallible> {
+		Ok(Self::from_name(s))
+	}
+}
+
+impl From<&pcb::Layer> for CanonicalLayer {
+	fn from(layer: &pcb::Layer) -> Self {
+		Self::from_name(layer.name())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn role() {
+		let role = |name| CanonicalLayer::from_name(name).role();
+		assert_eq!(role("F.SilkS"), LayerRole::Silkscreen);
+		assert_eq!(role("B.Silkscreen"), LayerRole::Silkscreen);
+		assert_eq!(role("F.Mask"), LayerRole::SolderMask);
+		assert_eq!(role("F.CrtYd"), LayerRole::Courtyard);
+		assert_eq!(role("B.Fab"), LayerRole::Fabrication);
+		assert_eq!(role("In4.Cu"), LayerRole::Copper);
+		assert_eq!(role("Edge.Cuts"), LayerRole::BoardOutline);
+		assert_eq!(role("User.3"), LayerRole::User);
+		assert_eq!(role("Custom"), LayerRole::User);
+	}
+
+	#[test]
+	fn front_copper() {
+		let layer = CanonicalLayer::from_name("F.Cu");
+		assert_eq!(layer, CanonicalLayer::FCu);
+		assert_eq!(layer.ordinal(), Some(0));
+		assert_eq!(CanonicalLayer::from_ordinal(0), Some(CanonicalLayer::FCu));
+		assert_eq!(layer.name(), "F.Cu");
+	}
+
+	#[test]
+	fn back_copper() {
+		let layer = CanonicalLayer::from_name("B.Cu");
+		assert_eq!(layer, CanonicalLayer::BCu);
+		assert_eq!(layer.ordinal(), Some(31));
+		assert_eq!(CanonicalLayer::from_ordinal(31), Some(CanonicalLayer::BCu));
+	}
+
+	#[test]
+	fn numbered_layers() {
+		assert_eq!(CanonicalLayer::from_name("In2.Cu"), CanonicalLayer::InCu(2));
+		assert_eq!(CanonicalLayer::InCu(2).ordinal(), Some(2));
+		assert_eq!(CanonicalLayer::from_name("User.9"), CanonicalLayer::User(9));
+		assert_eq!(
+			CanonicalLayer::from_ordinal(58),
+			Some(CanonicalLayer::User(9))
+		);
+		assert_eq!(CanonicalLayer::User(9).to_string(), "User.9");
+	}
+
+	#[test]
+	fn flipped() {
+		assert_eq!(CanonicalLayer::FCu.flipped(), CanonicalLayer::BCu);
+		assert_eq!(CanonicalLayer::BSilkS.flipped(), CanonicalLayer::FSilkS);
+		assert_eq!(CanonicalLayer::InCu(2).flipped(), CanonicalLayer::InCu(2));
+		assert_eq!(CanonicalLayer::EdgeCuts.flipped(), CanonicalLayer::EdgeCuts);
+	}
+
+	#[test]
+	fn aliases_and_unknown_layers() {
+		assert_eq!(
+			CanonicalLayer::from_name("F.Silkscreen"),
+			CanonicalLayer::FSilkS
+		);
+		let other = CanonicalLayer::from_name("In31.Cu");
+		assert_eq!(other, CanonicalLayer::Other("In31.Cu".to_owned()));
+		assert_eq!(other.ordinal(), None);
+		assert_eq!(CanonicalLayer::from_ordinal(59), None);
+	}
+
+	#[test]
+	fn pcb_layer_definition() {
+		let definition = CanonicalLayer::BCu.definition().unwrap();
+		assert_eq!(definition.number(), 31);
+		assert_eq!(definition.to_string(), r#"(31 "B.Cu" signal)"#);
+		assert_eq!(CanonicalLayer::from(&definition), CanonicalLayer::BCu);
+	}
+}
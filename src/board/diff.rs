@@ -0,0 +1,223 @@
+//! A semantic diff between two boards, returned by
+//! [`PCB::diff`](crate::pcb::PCB::diff).
+
+use crate::internal::scanner;
+use serde::Serialize;
+use std::{
+	collections::{BTreeMap, HashMap, VecDeque},
+	fmt::{self, Display, Formatter}
+};
+
+/// The differences between two boards. Nets are matched by their name, layers by
+/// their canonical name and all other items by their unique identifier. Items
+/// without an identifier can only be reported as added or removed.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct PcbDiff {
+	pub nets: Vec<DiffEntry>,
+	pub layers: Vec<DiffEntry>,
+	pub items: Vec<DiffEntry>
+}
+
+/// A single difference of a [`PcbDiff`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct DiffEntry {
+	/// The name of the net or layer, the identifier of the item, or the s-expr
+	/// of items without an identifier.
+	pub key: String,
+	pub change: Change
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+	Added,
+	Removed,
+	Modified(Vec<FieldChange>)
+}
+
+/// A field that differs between two versions of an item. The field is the name
+/// of the s-expr node, or the index in brackets for atoms, followed by the
+/// index in brackets if the node occurs multiple times.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FieldChange {
+	pub field: String,
+	pub old: Option<String>,
+	pub new: Option<String>
+}
+
+impl PcbDiff {
+	pub fn is_empty(&self) -> bool {
+		self.nets.is_empty() && self.layers.is_empty() && self.items.is_empty()
+	}
+}
+
+impl Display for PcbDiff {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		for (kind, entries) in [
+			("net", &self.nets),
+			("layer", &self.layers),
+			("item", &self.items)
+		] {
+			for entry in entries {
+				match &entry.change {
+					Change::Added => writeln!(f, "+ {kind} {}", entry.key)?,
+					Change::Removed => writeln!(f, "- {kind} {}", entry.key)?,
+					Change::Modified(fields) => {
+						writeln!(f, "~ {kind} {}", entry.key)?;
+						for field in fields {
+							writeln!(
+								f,
+								"    {}: {} -> {}",
+								field.field,
+								field.old.as_deref().unwrap_or("-"),
+								field.new.as_deref().unwrap_or("-")
+							)?;
+						}
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Compare two lists of `(key, s-expr)` pairs. Entries with the same key are
+/// matched in order.
+pub(crate) fn diff_entries(
+	old: &[(String, String)],
+	new: &[(String, String)]
+) -> Vec<DiffEntry> {
+	let mut unmatched: HashMap<&str, VecDeque<&str>> = HashMap::new();
+	for (key, text) in new {
+		unmatched.entry(key).or_default().push_back(text);
+	}
+
+	let mut entries = Vec::new();
+	let mut matched: HashMap<&str, usize> = HashMap::new();
+	for (key, text) in old {
+		let change = match unmatched
+			.get_mut(key.as_str())
+			.and_then(VecDeque::pop_front)
+		{
+			Some(new_text) => {
+				*matched.entry(key).or_default() += 1;
+				if text == new_text {
+					continue;
+				}
+				Change::Modified(field_changes(text, new_text))
+			},
+			None => Change::Removed
+		};
+		entries.push(DiffEntry {
+			key: key.clone(),
+			change
+		});
+	}
+
+	let mut seen: HashMap<&str, usize> = HashMap::new();
+	for (key, _) in new {
+		let count = seen.entry(key).or_default();
+		*count += 1;
+		if *count > matched.get(key.as_str()).copied().unwrap_or(0) {
+			entries.push(DiffEntry {
+				key: key.clone(),
+				change: Change::Added
+			});
+		}
+	}
+	entries
+}
+
+/// Label the children of an s-expr with their field names.
+fn fields(text: &str) -> Option<BTreeMap<String, &str>> {
+	let (_, nodes) = scanner::elements(text).ok()?;
+	let mut fields = BTreeMap::new();
+	let mut counts: HashMap<&str, usize> = HashMap::new();
+	let mut atoms = 0;
+	for node in nodes {
+		let label = match node.name {
+			"" => {
+				atoms += 1;
+				format!("[{}]", atoms - 1)
+			},
+			name => {
+				let count = counts.entry(name).or_default();
+				*count += 1;
+				match count {
+					1 => name.to_owned(),
+					n => format!("{name}[{}]", *n - 1)
+				}
+			}
+		};
+		fields.insert(label, node.text);
+	}
+	Some(fields)
+}
+
+fn field_changes(old: &str, new: &str) -> Vec<FieldChange> {
+	let (Some(old_fields), Some(new_fields)) = (fields(old), fields(new)) else {
+		return vec![FieldChange {
+			field: String::new(),
+			old: Some(old.to_owned()),
+			new: Some(new.to_owned())
+		}];
+	};
+
+	let mut changes: Vec<_> = old_fields
+		.iter()
+		.filter(|(field, text)| new_fields.get(*field) != Some(text))
+		.map(|(field, text)| FieldChange {
+			field: field.clone(),
+			old: Some((*text).to_owned()),
+			new: new_fields.get(field).map(|text| (*text).to_owned())
+		})
+		.collect();
+	changes.extend(
+		new_fields
+			.iter()
+			.filter(|(field, _)| !old_fields.contains_key(*field))
+			.map(|(field, text)| FieldChange {
+				field: field.clone(),
+				old: None,
+				new: Some((*text).to_owned())
+			})
+	);
+	changes
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn entry(key: &str, text: &str) -> (String, String) {
+		(key.to_owned(), text.to_owned())
+	}
+
+	#[test]
+	fn modified_fields() {
+		let entries =
+			diff_entries(&[entry("a", "(net 1 GND)")], &[entry("a", "(net 2 GND)")]);
+		assert_eq!(entries, vec![DiffEntry {
+			key: "a".to_owned(),
+			change: Change::Modified(vec![FieldChange {
+				field: "[0]".to_owned(),
+				old: Some("1".to_owned()),
+				new: Some("2".to_owned())
+			}])
+		}]);
+	}
+
+	#[test]
+	fn duplicate_keys() {
+		let old = [entry("(xy 0 0)", "(xy 0 0)")];
+		let new = [entry("(xy 0 0)", "(xy 0 0)"), entry("(xy 0 0)", "(xy 0 0)")];
+		assert_eq!(diff_entries(&old, &new), vec![DiffEntry {
+			key: "(xy 0 0)".to_owned(),
+			change: Change::Added
+		}]);
+		assert_eq!(diff_entries(&new, &old), vec![DiffEntry {
+			key: "(xy 0 0)".to_owned(),
+			change: Change::Removed
+		}]);
+	}
+}
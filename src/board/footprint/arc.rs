@@ -1,15 +1,17 @@
+use super::stroke::{self, InvalidStroke, StrokeDef};
 use crate::{
 	board::Layer,
-	common::Point,
+	common::{Point, PointList},
 	deg,
-	internal::{option_tuple, option_unit, rename, tuple, tuple_or_default},
-	mm
+	internal::{option_tuple, option_unit, rename, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "fp_arc")]
 struct ArcDef {
 	#[serde(with = "rename::start")]
@@ -24,15 +26,18 @@ struct ArcDef {
 	#[serde(with = "option_tuple")]
 	angle: Option<deg>,
 
+	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
+
 	layer: Layer,
 
-	#[serde(with = "tuple")]
-	width: mm,
+	#[serde(with = "option_tuple")]
+	width: Option<mm>,
 
 	#[serde(with = "option_unit")]
 	locked: bool,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
+	#[serde(with = "tstamp", skip_serializing_if = "crate::skip_uuid")]
 	tstamp: Uuid
 }
 
@@ -42,7 +47,10 @@ pub(crate) enum InvalidArc {
 	MissingMidOrAngle,
 
 	#[error("Conflicting mid and angle definitions")]
-	ConflictingMidAndAngle
+	ConflictingMidAndAngle,
+
+	#[error(transparent)]
+	Stroke(#[from] InvalidStroke)
 }
 
 pub(crate) fn start_mid_from_start_end_angle(
@@ -60,13 +68,15 @@ impl TryFrom<ArcDef> for Arc {
 	type Error = InvalidArc;
 
 	fn try_from(def: ArcDef) -> Result<Self, InvalidArc> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
 		match (def.mid, def.angle) {
 			(Some(mid), None) => Ok(Self {
 				start: def.start,
 				mid,
 				end: def.end,
 				layer: def.layer,
-				width: def.width,
+				width,
+				stroke_type,
 				locked: def.locked,
 				tstamp: def.tstamp
 			}),
@@ -79,7 +89,8 @@ impl TryFrom<ArcDef> for Arc {
 					mid,
 					end: def.end,
 					layer: def.layer,
-					width: def.width,
+					width,
+					stroke_type,
 					locked: def.locked,
 					tstamp: def.tstamp
 				})
@@ -91,30 +102,51 @@ impl TryFrom<ArcDef> for Arc {
 	}
 }
 
+impl From<Arc> for ArcDef {
+	fn from(arc: Arc) -> Self {
+		let (stroke, width) = stroke::into_def(arc.width, arc.stroke_type);
+		Self {
+			start: arc.start,
+			mid: Some(arc.mid),
+			end: arc.end,
+			angle: None,
+			stroke,
+			layer: arc.layer,
+			width,
+			locked: arc.locked,
+			tstamp: arc.tstamp
+		}
+	}
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(try_from = "ArcDef", rename = "fp_arc")]
+#[serde(try_from = "ArcDef", into = "ArcDef")]
 pub struct Arc {
-	#[serde(with = "rename::start")]
 	pub start: Point,
 
-	#[serde(with = "rename::mid")]
 	pub mid: Point,
 
-	#[serde(with = "rename::end")]
 	pub end: Point,
 
 	pub layer: Layer,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "option_unit")]
+	/// See [`Line::stroke_type`](super::Line::stroke_type).
+	pub stroke_type: Option<StrokeType>,
+
 	pub locked: bool,
 
-	#[serde(with = "tuple", skip_serializing_if = "crate::skip_uuid")]
 	pub tstamp: Uuid
 }
 
+impl Arc {
+	/// Approximate the arc with a polyline of `segments` line segments.
+	pub fn to_polyline(&self, segments: usize) -> PointList {
+		PointList::arc_polyline(self.start, self.mid, self.end, segments)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -129,6 +161,7 @@ mod tests {
 			end: Point::new(8.9.mm(), -2.0.mm()),
 			layer: Layer::new("F.SilkS"),
 			width: 0.12.mm(),
+			stroke_type: None,
 			locked: false,
 			tstamp: Uuid::nil()
 		}
@@ -143,6 +176,7 @@ mod tests {
 			end: Point::new(8.9.mm(), -2.0.mm()),
 			layer: Layer::new("F.SilkS"),
 			width: 0.12.mm(),
+			stroke_type: None,
 			locked: false,
 			tstamp: Uuid::nil()
 		};
@@ -151,4 +185,19 @@ mod tests {
 			serde_sexpr::from_str(input).expect("Failed to parse input");
 		pretty_assertions::assert_eq!(parsed, value);
 	}
+
+	sexpr_test_case! {
+		name: arc_stroke,
+		input: r#"(fp_arc (start -1.4 -0.3) (mid -1.194975 -0.794975) (end -0.7 -1) (stroke (width 0.12) (type solid)) (layer "F.SilkS") (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Arc {
+			start: Point::new(-1.4.mm(), -0.3.mm()),
+			mid: Point::new(-1.194975.mm(), -0.794975.mm()),
+			end: Point::new(-0.7.mm(), -1.0.mm()),
+			layer: Layer::new("F.SilkS"),
+			width: 0.12.mm(),
+			stroke_type: Some(StrokeType::Solid),
+			locked: false,
+			tstamp: Uuid::nil()
+		}
+	}
 }
@@ -1,34 +1,102 @@
-use super::FillType;
+use super::{
+	stroke::{self, Fill, InvalidStroke, StrokeDef},
+	FillType
+};
 use crate::{
 	board::Layer,
 	common::Point,
-	internal::{option_tuple, option_unit, rename, tuple, tuple_or_default},
-	mm
+	internal::{option_tuple, option_unit, rename, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "fp_circle")]
-pub struct Circle {
+struct CircleDef {
 	#[serde(with = "rename::center")]
-	pub center: Point,
+	center: Point,
 
 	#[serde(with = "rename::end")]
+	end: Point,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke_fill: Option<Fill>,
+
+	layer: Layer,
+
+	#[serde(with = "option_tuple")]
+	width: Option<mm>,
+
+	#[serde(with = "option_tuple")]
+	fill: Option<FillType>,
+
+	#[serde(with = "option_unit")]
+	locked: bool,
+
+	#[serde(with = "tstamp", skip_serializing_if = "crate::skip_uuid")]
+	tstamp: Uuid
+}
+
+impl TryFrom<CircleDef> for Circle {
+	type Error = InvalidStroke;
+
+	fn try_from(def: CircleDef) -> Result<Self, InvalidStroke> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
+		Ok(Self {
+			center: def.center,
+			end: def.end,
+			layer: def.layer,
+			width,
+			stroke_type,
+			fill: stroke::from_fill_def(def.stroke_fill, def.fill),
+			locked: def.locked,
+			tstamp: def.tstamp
+		})
+	}
+}
+
+impl From<Circle> for CircleDef {
+	fn from(circle: Circle) -> Self {
+		let (stroke, width) = stroke::into_def(circle.width, circle.stroke_type);
+		let (stroke_fill, fill) =
+			stroke::into_fill_def(circle.fill, circle.stroke_type);
+		Self {
+			center: circle.center,
+			end: circle.end,
+			stroke,
+			stroke_fill,
+			layer: circle.layer,
+			width,
+			fill,
+			locked: circle.locked,
+			tstamp: circle.tstamp
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "CircleDef", into = "CircleDef")]
+pub struct Circle {
+	pub center: Point,
+
 	pub end: Point,
 
 	pub layer: Layer,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "option_tuple")]
+	/// See [`Line::stroke_type`](super::Line::stroke_type).
+	pub stroke_type: Option<StrokeType>,
+
 	pub fill: Option<FillType>,
 
-	#[serde(with = "option_unit")]
 	pub locked: bool,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
 	pub tstamp: Uuid
 }
 
@@ -45,6 +113,7 @@ mod tests {
 			end: Point::new(2.0.mm(), 2.0.mm()),
 			layer: Layer::new("F.SilkS"),
 			width: 0.12.mm(),
+			stroke_type: None,
 			fill: None,
 			locked: false,
 			tstamp: Uuid::nil()
@@ -59,6 +128,7 @@ mod tests {
 			end: Point::new(2.0.mm(), 2.0.mm()),
 			layer: Layer::new("F.SilkS"),
 			width: 0.12.mm(),
+			stroke_type: None,
 			fill: Some(FillType::Solid),
 			locked: false,
 			tstamp: Uuid::nil()
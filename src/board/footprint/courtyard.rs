@@ -0,0 +1,168 @@
+use super::{Footprint, FootprintContent};
+use crate::{
+	board::CanonicalLayer,
+	common::{point_list, Point, PointList}
+};
+
+fn is_courtyard(layer: &str) -> bool {
+	matches!(
+		CanonicalLayer::from_name(layer),
+		CanonicalLayer::FCrtYd | CanonicalLayer::BCrtYd
+	)
+}
+
+impl Footprint {
+	/// Return the courtyard outline of this footprint, relative to the
+	/// footprint. The outline is taken from a rectangle or polygon on the front
+	/// or back courtyard layer, or stitched together from the lines and arcs on
+	/// these layers, with arcs approximated by their chords. Returns `None` if
+	/// the footprint has no closed courtyard.
+	pub fn courtyard_polygon(&self) -> Option<PointList> {
+		let mut segments = Vec::new();
+		for content in &self.content {
+			match content {
+				FootprintContent::Rect(rect) if is_courtyard(&rect.layer.0) => {
+					let (start, end) = (rect.start, rect.end);
+					return Some(PointList::new(vec![
+						start,
+						Point::new(end.x, start.y),
+						end,
+						Point::new(start.x, end.y),
+					]));
+				},
+				FootprintContent::Poly(poly) if is_courtyard(&poly.layer.0) => {
+					return Some(poly.pts.clone());
+				},
+				FootprintContent::Line(line) if is_courtyard(&line.layer.0) => {
+					segments.push((line.start, line.end));
+				},
+				FootprintContent::Arc(arc) if is_courtyard(&arc.layer.0) => {
+					segments.push((arc.start, arc.mid));
+					segments.push((arc.mid, arc.end));
+				},
+				_ => {}
+			}
+		}
+		point_list::stitch(&segments)
+	}
+}
+
+/// Return `true` if the courtyards of the two footprints overlap once they
+/// are placed on the board. Footprints without a courtyard never overlap.
+pub fn footprints_overlap(a: &Footprint, b: &Footprint) -> bool {
+	let outline = |fp: &Footprint| -> Option<Vec<Point>> {
+		let courtyard = fp.courtyard_polygon()?;
+		Some(courtyard.iter().map(|p| fp.to_board(*p)).collect())
+	};
+	match (outline(a), outline(b)) {
+		(Some(a), Some(b)) => polygons_overlap(&a, &b),
+		_ => false
+	}
+}
+
+fn cross(o: Point, a: Point, b: Point) -> f32 {
+	let (ax, ay) = ((a.x - o.x).raw_value(), (a.y - o.y).raw_value());
+	let (bx, by) = ((b.x - o.x).raw_value(), (b.y - o.y).raw_value());
+	ax * by - ay * bx
+}
+
+/// Test whether two line segments properly cross each other. Segments that
+/// only touch are not considered to cross.
+fn segments_cross((a, b): (Point, Point), (c, d): (Point, Point)) -> bool {
+	let d1 = cross(c, d, a);
+	let d2 = cross(c, d, b);
+	let d3 = cross(a, b, c);
+	let d4 = cross(a, b, d);
+	d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+/// Test whether the point lies strictly inside the polygon, using the even-odd
+/// rule.
+fn contains(polygon: &[Point], p: Point) -> bool {
+	let mut inside = false;
+	for (a, b) in edges(polygon) {
+		if (a.y > p.y) != (b.y > p.y) {
+			let t = (p.y - a.y).raw_value() / (b.y - a.y).raw_value();
+			if p.x < a.x + t * (b.x - a.x) {
+				inside = !inside;
+			}
+		}
+	}
+	inside
+}
+
+fn edges(polygon: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+	polygon
+		.iter()
+		.zip(polygon.iter().cycle().skip(1))
+		.map(|(a, b)| (*a, *b))
+}
+
+fn polygons_overlap(a: &[Point], b: &[Point]) -> bool {
+	edges(a).any(|ea| edges(b).any(|eb| segments_cross(ea, eb)))
+		|| a.iter().any(|p| contains(b, *p))
+		|| b.iter().any(|p| contains(a, *p))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{board::footprint::Line, deg, Deg, Unit};
+	use uuid::Uuid;
+
+	fn courtyard(width: f32, height: f32) -> Footprint {
+		let (w, h) = (width / 2.0, height / 2.0);
+		let corners = [(-w, -h), (w, -h), (w, h), (-w, h)];
+		let mut builder = Footprint::builder("R_0603");
+		for i in 0..4 {
+			let (start, end) = (corners[i], corners[(i + 1) % 4]);
+			builder = builder.add_graphic(FootprintContent::Line(Line {
+				start: Point::new(start.0.mm(), start.1.mm()),
+				end: Point::new(end.0.mm(), end.1.mm()),
+				layer: crate::board::Layer::new("F.CrtYd"),
+				width: 0.05.mm(),
+				stroke_type: None,
+				locked: false,
+				tstamp: Uuid::nil()
+			}));
+		}
+		builder.build()
+	}
+
+	fn placed(width: f32, height: f32, x: f32, y: f32, rotation: deg) -> Footprint {
+		let mut fp = courtyard(width, height);
+		fp.place(Point::new(x.mm(), y.mm()), rotation);
+		fp
+	}
+
+	#[test]
+	fn courtyard_from_lines() {
+		let outline = courtyard(2.0, 1.0).courtyard_polygon().unwrap();
+		assert_eq!(outline.len(), 4);
+		assert!(Footprint::builder("empty")
+			.build()
+			.courtyard_polygon()
+			.is_none());
+	}
+
+	#[test]
+	fn separate_footprints() {
+		let a = placed(2.0, 1.0, 0.0, 0.0, 0.0.deg());
+		let b = placed(2.0, 1.0, 3.0, 0.0, 0.0.deg());
+		assert!(!footprints_overlap(&a, &b));
+
+		// rotated by 90°, the courtyard of b no longer reaches a
+		let b = placed(4.0, 1.0, 2.0, 0.0, 90.0.deg());
+		assert!(!footprints_overlap(&a, &b));
+	}
+
+	#[test]
+	fn overlapping_footprints() {
+		let a = placed(2.0, 1.0, 0.0, 0.0, 0.0.deg());
+		let b = placed(2.0, 1.0, 1.5, 0.25, 0.0.deg());
+		assert!(footprints_overlap(&a, &b));
+
+		let inner = placed(0.5, 0.5, 0.0, 0.0, 45.0.deg());
+		assert!(footprints_overlap(&a, &inner));
+	}
+}
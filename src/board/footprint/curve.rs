@@ -1,29 +1,92 @@
+use super::stroke::{self, InvalidStroke, StrokeDef};
 use crate::{
 	board::Layer,
 	common::PointList,
-	internal::{option_unit, tuple, tuple_or_default},
-	mm
+	internal::{option_tuple, option_unit, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "fp_curve")]
+struct CurveDef {
+	pts: PointList,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
+
+	layer: Layer,
+
+	#[serde(with = "option_tuple")]
+	width: Option<mm>,
+
+	#[serde(with = "option_unit")]
+	locked: bool,
+
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
+	tstamp: Uuid
+}
+
+impl TryFrom<CurveDef> for Curve {
+	type Error = InvalidStroke;
+
+	fn try_from(def: CurveDef) -> Result<Self, InvalidStroke> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
+		Ok(Self {
+			pts: def.pts,
+			layer: def.layer,
+			width,
+			stroke_type,
+			locked: def.locked,
+			tstamp: def.tstamp
+		})
+	}
+}
+
+impl From<Curve> for CurveDef {
+	fn from(curve: Curve) -> Self {
+		let (stroke, width) = stroke::into_def(curve.width, curve.stroke_type);
+		Self {
+			pts: curve.pts,
+			stroke,
+			layer: curve.layer,
+			width,
+			locked: curve.locked,
+			tstamp: curve.tstamp
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "CurveDef", into = "CurveDef")]
 pub struct Curve {
 	pub pts: PointList,
 
 	pub layer: Layer,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "option_unit")]
+	/// See [`Line::stroke_type`](super::Line::stroke_type).
+	pub stroke_type: Option<StrokeType>,
+
 	pub locked: bool,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
 	pub tstamp: Uuid
 }
 
+impl Curve {
+	/// Approximate the curve with a polyline of `segments` line segments.
+	pub fn to_polyline(&self, segments: usize) -> PointList {
+		self.pts.cubic_bezier_polyline(segments)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -41,6 +104,7 @@ mod tests {
 			]),
 			layer: Layer::new("F.SilkS"),
 			width: 0.12.mm(),
+			stroke_type: None,
 			locked: false,
 			tstamp: Uuid::nil()
 		}
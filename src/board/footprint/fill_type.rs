@@ -3,6 +3,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum FillType {
+	/// The primitives of custom pads are filled with `(fill yes)`.
+	#[serde(alias = "yes")]
 	Solid,
+	#[serde(alias = "no")]
 	None
 }
@@ -0,0 +1,261 @@
+use super::{add_angle, Footprint, FootprintContent};
+use crate::{
+	board::{graphic::GraphicItem, CanonicalLayer, Layer},
+	common::{Point, Position},
+	deg, Deg
+};
+
+/// Return the name of the layer on the other side of the board. Layers without
+/// a side, like `Edge.Cuts` or the `*.Cu` wildcard of pads, keep their name.
+fn flip_layer(name: &str) -> String {
+	let layer = CanonicalLayer::from_name(name);
+	let flipped = layer.flipped();
+	match flipped == layer {
+		true => name.to_owned(),
+		false => flipped.name()
+	}
+}
+
+fn flip(layer: &mut Layer) {
+	layer.0 = flip_layer(&layer.0);
+}
+
+fn mirror(point: &mut Point) {
+	point.x = -point.x;
+}
+
+fn mirror_all<'a>(points: impl IntoIterator<Item = &'a mut Point>) {
+	points.into_iter().for_each(mirror);
+}
+
+/// Mirror a position relative to the footprint. Its angle is stored as an
+/// absolute angle, so the rotation of the footprint has to be taken into
+/// account.
+fn mirror_position(position: &mut Position, rotation: f32) {
+	position.x = -position.x;
+	let angle = position.angle.map(deg::raw_value).unwrap_or(0.0);
+	position.angle = add_angle(None, 2.0 * rotation - angle);
+}
+
+/// Mirror a point in board coordinates along the y axis of the footprint.
+fn mirror_absolute(point: &mut Point, origin: Point, rotation: f32) {
+	let local = point.rotate_around(origin, rotation.deg());
+	let mirrored = Point::new(2.0 * origin.x - local.x, local.y);
+	*point = mirrored.rotate_around(origin, -rotation.deg());
+}
+
+/// Mirror the graphic primitives of a custom pad, relative to the pad.
+fn mirror_primitive(item: &mut GraphicItem) {
+	match item {
+		GraphicItem::Arc(arc) => {
+			mirror_all([&mut arc.start, &mut arc.mid, &mut arc.end])
+		},
+		GraphicItem::Circle(circle) => {
+			mirror_all([&mut circle.center, &mut circle.end])
+		},
+		GraphicItem::Curve(curve) => mirror_all(curve.pts.iter_mut()),
+		GraphicItem::Line(line) => {
+			mirror_all([&mut line.start, &mut line.end]);
+			line.angle = line.angle.map(|angle| -angle);
+		},
+		GraphicItem::Poly(poly) => mirror_all(poly.pts.iter_mut()),
+		GraphicItem::Rect(rect) => mirror_all([&mut rect.start, &mut rect.end]),
+		GraphicItem::Text(text) => mirror_position(&mut text.position, 0.0),
+		GraphicItem::Dimension(_) => {}
+	}
+}
+
+impl Footprint {
+	/// Flip the footprint to the other side of the board. All items are mirrored
+	/// along the y axis of the footprint and moved to the layer on the other side,
+	/// e.g. from `F.Cu` to `B.Cu`. Texts are mirrored as well. The position of the
+	/// footprint and its 3D models are not changed.
+	pub fn flip(&mut self) {
+		let rotation = self
+			.position
+			.and_then(|pos| pos.angle)
+			.map(deg::raw_value)
+			.unwrap_or(0.0);
+		flip(&mut self.layer);
+
+		for content in &mut self.content {
+			match content {
+				FootprintContent::Text(text) => {
+					mirror_position(&mut text.position, rotation);
+					text.effects.justify.mirror = !text.effects.justify.mirror;
+				},
+				FootprintContent::Line(line) => {
+					mirror_all([&mut line.start, &mut line.end])
+				},
+				FootprintContent::Rect(rect) => {
+					mirror_all([&mut rect.start, &mut rect.end])
+				},
+				FootprintContent::Circle(circle) => {
+					mirror_all([&mut circle.center, &mut circle.end])
+				},
+				FootprintContent::Arc(arc) => {
+					mirror_all([&mut arc.start, &mut arc.mid, &mut arc.end])
+				},
+				FootprintContent::Poly(poly) => mirror_all(poly.pts.iter_mut()),
+				FootprintContent::Curve(curve) => mirror_all(curve.pts.iter_mut()),
+				FootprintContent::Pad(pad) => {
+					mirror_position(&mut pad.position, rotation);
+					if let Some(offset) =
+						pad.drill.as_mut().and_then(|d| d.offset.as_mut())
+					{
+						mirror(offset);
+					}
+					if let Some(primitives) = pad.custom_pad_primitives.as_mut() {
+						primitives.items.iter_mut().for_each(mirror_primitive);
+					}
+					for layer in &mut pad.layers {
+						*layer = flip_layer(layer);
+					}
+				},
+				FootprintContent::Property(prop) => {
+					if let Some(position) = prop.position.as_mut() {
+						mirror_position(position, rotation);
+					}
+				},
+				FootprintContent::Zone(zone) => {
+					// unlike all other items, zones are stored in board coordinates
+					let origin =
+						self.position.unwrap_or_else(Position::origin).point();
+					let outline = zone.polygon.pts.pts.iter_mut();
+					for point in outline.chain(
+						zone.filled_polygons
+							.iter_mut()
+							.flat_map(|filled| filled.pts.pts.iter_mut())
+					) {
+						mirror_absolute(point, origin, rotation);
+					}
+					for layer in zone.layers.iter_mut().flatten() {
+						*layer = flip_layer(layer);
+					}
+					for filled in &mut zone.filled_polygons {
+						flip(&mut filled.layer);
+					}
+				},
+				FootprintContent::Model(_) | FootprintContent::EmbeddedFiles(_) => {}
+			}
+			if let Some(layer) = content.layer_mut() {
+				flip(layer);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		board::{
+			footprint::{Pad, PadShape, PadSize, PadType},
+			pcb::Property
+		},
+		Deg, Unit
+	};
+	use uuid::Uuid;
+
+	fn pad(x: f32, angle: Option<f32>) -> Pad {
+		Pad::new(
+			"1",
+			PadType::Smd,
+			PadShape::Rect,
+			Position {
+				x: x.mm(),
+				y: 0.5.mm(),
+				angle: angle.map(Deg::deg)
+			},
+			PadSize::new(1.0.mm(), 0.5.mm()),
+			vec!["F.Cu".to_owned(), "F.Paste".to_owned(), "F.Mask".to_owned()],
+			Uuid::nil()
+		)
+	}
+
+	#[test]
+	fn flip_pad() {
+		let mut fp = Footprint::builder("R_0603")
+			.add_pad(pad(0.8, None))
+			.reference("R1")
+			.build();
+		fp.flip();
+
+		assert_eq!(fp.layer, Layer::new("B.Cu"));
+		let pad = fp.pads().next().unwrap();
+		assert_eq!(pad.layers, ["B.Cu", "B.Paste", "B.Mask"]);
+		assert_eq!(pad.position.point(), Point::new(-0.8.mm(), 0.5.mm()));
+		match &fp.content[1] {
+			FootprintContent::Text(text) => {
+				assert_eq!(text.layer, Layer::new("B.SilkS"));
+				assert!(text.effects.justify.mirror);
+			},
+			content => panic!("unexpected content {content:?}")
+		}
+
+		fp.flip();
+		assert_eq!(fp.layer, Layer::new("F.Cu"));
+		assert_eq!(fp.pads().next().unwrap().position.x, 0.8.mm());
+	}
+
+	#[test]
+	fn flip_rotated_pad() {
+		let mut fp = Footprint::builder("R_0603")
+			.add_pad(pad(0.8, Some(120.0)))
+			.build();
+		fp.place(Point::new(10.0.mm(), 10.0.mm()), 90.0.deg());
+		fp.flip();
+
+		// the pad is rotated by 120° relative to the footprint, which becomes -120°
+		let pad = fp.pads().next().unwrap();
+		assert_eq!(pad.position.angle, Some((-30.0).deg()));
+	}
+
+	#[test]
+	fn flip_property() {
+		let mut fp = Footprint::builder("R_0603").build();
+		fp.content.push(FootprintContent::Property(Property {
+			key: "Reference".to_owned(),
+			value: "R1".to_owned(),
+			position: Some(Position::new(1.0.mm(), -1.5.mm())),
+			layer: Some(Layer::new("F.SilkS")),
+			..Default::default()
+		}));
+		fp.flip();
+
+		match &fp.content[0] {
+			FootprintContent::Property(prop) => {
+				assert_eq!(prop.layer, Some(Layer::new("B.SilkS")));
+				assert_eq!(
+					prop.position.map(|position| position.point()),
+					Some(Point::new(-1.0.mm(), -1.5.mm()))
+				);
+			},
+			content => panic!("unexpected content {content:?}")
+		}
+	}
+
+	#[test]
+	fn flip_zone() {
+		let zone = r#"(zone (net 0) (net_name "") (layer "F.Cu") (tstamp 1238ac67-6c22-4228-9fa4-3def85aae58e) (hatch full 0.508) (connect_pads (clearance 0)) (min_thickness 0.254) (filled_areas_thickness no) (keepout (tracks allowed) (vias not_allowed) (pads allowed) (copperpour not_allowed) (footprints not_allowed)) (fill (thermal_gap 0.508) (thermal_bridge_width 0.508)) (polygon (pts (xy 12 1) (xy 11 1) (xy 11 2))))"#;
+		let mut fp = Footprint::builder("USB_C").build();
+		fp.place(Point::new(10.0.mm(), 0.0.mm()), 90.0.deg());
+		fp.content.push(serde_sexpr::from_str(zone).unwrap());
+		fp.flip();
+
+		match &fp.content[0] {
+			FootprintContent::Zone(zone) => {
+				assert_eq!(zone.layer, Some(Layer::new("B.Cu")));
+				// the y axis of the rotated footprint is parallel to the x axis
+				let pts = zone.polygon.pts.pts.iter();
+				let pts = pts.map(|pt| pt.round_nm_precision());
+				assert_eq!(pts.collect::<Vec<_>>(), [
+					Point::new(12.0.mm(), -1.0.mm()),
+					Point::new(11.0.mm(), -1.0.mm()),
+					Point::new(11.0.mm(), -2.0.mm())
+				]);
+			},
+			content => panic!("unexpected content {content:?}")
+		}
+	}
+}
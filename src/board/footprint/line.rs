@@ -1,30 +1,87 @@
+use super::stroke::{self, InvalidStroke, StrokeDef};
 use crate::{
 	board::Layer,
 	common::Point,
-	internal::{option_unit, rename, tuple, tuple_or_default},
-	mm
+	internal::{option_tuple, option_unit, rename, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "fp_line")]
-pub struct Line {
+struct LineDef {
 	#[serde(with = "rename::start")]
-	pub start: Point,
+	start: Point,
 
 	#[serde(with = "rename::end")]
+	end: Point,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
+
+	layer: Layer,
+
+	#[serde(with = "option_tuple")]
+	width: Option<mm>,
+
+	#[serde(with = "option_unit")]
+	locked: bool,
+
+	#[serde(with = "tstamp", skip_serializing_if = "crate::skip_uuid")]
+	tstamp: Uuid
+}
+
+impl TryFrom<LineDef> for Line {
+	type Error = InvalidStroke;
+
+	fn try_from(def: LineDef) -> Result<Self, InvalidStroke> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
+		Ok(Self {
+			start: def.start,
+			end: def.end,
+			layer: def.layer,
+			width,
+			stroke_type,
+			locked: def.locked,
+			tstamp: def.tstamp
+		})
+	}
+}
+
+impl From<Line> for LineDef {
+	fn from(line: Line) -> Self {
+		let (stroke, width) = stroke::into_def(line.width, line.stroke_type);
+		Self {
+			start: line.start,
+			end: line.end,
+			stroke,
+			layer: line.layer,
+			width,
+			locked: line.locked,
+			tstamp: line.tstamp
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "LineDef", into = "LineDef")]
+pub struct Line {
+	pub start: Point,
+
 	pub end: Point,
 
 	pub layer: Layer,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "option_unit")]
+	/// The stroke type of KiCad 7 footprints. If this is `None`, the width is
+	/// written in the `(width ..)` form of earlier versions.
+	pub stroke_type: Option<StrokeType>,
+
 	pub locked: bool,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
 	pub tstamp: Uuid
 }
 
@@ -41,6 +98,7 @@ mod tests {
 			end: Point::new(2.0.mm(), 0.0.mm()),
 			layer: Layer::new("F.SilkS"),
 			width: 0.12.mm(),
+			stroke_type: None,
 			locked: false,
 			tstamp: Uuid::nil()
 		}
@@ -54,8 +112,23 @@ mod tests {
 			end: Point::new(2.0.mm(), 0.0.mm()),
 			layer: Layer::new("F.SilkS"),
 			width: 0.12.mm(),
+			stroke_type: None,
 			locked: true,
 			tstamp: Uuid::nil()
 		}
 	}
+
+	sexpr_test_case! {
+		name: line_stroke,
+		input: r#"(fp_line (start -4.75 -5.37) (end -4.75 1.89) (stroke (width 0.12) (type solid)) (layer "F.SilkS") (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Line {
+			start: Point::new(-4.75.mm(), -5.37.mm()),
+			end: Point::new(-4.75.mm(), 1.89.mm()),
+			layer: Layer::new("F.SilkS"),
+			width: 0.12.mm(),
+			stroke_type: Some(StrokeType::Solid),
+			locked: false,
+			tstamp: Uuid::nil()
+		}
+	}
 }
@@ -1,41 +1,59 @@
-use super::{footprint_module::FootprintModule, ConnectPads, Layer, Timestamp};
-use crate::{common::Position, internal::option_tuple, mm};
-use serde::{Deserialize, Serialize};
+use super::{
+	footprint_module::FootprintModule, pcb::Property, ConnectPads, Layer, Timestamp,
+	UuidRemap, Zone
+};
+#[cfg(feature = "json")]
+use crate::internal::json;
+use crate::{
+	common::{Effects, EmbeddedFiles, Font, Point, Position},
+	deg,
+	error::from_node,
+	internal::{option_tuple, scanner, tstamp},
+	mm,
+	symbol::StrokeType,
+	Deg, Unit
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use uuid::Uuid;
 
 pub(super) mod arc;
 mod attributes;
 mod circle;
+mod courtyard;
 mod curve;
 mod fill_type;
+mod flip;
 mod line;
 mod model;
 mod pad;
 mod poly;
 mod primitives;
 mod rect;
+pub(crate) mod stroke;
 mod text;
 
 pub use arc::Arc;
 pub use attributes::{Attributes, FootprintType};
 pub use circle::Circle;
+pub use courtyard::footprints_overlap;
 pub use curve::Curve;
 pub use fill_type::FillType;
 pub use line::Line;
 pub use model::{Model, Xyz};
 pub use pad::{
-	Pad, PadAnchor, PadClearanceType, PadDrill, PadOptions, PadShape, PadSize,
-	PadType
+	Pad, PadAnchor, PadClearanceType, PadDrill, PadNumbering, PadOptions, PadShape,
+	PadSize, PadType
 };
 pub use poly::Polygon;
 pub use primitives::Primitives;
 pub use rect::Rectangle;
-use std::str::FromStr;
-pub use text::Text;
+use std::{borrow::Cow, str::FromStr};
+pub use text::{Text, TextType};
 
 serde_sexpr::untagged! {
 	#[derive(Clone, Debug, PartialEq)]
 	pub enum FootprintContent {
+		Property(Property),
 		Text(Text),
 		Line(Line),
 		Rect(Rectangle),
@@ -44,7 +62,62 @@ serde_sexpr::untagged! {
 		Poly(Polygon),
 		Curve(Curve),
 		Pad(Pad),
-		Model(Model)
+		Model(Model),
+		Zone(Zone),
+		EmbeddedFiles(EmbeddedFiles)
+	}
+}
+
+impl FootprintContent {
+	/// Return the unique identifier of this item, if it has one.
+	pub fn tstamp_mut(&mut self) -> Option<&mut Uuid> {
+		match self {
+			Self::Text(text) => Some(&mut text.tstamp),
+			Self::Line(line) => Some(&mut line.tstamp),
+			Self::Rect(rect) => Some(&mut rect.tstamp),
+			Self::Circle(circle) => Some(&mut circle.tstamp),
+			Self::Arc(arc) => Some(&mut arc.tstamp),
+			Self::Poly(poly) => Some(&mut poly.tstamp),
+			Self::Curve(curve) => Some(&mut curve.tstamp),
+			Self::Pad(pad) => Some(&mut pad.tstamp),
+			Self::Zone(zone) => Some(&mut zone.tstamp),
+			Self::Property(_) | Self::Model(_) | Self::EmbeddedFiles(_) => None
+		}
+	}
+
+	/// Return the layer of this item, if it is placed on a single layer.
+	pub fn layer_mut(&mut self) -> Option<&mut Layer> {
+		match self {
+			Self::Text(text) => Some(&mut text.layer),
+			Self::Line(line) => Some(&mut line.layer),
+			Self::Rect(rect) => Some(&mut rect.layer),
+			Self::Circle(circle) => Some(&mut circle.layer),
+			Self::Arc(arc) => Some(&mut arc.layer),
+			Self::Poly(poly) => Some(&mut poly.layer),
+			Self::Curve(curve) => Some(&mut curve.layer),
+			Self::Property(prop) => prop.layer.as_mut(),
+			Self::Zone(zone) => zone.layer.as_mut(),
+			Self::Pad(_) | Self::Model(_) | Self::EmbeddedFiles(_) => None
+		}
+	}
+
+	/// Return the stroke type of this item, if it is a graphic drawn with a
+	/// stroke.
+	pub fn stroke_type_mut(&mut self) -> Option<&mut Option<StrokeType>> {
+		match self {
+			Self::Line(line) => Some(&mut line.stroke_type),
+			Self::Rect(rect) => Some(&mut rect.stroke_type),
+			Self::Circle(circle) => Some(&mut circle.stroke_type),
+			Self::Arc(arc) => Some(&mut arc.stroke_type),
+			Self::Poly(poly) => Some(&mut poly.stroke_type),
+			Self::Curve(curve) => Some(&mut curve.stroke_type),
+			Self::Property(_)
+			| Self::Text(_)
+			| Self::Pad(_)
+			| Self::Model(_)
+			| Self::Zone(_)
+			| Self::EmbeddedFiles(_) => None
+		}
 	}
 }
 
@@ -78,6 +151,11 @@ pub struct Footprint {
 	#[serde(with = "option_tuple")]
 	pub generator: Option<String>,
 
+	/// The version of the program that generated the file, e.g. `8.0`. Only
+	/// written by KiCad 8 and later.
+	#[serde(with = "option_tuple")]
+	pub generator_version: Option<String>,
+
 	/// Defines a flag to indicate the footprint cannot be edited.
 	pub locked: bool,
 
@@ -87,12 +165,14 @@ pub struct Footprint {
 	/// Defines the canonical layer the footprint is placed.
 	pub layer: Layer,
 
-	/// Defines a the last time the footprint was edited.
-	pub tedit: Timestamp,
+	/// Defines a the last time the footprint was edited. No longer written by
+	/// KiCad 7 and later.
+	#[serde(with = "serde_sexpr::Option")]
+	pub tedit: Option<Timestamp>,
 
 	/// Defines the unique identifier for the footprint. This only applies to
 	/// footprints defined in the board file format.
-	#[serde(with = "option_tuple")]
+	#[serde(with = "tstamp::option")]
 	pub tstamp: Option<Uuid>,
 
 	/// The X and Y coordinates and rotational angle of the footprint. This only
@@ -161,10 +241,81 @@ pub struct Footprint {
 	/// Defines the attributes of the footprint.
 	pub attributes: Attributes,
 
+	/// The non-copper layers that are private to the footprint library, i.e.
+	/// hidden when the footprint is placed on a board. Only written by KiCad 7
+	/// and later.
+	#[serde(
+		deserialize_with = "deserialize_private_layers",
+		serialize_with = "serialize_private_layers"
+	)]
+	pub private_layers: Vec<Layer>,
+
+	/// The groups of pads that are allowed to short different nets, for net-tie
+	/// footprints. Only written by KiCad 7 and later.
+	#[serde(
+		deserialize_with = "deserialize_net_tie_pad_groups",
+		serialize_with = "serialize_net_tie_pad_groups"
+	)]
+	pub net_tie_pad_groups: Vec<Vec<String>>,
+
 	#[serde(default, rename = "")]
 	pub content: Vec<FootprintContent>
 }
 
+fn deserialize_private_layers<'de, D>(
+	deserializer: D
+) -> Result<Vec<Layer>, D::Error>
+where
+	D: Deserializer<'de>
+{
+	let layers: Option<Vec<String>> =
+		serde_sexpr::Option::deserialize(deserializer)?;
+	Ok(layers.into_iter().flatten().map(Layer).collect())
+}
+
+fn serialize_private_layers<S>(
+	layers: &[Layer],
+	serializer: S
+) -> Result<S::Ok, S::Error>
+where
+	S: Serializer
+{
+	let names: Vec<&str> = layers.iter().map(|layer| layer.0.as_str()).collect();
+	let names = (!names.is_empty()).then_some(names);
+	serde_sexpr::Option::serialize(&names, serializer)
+}
+
+/// Pad groups are written as a list of strings, each containing the names of the
+/// pads of one group separated by commas, e.g. `(net_tie_pad_groups "1,2" "3,4")`.
+fn deserialize_net_tie_pad_groups<'de, D>(
+	deserializer: D
+) -> Result<Vec<Vec<String>>, D::Error>
+where
+	D: Deserializer<'de>
+{
+	let groups: Option<Vec<String>> =
+		serde_sexpr::Option::deserialize(deserializer)?;
+	Ok(groups
+		.into_iter()
+		.flatten()
+		.map(|group| {
+			group.split(',').map(|pad| pad.trim().to_owned()).collect()
+		})
+		.collect())
+}
+
+fn serialize_net_tie_pad_groups<S>(
+	groups: &[Vec<String>],
+	serializer: S
+) -> Result<S::Ok, S::Error>
+where
+	S: Serializer
+{
+	let groups: Vec<String> = groups.iter().map(|group| group.join(",")).collect();
+	let groups = (!groups.is_empty()).then_some(groups);
+	serde_sexpr::Option::serialize(&groups, serializer)
+}
+
 serde_sexpr::untagged! {
 	enum FootprintOrModule {
 		Footprint(Footprint),
@@ -172,11 +323,478 @@ serde_sexpr::untagged! {
 	}
 }
 
+impl Footprint {
+	pub fn builder<T>(library_link: T) -> FootprintBuilder
+	where
+		T: Into<String>
+	{
+		FootprintBuilder::new(library_link)
+	}
+
+	pub fn add_pad(&mut self, pad: Pad) {
+		self.content.push(FootprintContent::Pad(pad));
+	}
+
+	pub fn add_graphic(&mut self, item: FootprintContent) {
+		self.content.push(item);
+	}
+
+	/// Move the footprint to `at` and rotate it to `rotation`. The geometry of the
+	/// footprint is stored relative to the footprint, so only the rotation of pads
+	/// and texts, which is always stored as an absolute angle, has to be updated.
+	pub fn place(&mut self, at: Point, rotation: deg) {
+		let old = self.position.and_then(|pos| pos.angle);
+		let delta = rotation.raw_value() - old.map(deg::raw_value).unwrap_or(0.0);
+		self.position = Some(Position {
+			x: at.x,
+			y: at.y,
+			angle: add_angle(None, rotation.raw_value())
+		});
+
+		for content in &mut self.content {
+			let position = match content {
+				FootprintContent::Pad(pad) => &mut pad.position,
+				FootprintContent::Text(text) => &mut text.position,
+				FootprintContent::Property(prop) => match prop.position.as_mut() {
+					Some(position) => position,
+					None => continue
+				},
+				_ => continue
+			};
+			position.angle = add_angle(position.angle, delta);
+		}
+	}
+
+	/// Convert a point relative to the footprint into absolute board coordinates.
+	pub fn to_board(&self, local: Point) -> Point {
+		let origin = self.position.unwrap_or_else(Position::origin);
+		let offset = Point::new(origin.x + local.x, origin.y + local.y);
+		match origin.angle {
+			// KiCad's y axis points downwards, so positive angles rotate clockwise
+			// in the mathematical sense
+			Some(angle) => offset.rotate_around(origin.point(), -angle),
+			None => offset
+		}
+	}
+
+	/// Return the value of the property with the given key.
+	pub fn get_property(&self, key: &str) -> Option<&str> {
+		self.content.iter().find_map(|c| match c {
+			FootprintContent::Property(prop) if prop.key == key => {
+				Some(prop.value.as_str())
+			},
+			_ => None
+		})
+	}
+
+	/// Update the value of the property with the given key, or add a new property
+	/// after the existing ones if there is none.
+	pub fn set_property<K, V>(&mut self, key: K, value: V)
+	where
+		K: Into<String>,
+		V: Into<String>
+	{
+		let key = key.into();
+		let value = value.into();
+		let existing = self.content.iter_mut().find_map(|c| match c {
+			FootprintContent::Property(prop) if prop.key == key => Some(prop),
+			_ => None
+		});
+		if let Some(prop) = existing {
+			prop.value = value;
+			return;
+		}
+
+		let idx = self
+			.content
+			.iter()
+			.rposition(|c| matches!(c, FootprintContent::Property(_)))
+			.map(|idx| idx + 1)
+			.unwrap_or(0);
+		self.content.insert(
+			idx,
+			FootprintContent::Property(Property {
+				key,
+				value,
+				..Default::default()
+			})
+		);
+	}
+
+	/// Remove the property with the given key, returning it if it existed.
+	pub fn remove_property(&mut self, key: &str) -> Option<Property> {
+		let idx = self.content.iter().position(
+			|c| matches!(c, FootprintContent::Property(prop) if prop.key == key)
+		)?;
+		match self.content.remove(idx) {
+			FootprintContent::Property(prop) => Some(prop),
+			_ => unreachable!()
+		}
+	}
+
+	/// Return the reference designator, taken from the reference text or, for
+	/// newer files, the `Reference` property.
+	pub fn reference(&self) -> Option<&str> {
+		self.content
+			.iter()
+			.find_map(|c| match c {
+				FootprintContent::Text(text) if text.ty == TextType::Reference => {
+					Some(text.text.as_str())
+				},
+				_ => None
+			})
+			.or_else(|| self.get_property("Reference"))
+	}
+
+	/// Return all pads of this footprint.
+	pub fn pads(&self) -> impl Iterator<Item = &Pad> {
+		self.content.iter().filter_map(|c| match c {
+			FootprintContent::Pad(pad) => Some(pad),
+			_ => None
+		})
+	}
+
+	/// Return all pads of this footprint for editing.
+	pub fn pads_mut(&mut self) -> impl Iterator<Item = &mut Pad> {
+		self.content.iter_mut().filter_map(|c| match c {
+			FootprintContent::Pad(pad) => Some(pad),
+			_ => None
+		})
+	}
+
+	/// Return the first pad with the given name, e.g. `"1"`. Use [`pads`] to
+	/// find all pads sharing a name, like the parts of a thermal pad.
+	///
+	/// [`pads`]: Self::pads
+	pub fn pad(&self, name: &str) -> Option<&Pad> {
+		self.pads().find(|pad| pad.number == name)
+	}
+
+	/// Connect all pads with the given name to a net, returning the number of
+	/// pads that were updated.
+	pub fn assign_net<N>(
+		&mut self,
+		pad_name: &str,
+		net_number: u32,
+		net_name: N
+	) -> usize
+	where
+		N: Into<String>
+	{
+		let net_name = net_name.into();
+		let mut count = 0;
+		for pad in self.pads_mut().filter(|pad| pad.number == pad_name) {
+			pad.net = Some((net_number, net_name.clone()));
+			count += 1;
+		}
+		count
+	}
+
+	/// Add a grid of `count` (columns, rows) pads based on `template`, with the
+	/// first pad at `start` and the others spaced `pitch` (x, y) apart. The pads
+	/// are numbered from 1 in [`PadNumbering::RowMajor`] order and receive fresh
+	/// unique identifiers.
+	pub fn add_pad_array(
+		&mut self,
+		start: Point,
+		pitch: (mm, mm),
+		count: (u32, u32),
+		template: Pad
+	) {
+		let numbering = PadNumbering::default();
+		self.add_pad_array_with(start, pitch, count, template, numbering);
+	}
+
+	/// Like [`add_pad_array`](Self::add_pad_array), but numbering the pads in the
+	/// order given by `numbering`.
+	pub fn add_pad_array_with(
+		&mut self,
+		start: Point,
+		pitch: (mm, mm),
+		count: (u32, u32),
+		template: Pad,
+		numbering: PadNumbering
+	) {
+		let (columns, rows) = count;
+		let mut pads = Vec::new();
+		for row in 0 .. rows {
+			for column in 0 .. columns {
+				let number = match numbering {
+					PadNumbering::RowMajor => row * columns + column + 1,
+					PadNumbering::ColumnMajor => column * rows + row + 1
+				};
+				let mut pad = template.clone();
+				pad.number = number.to_string();
+				pad.position.x = start.x + pitch.0 * column as f32;
+				pad.position.y = start.y + pitch.1 * row as f32;
+				pad.tstamp = Uuid::new_v4();
+				pads.push((number, pad));
+			}
+		}
+		pads.sort_by_key(|(number, _)| *number);
+		self.content
+			.extend(pads.into_iter().map(|(_, pad)| FootprintContent::Pad(pad)));
+	}
+
+	/// Set the text of the reference designator, adding a reference text on the
+	/// front silkscreen if the footprint does not have one yet.
+	pub fn set_reference<T>(&mut self, reference: T)
+	where
+		T: Into<String>
+	{
+		let reference = reference.into();
+		let existing = self.content.iter_mut().find_map(|c| match c {
+			FootprintContent::Text(text) if text.ty == TextType::Reference => {
+				Some(text)
+			},
+			_ => None
+		});
+		match existing {
+			Some(text) => {
+				text.text = reference;
+				// the cached outline shows the previous reference
+				text.render_cache = None;
+			},
+			None => self.content.push(FootprintContent::Text(Text {
+				ty: TextType::Reference,
+				text: reference,
+				position: Position::new(0.0.mm(), 0.0.mm()),
+				unlocked: false,
+				layer: Layer::new("F.SilkS"),
+				hide: false,
+				effects: Effects::new(Font::new(1.0.mm())),
+				tstamp: Uuid::new_v4(),
+				render_cache: None
+			}))
+		}
+	}
+
+	/// Move all graphic items and pads of this footprint from layer `from` to layer
+	/// `to`, returning the number of items changed.
+	pub fn remap_layer(&mut self, from: &str, to: &str) -> usize {
+		let mut changed = 0;
+		for content in &mut self.content {
+			if let FootprintContent::Pad(pad) = content {
+				if pad.layers.iter().any(|l| l == from) {
+					let has_to = pad.layers.iter().any(|l| l == to);
+					pad.layers.retain(|l| l != from || !has_to);
+					pad.layers.iter_mut().filter(|l| *l == from).for_each(|l| {
+						*l = to.to_owned();
+					});
+					changed += 1;
+				}
+			} else if let Some(layer) = content.layer_mut() {
+				if layer.0 == from {
+					layer.0 = to.to_owned();
+					changed += 1;
+				}
+			}
+		}
+		changed
+	}
+
+	/// Regenerate the unique identifiers of the footprint and all of its items.
+	pub(crate) fn regenerate_tstamps_with(&mut self, remap: &mut UuidRemap) {
+		if let Some(tstamp) = self.tstamp.as_mut() {
+			remap.regenerate(tstamp);
+		}
+		for tstamp in self
+			.content
+			.iter_mut()
+			.filter_map(FootprintContent::tstamp_mut)
+		{
+			remap.regenerate(tstamp);
+		}
+	}
+}
+
+fn add_angle(angle: Option<deg>, delta: f32) -> Option<deg> {
+	let angle = (angle.map(deg::raw_value).unwrap_or(0.0) + delta) % 360.0;
+	(angle != 0.0).then(|| angle.deg())
+}
+
+/// A builder to construct a [`Footprint`] programmatically.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FootprintBuilder {
+	footprint: Footprint
+}
+
+impl FootprintBuilder {
+	pub fn new<T>(library_link: T) -> Self
+	where
+		T: Into<String>
+	{
+		Self {
+			footprint: Footprint {
+				library_link: Some(library_link.into()),
+				version: Some(Version::default()),
+				generator: Some(env!("CARGO_PKG_NAME").to_owned()),
+				generator_version: None,
+				locked: false,
+				placed: false,
+				layer: Layer::new("F.Cu"),
+				tedit: Some(Timestamp(0)),
+				tstamp: Some(Uuid::new_v4()),
+				position: None,
+				description: None,
+				tags: None,
+				path: None,
+				autoplace_cost90: None,
+				autoplace_cost180: None,
+				solder_mask_margin: None,
+				solder_paste_margin: None,
+				solder_paste_ratio: None,
+				clearance: None,
+				zone_connect: None,
+				thermal_width: None,
+				thermal_gap: None,
+				attributes: Attributes {
+					ty: None,
+					board_only: false,
+					exclude_from_pos_files: false,
+					exclude_from_bom: false
+				},
+				private_layers: Vec::new(),
+				net_tie_pad_groups: Vec::new(),
+				content: Vec::new()
+			}
+		}
+	}
+
+	pub fn version(mut self, version: u32) -> Self {
+		self.footprint.version = Some(Version(version));
+		self
+	}
+
+	pub fn generator<T>(mut self, generator: T) -> Self
+	where
+		T: Into<String>
+	{
+		self.footprint.generator = Some(generator.into());
+		self
+	}
+
+	pub fn layer(mut self, layer: Layer) -> Self {
+		self.footprint.layer = layer;
+		self
+	}
+
+	pub fn tstamp(mut self, tstamp: Uuid) -> Self {
+		self.footprint.tstamp = Some(tstamp);
+		self
+	}
+
+	pub fn position(mut self, position: Position) -> Self {
+		self.footprint.position = Some(position);
+		self
+	}
+
+	pub fn description<T>(mut self, description: T) -> Self
+	where
+		T: Into<String>
+	{
+		self.footprint.description = Some(description.into());
+		self
+	}
+
+	pub fn tags<T>(mut self, tags: T) -> Self
+	where
+		T: Into<String>
+	{
+		self.footprint.tags = Some(tags.into());
+		self
+	}
+
+	pub fn attributes(mut self, attributes: Attributes) -> Self {
+		self.footprint.attributes = attributes;
+		self
+	}
+
+	pub fn reference<T>(mut self, reference: T) -> Self
+	where
+		T: Into<String>
+	{
+		self.footprint.set_reference(reference);
+		self
+	}
+
+	pub fn add_pad(mut self, pad: Pad) -> Self {
+		self.footprint.add_pad(pad);
+		self
+	}
+
+	pub fn add_graphic(mut self, item: FootprintContent) -> Self {
+		self.footprint.add_graphic(item);
+		self
+	}
+
+	pub fn build(self) -> Footprint {
+		self.footprint
+	}
+}
+
+/// The nodes of a footprint that precede its content.
+const HEADER_NODES: &[&str] = &[
+	"version",
+	"generator",
+	"generator_version",
+	"layer",
+	"tedit",
+	"tstamp",
+	"at",
+	"descr",
+	"tags",
+	"path",
+	"autoplace_cost90",
+	"autoplace_cost180",
+	"solder_mask_margin",
+	"solder_paste_margin",
+	"solder_paste_ratio",
+	"clearance",
+	"zone_connect",
+	"thermal_width",
+	"thermal_gap",
+	"attr",
+	"private_layers",
+	"net_tie_pad_groups"
+];
+
+/// KiCad writes the properties of a footprint in between its header nodes, e.g.
+/// before the `(path ..)` and `(attr ..)` of footprints placed on a board, but
+/// the header has to precede the content when deserializing. This moves all
+/// content after the header, keeping the order of both otherwise.
+pub(crate) fn content_after_header(
+	node: &str
+) -> Result<Cow<'_, str>, serde_sexpr::de::Error> {
+	let (name, elements) = scanner::elements(node)?;
+	let is_header = |element: &&scanner::Node<'_>| {
+		!element.text.starts_with('(') || HEADER_NODES.contains(&element.name)
+	};
+	let first_content = elements.iter().position(|element| !is_header(&element));
+	let in_order = first_content.is_none_or(|idx| {
+		!elements[idx..].iter().any(|element| is_header(&element))
+	});
+	if name != "footprint" || in_order {
+		return Ok(Cow::Borrowed(node));
+	}
+
+	let (header, content): (Vec<&scanner::Node<'_>>, Vec<_>) =
+		elements.iter().partition(is_header);
+	let mut out = format!("({name}");
+	for element in header.into_iter().chain(content) {
+		out.push(' ');
+		out.push_str(element.text);
+	}
+	out.push(')');
+	Ok(Cow::Owned(out))
+}
+
 impl FromStr for Footprint {
-	type Err = serde_sexpr::de::Error;
+	type Err = crate::Error;
 
-	fn from_str(s: &str) -> Result<Self, serde_sexpr::de::Error> {
-		let fp: FootprintOrModule = serde_sexpr::from_str(s)?;
+	fn from_str(s: &str) -> Result<Self, crate::Error> {
+		let s = scanner::strip_comments(s);
+		let fp: FootprintOrModule = from_node(&content_after_header(&s)?)?;
 		Ok(match fp {
 			FootprintOrModule::Footprint(fp) => fp,
 			FootprintOrModule::Module(m) => m.into()
@@ -184,6 +802,19 @@ impl FromStr for Footprint {
 	}
 }
 
+#[cfg(feature = "json")]
+impl Footprint {
+	/// Write this footprint as JSON.
+	pub fn to_json(&self) -> Result<String, serde_json::Error> {
+		serde_json::to_string(&json::to_value(self)?)
+	}
+
+	/// Parse a footprint from JSON written by [`Footprint::to_json`].
+	pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+		json::from_value(&serde_json::from_str(s)?)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -206,10 +837,11 @@ mod tests {
 			library_link: Some("MountingHole".to_owned()),
 			version: Some(Version(20211014)),
 			generator: Some("foobar".to_owned()),
+			generator_version: None,
 			locked: false,
 			placed: false,
 			layer: Layer::new("F.Cu"),
-			tedit: Timestamp(0xDEADBEEF),
+			tedit: Some(Timestamp(0xDEADBEEF)),
 			tstamp: None,
 			position: None,
 			description: Some("A mounting hole".to_owned()),
@@ -225,6 +857,8 @@ mod tests {
 			thermal_width: None,
 			thermal_gap: None,
 			attributes: Attributes::new_virtual(),
+			private_layers: Vec::new(),
+			net_tie_pad_groups: Vec::new(),
 			content: Vec::new()
 		};
 
@@ -233,6 +867,112 @@ mod tests {
 		assert_eq!(parsed, expected);
 	}
 
+	#[test]
+	fn net_tie() {
+		let input = r#"(footprint "NetTie-2_SMD_Pad0.5mm" (version 20221018) (generator pcbnew) (layer "F.Cu") (attr exclude_from_pos_files exclude_from_bom) (private_layers "F.SilkS" "User.1") (net_tie_pad_groups "1, 2" "3,4,5"))"#;
+		let parsed: Footprint = input.parse().unwrap();
+		assert_eq!(parsed.private_layers, vec![
+			Layer::new("F.SilkS"),
+			Layer::new("User.1")
+		]);
+		assert_eq!(parsed.net_tie_pad_groups, vec![vec!["1", "2"], vec![
+			"3", "4", "5"
+		]]);
+
+		let written = serde_sexpr::to_string(&parsed).unwrap();
+		assert!(written.contains(
+			r#"(attr exclude_from_pos_files exclude_from_bom) (private_layers "F.SilkS" "User.1") (net_tie_pad_groups "1,2" "3,4,5"))"#
+		), "{written}");
+		assert_eq!(written.parse::<Footprint>().unwrap(), parsed);
+
+		let plain = Footprint::builder("R_0603").build();
+		let written = serde_sexpr::to_string(&plain).unwrap();
+		assert!(!written.contains("private_layers"), "{written}");
+		assert!(!written.contains("net_tie_pad_groups"), "{written}");
+	}
+
+	#[test]
+	fn board_footprint() {
+		// copied from minnow.kicad_pcb, which was written by KiCad 7, with most
+		// of the render cache and properties removed
+		let input = r#"
+			(footprint "Jumper:SolderJumper-2_P1.3mm_Bridged_RoundedPad1.0x1.5mm" (layer "B.Cu")
+				(tstamp d0d93d44-c3c0-41e0-b99c-c610e31c240c)
+				(at 162.6 45.2)
+				(descr "SMD Solder Jumper, 1x1.5mm, rounded Pads, 0.3mm gap, bridged with 1 copper strip")
+				(tags "net tie solder jumper bridged")
+				(property "Config" "nofit")
+				(property "Sheetfile" "minnow.kicad_sch")
+				(property "ki_description" "Solder Jumper, 2-pole, closed/bridged")
+				(path "/ccc77ab0-d67c-4b4b-81ca-068a34c2d8d3")
+				(attr exclude_from_pos_files)
+				(net_tie_pad_groups "1, 2")
+				(fp_text reference "JP2" (at 0 -1.7) (layer "B.SilkS")
+						(effects (font (face "Hack") (size 0.6 0.6) (thickness 0.1)) (justify mirror))
+					(tstamp cf213f50-55ed-4952-aa12-fd2ff9d4100d)
+					(render_cache "JP2" 0
+						(polygon
+							(pts
+								(xy 163.152037 43.758378) (xy 163.160136 43.758304) (xy 163.168111 43.75808)
+							)
+						)
+					)
+				)
+				(fp_text value "SolderJumper_2_Bridged" (at 0 -1.9) (layer "B.Fab")
+						(effects (font (size 1 1) (thickness 0.15)) (justify mirror))
+					(tstamp d8982717-98cc-45f1-b0af-37150cb11172)
+				)
+				(fp_line (start -1.4 -0.3) (end -1.4 0.3)
+					(stroke (width 0.12) (type solid)) (layer "B.SilkS") (tstamp 2e059880-a1ad-44ef-a6b0-53ff9af6f371))
+				(fp_arc (start -1.4 -0.3) (mid -1.194975 -0.794975) (end -0.7 -1)
+					(stroke (width 0.12) (type solid)) (layer "B.SilkS") (tstamp abd1e3da-5dcc-4997-ada2-2edab6e257a8))
+				(pad "1" smd custom (at -0.65 0) (size 1 0.5) (layers "B.Cu" "B.Mask")
+					(net 36 "/DEV_TXD") (pinfunction "A") (pintype "passive") (zone_connect 2) (thermal_bridge_angle 45)
+					(options (clearance outline) (anchor rect))
+					(primitives
+						(gr_circle (center 0 -0.25) (end 0.5 -0.25) (width 0) (fill yes))
+						(gr_circle (center 0 0.25) (end 0.5 0.25) (width 0) (fill yes))
+						(gr_poly
+							(pts
+								(xy 0.5 -0.75)
+								(xy 0 -0.75)
+								(xy 0 0.75)
+								(xy 0.5 0.75)
+							)
+							(width 0) (fill yes))
+					) (tstamp 52358543-5d6f-48cf-99a6-7b3cca179b3a))
+			)
+		"#;
+		let parsed: Footprint = input.parse().unwrap();
+		assert_eq!(parsed.tedit, None);
+		assert_eq!(
+			parsed.path.as_deref(),
+			Some("/ccc77ab0-d67c-4b4b-81ca-068a34c2d8d3")
+		);
+		assert_eq!(parsed.net_tie_pad_groups, vec![vec!["1", "2"]]);
+		assert_eq!(parsed.get_property("Config"), Some("nofit"));
+		assert_eq!(parsed.content.len(), 8);
+		assert!(matches!(&parsed.content[3], FootprintContent::Text(text)
+			if text.render_cache.as_ref().is_some_and(|cache| cache.text == "JP2")));
+		assert!(parsed.pads().all(|pad| pad.custom_pad_primitives.is_some()));
+
+		let written = serde_sexpr::to_string(&parsed).unwrap();
+		assert!(!written.contains("tedit"), "{written}");
+		assert_eq!(written.parse::<Footprint>().unwrap(), parsed);
+	}
+
+	#[test]
+	fn generator_version() {
+		let input = r#"(footprint "MountingHole" (version 20240108) (generator "pcbnew") (generator_version "8.0") (layer "F.Cu") (attr exclude_from_pos_files exclude_from_bom))"#;
+		let parsed: Footprint = input.parse().unwrap();
+		assert_eq!(parsed.generator_version.as_deref(), Some("8.0"));
+		assert_eq!(parsed.tedit, None);
+		let written = serde_sexpr::to_string(&parsed).unwrap();
+		assert!(written.contains(
+			r#"(generator pcbnew) (generator_version "8.0") (layer "F.Cu") (attr"#
+		));
+	}
+
 	#[test]
 	fn empty_pcb_footprint() {
 		let input = r#"
@@ -250,10 +990,11 @@ mod tests {
 			library_link: Some("MountingHole:MountingHole".to_owned()),
 			version: None,
 			generator: None,
+			generator_version: None,
 			locked: false,
 			placed: false,
 			layer: Layer::new("F.Cu"),
-			tedit: Timestamp(0xDEADBEEF),
+			tedit: Some(Timestamp(0xDEADBEEF)),
 			tstamp: Some("931fb3d7-f50a-4517-80c8-bbc40990b0af".parse().unwrap()),
 			position: Some(Position::new(42.0.mm(), 42.0.mm())),
 			description: Some("A mounting hole".to_owned()),
@@ -269,6 +1010,8 @@ mod tests {
 			thermal_width: None,
 			thermal_gap: None,
 			attributes: Attributes::new_virtual(),
+			private_layers: Vec::new(),
+			net_tie_pad_groups: Vec::new(),
 			content: Vec::new()
 		};
 
@@ -276,4 +1019,246 @@ mod tests {
 			serde_sexpr::from_str(input).expect("Failed to parse input");
 		assert_eq!(parsed, expected);
 	}
+
+	#[test]
+	fn footprint_description_round_trip() {
+		let footprint = Footprint::builder("Capacitor_SMD:C_0603_1608Metric")
+			.tstamp(Uuid::nil())
+			.description("Capacitor SMD 0603 (1608 Metric), \"square\" end terminal")
+			.tags("capacitor")
+			.attributes(Attributes {
+				exclude_from_bom: true,
+				..Attributes::new(FootprintType::Smd)
+			})
+			.build();
+
+		let written = serde_sexpr::to_string(&footprint).unwrap();
+		assert!(written.contains("(attr smd exclude_from_bom)"), "{written}");
+		let parsed: Footprint = serde_sexpr::from_str(&written).unwrap();
+		assert_eq!(parsed, footprint);
+	}
+
+	#[test]
+	fn build_0402_footprint() {
+		let pad = |number: &str, x: f32| {
+			Pad::new(
+				number,
+				PadType::Smd,
+				PadShape::Rect,
+				Position::new(x.mm(), 0.0.mm()),
+				PadSize::new(0.56.mm(), 0.62.mm()),
+				vec!["F.Cu".to_owned(), "F.Paste".to_owned(), "F.Mask".to_owned()],
+				Uuid::nil()
+			)
+		};
+		let mut footprint = Footprint::builder("R_0402_1005Metric")
+			.attributes(Attributes::new(FootprintType::Smd))
+			.reference("R1")
+			.add_pad(pad("1", -0.48))
+			.add_pad(pad("2", 0.48))
+			.build();
+		assert!(footprint.tstamp.is_some());
+		assert_eq!(footprint.layer, Layer::new("F.Cu"));
+
+		footprint.set_reference("R2");
+		let references: Vec<_> = footprint
+			.content
+			.iter()
+			.filter_map(|c| match c {
+				FootprintContent::Text(text) => Some(text.text.as_str()),
+				_ => None
+			})
+			.collect();
+		assert_eq!(references, vec!["R2"]);
+
+		let written = serde_sexpr::to_string(&footprint).unwrap();
+		for (number, x) in [("1", "-0.48"), ("2", "0.48")] {
+			let expected = format!(
+				r#"(pad "{number}" smd rect (at {x} 0) (size 0.56 0.62) (layers "F.Cu" "F.Paste" "F.Mask")"#
+			);
+			assert!(written.contains(&expected), "{written}");
+		}
+	}
+
+	#[test]
+	fn add_pad_array() {
+		let template = Pad::new(
+			"",
+			PadType::ThroughHole,
+			PadShape::Circle,
+			Position::origin(),
+			PadSize::new(1.7.mm(), 1.7.mm()),
+			vec!["*.Cu".to_owned()],
+			Uuid::nil()
+		);
+		let pads = |footprint: &Footprint| -> Vec<(String, Point)> {
+			footprint
+				.pads()
+				.map(|pad| (pad.number.clone(), pad.position.point()))
+				.collect()
+		};
+		let point = |x: f32, y: f32| Point::new(x.mm(), y.mm());
+
+		let mut footprint = Footprint::builder("PinHeader_2x02").build();
+		footprint.add_pad_array(
+			point(0.0, 0.0),
+			(2.54.mm(), 2.54.mm()),
+			(2, 2),
+			template.clone()
+		);
+		assert_eq!(pads(&footprint), vec![
+			("1".to_owned(), point(0.0, 0.0)),
+			("2".to_owned(), point(2.54, 0.0)),
+			("3".to_owned(), point(0.0, 2.54)),
+			("4".to_owned(), point(2.54, 2.54))
+		]);
+		assert!(footprint.pads().all(|pad| !pad.tstamp.is_nil()));
+
+		let mut footprint = Footprint::builder("PinHeader_2x02").build();
+		footprint.add_pad_array_with(
+			point(1.0, 1.0),
+			(2.54.mm(), 2.54.mm()),
+			(2, 2),
+			template,
+			PadNumbering::ColumnMajor
+		);
+		assert_eq!(pads(&footprint), vec![
+			("1".to_owned(), point(1.0, 1.0)),
+			("2".to_owned(), point(1.0, 3.54)),
+			("3".to_owned(), point(3.54, 1.0)),
+			("4".to_owned(), point(3.54, 3.54))
+		]);
+	}
+
+	#[test]
+	fn assign_net() {
+		let pad = |number: &str, x: f32| {
+			Pad::new(
+				number,
+				PadType::Smd,
+				PadShape::Rect,
+				Position::new(x.mm(), 0.0.mm()),
+				PadSize::new(0.5.mm(), 0.5.mm()),
+				vec!["F.Cu".to_owned()],
+				Uuid::nil()
+			)
+		};
+		let mut footprint = Footprint::builder("SOT-23-3")
+			.add_pad(pad("1", -1.0))
+			.add_pad(pad("2", 0.0))
+			.add_pad(pad("3", 1.0))
+			.add_pad(pad("1", 2.0))
+			.build();
+		let numbers: Vec<_> =
+			footprint.pads().map(|pad| pad.number.as_str()).collect();
+		assert_eq!(numbers, ["1", "2", "3", "1"]);
+		assert_eq!(footprint.pad("3").unwrap().position.x, 1.0.mm());
+		assert!(footprint.pad("4").is_none());
+
+		assert_eq!(footprint.assign_net("1", 2, "GND"), 2);
+		assert_eq!(footprint.assign_net("4", 2, "GND"), 0);
+		let nets: Vec<_> = footprint.pads().map(|pad| pad.net.clone()).collect();
+		assert_eq!(nets, [
+			Some((2, "GND".to_owned())),
+			None,
+			None,
+			Some((2, "GND".to_owned()))
+		]);
+	}
+
+	#[test]
+	fn footprint_properties() {
+		let mut footprint = Footprint::builder("R_0402_1005Metric")
+			.reference("R1")
+			.build();
+		footprint.set_property("Sheetfile", "minnow.kicad_sch");
+		footprint.set_property("Sheetname", "");
+		footprint.set_property("Sheetfile", "other.kicad_sch");
+
+		let keys: Vec<_> = footprint
+			.content
+			.iter()
+			.filter_map(|c| match c {
+				FootprintContent::Property(prop) => Some(prop.key.as_str()),
+				_ => None
+			})
+			.collect();
+		assert_eq!(keys, vec!["Sheetfile", "Sheetname"]);
+		assert_eq!(footprint.get_property("Sheetfile"), Some("other.kicad_sch"));
+		assert!(matches!(footprint.content[2], FootprintContent::Text(_)));
+
+		assert!(footprint.remove_property("Sheetname").is_some());
+		assert_eq!(footprint.get_property("Sheetname"), None);
+	}
+
+	#[test]
+	fn place_footprint() {
+		let pad = Pad::new(
+			"1",
+			PadType::Smd,
+			PadShape::Rect,
+			Position::new_with_angle(1.0.mm(), 0.0.mm(), 90.0.deg()),
+			PadSize::new(0.5.mm(), 0.5.mm()),
+			vec!["F.Cu".to_owned()],
+			Uuid::nil()
+		);
+		let mut footprint = Footprint::builder("Test")
+			.reference("U1")
+			.add_pad(pad)
+			.build();
+		footprint.place(Point::new(10.0.mm(), 10.0.mm()), 90.0.deg());
+		assert_eq!(
+			footprint.position,
+			Some(Position::new_with_angle(10.0.mm(), 10.0.mm(), 90.0.deg()))
+		);
+
+		let (text, pad) = match footprint.content.as_slice() {
+			[FootprintContent::Text(text), FootprintContent::Pad(pad)] => {
+				(text, pad)
+			},
+			content => panic!("unexpected content {content:?}")
+		};
+		assert_eq!(text.position.angle, Some(90.0.deg()));
+		assert_eq!(pad.position.angle, Some(180.0.deg()));
+
+		let absolute = footprint
+			.to_board(pad.position.point())
+			.round_nm_precision();
+		assert_eq!(absolute, Point::new(10.0.mm(), 9.0.mm()));
+
+		// placing again replaces the rotation instead of adding to it
+		footprint.place(Point::new(0.0.mm(), 0.0.mm()), 0.0.deg());
+		assert_eq!(footprint.position, Some(Position::origin()));
+		let pad = match &footprint.content[1] {
+			FootprintContent::Pad(pad) => pad,
+			content => panic!("unexpected content {content:?}")
+		};
+		assert_eq!(pad.position.angle, Some(90.0.deg()));
+	}
+
+	#[test]
+	fn remap_footprint_layer() {
+		let pad = Pad::new(
+			"1",
+			PadType::Smd,
+			PadShape::Rect,
+			Position::new(0.0.mm(), 0.0.mm()),
+			PadSize::new(0.5.mm(), 0.5.mm()),
+			vec!["F.Cu".to_owned(), "F.Paste".to_owned(), "F.Mask".to_owned()],
+			Uuid::nil()
+		);
+		let mut footprint = Footprint::builder("Test")
+			.reference("U1")
+			.add_pad(pad)
+			.build();
+		assert_eq!(footprint.remap_layer("F.Paste", "F.Mask"), 1);
+		assert_eq!(footprint.remap_layer("F.SilkS", "F.Fab"), 1);
+		match footprint.content.as_slice() {
+			[FootprintContent::Text(text), FootprintContent::Pad(pad)] => {
+				assert_eq!(text.layer, Layer::new("F.Fab"));
+				assert_eq!(pad.layers, vec!["F.Cu".to_owned(), "F.Mask".to_owned()]);
+			},
+			content => panic!("unexpected content {content:?}")
+		}
+	}
 }
@@ -4,7 +4,7 @@ use crate::{
 	mm
 };
 use serde::{Deserialize, Serialize};
-use std::ops::Add;
+use std::ops::{Add, Mul, Sub};
 
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "xyz")]
@@ -20,6 +20,59 @@ impl<T> Xyz<T> {
 	}
 }
 
+impl<T> Xyz<T>
+where
+	T: Copy + Mul,
+	T::Output: Add<Output = T::Output> + Sub<Output = T::Output>
+{
+	/// Return the dot product of this and the other vector.
+	pub fn dot(self, rhs: Self) -> T::Output {
+		self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+	}
+
+	/// Return the cross product of this and the other vector.
+	pub fn cross(self, rhs: Self) -> Xyz<T::Output> {
+		Xyz {
+			x: self.y * rhs.z - self.z * rhs.y,
+			y: self.z * rhs.x - self.x * rhs.z,
+			z: self.x * rhs.y - self.y * rhs.x
+		}
+	}
+}
+
+impl<T> Xyz<T>
+where
+	T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>
+{
+	/// Rotate the vector counterclockwise around the z axis, looking down onto
+	/// the xy plane with the y axis pointing upwards.
+	#[must_use]
+	pub fn rotate_z(self, angle: deg) -> Self {
+		let angle = angle.to_radians();
+		let sin = angle.sin();
+		let cos = angle.cos();
+		Self {
+			x: self.x * cos - self.y * sin,
+			y: self.x * sin + self.y * cos,
+			z: self.z
+		}
+	}
+}
+
+impl Xyz<f32> {
+	/// Return the length of the vector.
+	pub fn length(self) -> f32 {
+		self.dot(self).sqrt()
+	}
+}
+
+impl Xyz<mm> {
+	/// Return the length of the vector.
+	pub fn length(self) -> mm {
+		self.dot(self).sqrt()
+	}
+}
+
 impl<T> Add for Xyz<T>
 where
 	T: Add
@@ -35,12 +88,46 @@ where
 	}
 }
 
+impl<T> Sub for Xyz<T>
+where
+	T: Sub
+{
+	type Output = Xyz<T::Output>;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Xyz {
+			x: self.x - rhs.x,
+			y: self.y - rhs.y,
+			z: self.z - rhs.z
+		}
+	}
+}
+
+impl<T> Mul<f32> for Xyz<T>
+where
+	T: Mul<f32>
+{
+	type Output = Xyz<T::Output>;
+
+	fn mul(self, rhs: f32) -> Self::Output {
+		Xyz {
+			x: self.x * rhs,
+			y: self.y * rhs,
+			z: self.z * rhs
+		}
+	}
+}
+
 #[derive(Deserialize)]
 #[serde(deny_unknown_fields, rename = "model")]
 struct ModelDef {
 	file: String,
 
-	#[serde(with = "option_tuple")]
+	#[serde(default)]
+	hide: bool,
+
+	// written by older versions of KiCad, but never by this crate
+	#[serde(default, with = "option_tuple")]
 	at: Option<Xyz<mm>>,
 
 	#[serde(with = "option_tuple")]
@@ -57,6 +144,7 @@ impl From<ModelDef> for Model {
 	fn from(def: ModelDef) -> Self {
 		Self {
 			file: def.file,
+			hide: def.hide,
 			offset: def.at.unwrap_or_default() + def.offset.unwrap_or_default(),
 			scale: def.scale,
 			rotate: def.rotate
@@ -69,6 +157,9 @@ impl From<ModelDef> for Model {
 pub struct Model {
 	pub file: String,
 
+	/// Hide the model in the 3D viewer.
+	pub hide: bool,
+
 	#[serde(with = "tuple")]
 	pub offset: Xyz<mm>,
 
@@ -89,17 +180,31 @@ mod tests {
 		input: r#"(model "model.wrl" (offset (xyz 5 -1 3)) (scale (xyz 1 1 1)) (rotate (xyz 0 0 0)))"#,
 		value: Model {
 			file: "model.wrl".to_owned(),
+			hide: false,
 			offset: Xyz::new(5.0.mm(), -1.0.mm(), 3.0.mm()),
 			scale: Xyz::new(1.0, 1.0, 1.0),
 			rotate: Xyz::new(0.0.deg(), 0.0.deg(), 0.0.deg())
 		}
 	}
 
+	sexpr_test_case! {
+		name: hidden_model,
+		input: r#"(model "model.wrl" hide (offset (xyz 0 0 0)) (scale (xyz 1 1 1)) (rotate (xyz 0 0 0)))"#,
+		value: Model {
+			file: "model.wrl".to_owned(),
+			hide: true,
+			offset: Xyz::new(0.0.mm(), 0.0.mm(), 0.0.mm()),
+			scale: Xyz::new(1.0, 1.0, 1.0),
+			rotate: Xyz::new(0.0.deg(), 0.0.deg(), 0.0.deg())
+		}
+	}
+
 	#[test]
 	fn test_deserialize_model_with_at() {
 		let input = r#"(model "model.wrl" (at (xyz 5 -1 3)) (scale (xyz 1 1 1)) (rotate (xyz 0 0 0)))"#;
 		let value = Model {
 			file: "model.wrl".to_owned(),
+			hide: false,
 			offset: Xyz::new(5.0.mm(), -1.0.mm(), 3.0.mm()),
 			scale: Xyz::new(1.0, 1.0, 1.0),
 			rotate: Xyz::new(0.0.deg(), 0.0.deg(), 0.0.deg())
@@ -109,4 +214,33 @@ mod tests {
 			serde_sexpr::from_str(input).expect("Failed to parse input");
 		pretty_assertions::assert_eq!(parsed, value);
 	}
+
+	#[test]
+	fn vector_math() {
+		let a = Xyz::new(1.0.mm(), 2.0.mm(), 3.0.mm());
+		let b = Xyz::new(4.0.mm(), -5.0.mm(), 6.0.mm());
+		assert_eq!(a + b, Xyz::new(5.0.mm(), -3.0.mm(), 9.0.mm()));
+		assert_eq!(b - a, Xyz::new(3.0.mm(), -7.0.mm(), 3.0.mm()));
+		assert_eq!(a * 2.0, Xyz::new(2.0.mm(), 4.0.mm(), 6.0.mm()));
+
+		let x = Xyz::new(1.0, 0.0, 0.0);
+		let y = Xyz::new(0.0, 1.0, 0.0);
+		assert_eq!(Xyz::new(1.0, 2.0, 3.0).dot(Xyz::new(4.0, -5.0, 6.0)), 12.0);
+		assert_eq!(x.cross(y), Xyz::new(0.0, 0.0, 1.0));
+		assert_eq!(y.cross(x), Xyz::new(0.0, 0.0, -1.0));
+	}
+
+	#[test]
+	fn length() {
+		assert_eq!(Xyz::new(3.0, 4.0, 0.0).length(), 5.0);
+		assert_eq!(Xyz::new(3.0.mm(), 4.0.mm(), 0.0.mm()).length(), 5.0.mm());
+	}
+
+	#[test]
+	fn rotate_z() {
+		let rotated = Xyz::new(1.0.mm(), 0.0.mm(), 2.0.mm()).rotate_z(90.0.deg());
+		assert!(rotated.x.raw_value().abs() < 1e-6);
+		assert_eq!(rotated.y, 1.0.mm());
+		assert_eq!(rotated.z, 2.0.mm());
+	}
 }
@@ -1,7 +1,7 @@
 use super::{ConnectPads, Primitives};
 use crate::{
 	common::{Point, Position},
-	internal::{option_tuple, option_unit, rename, tuple, tuple_or_default},
+	internal::{option_tuple, option_unit, rename, tstamp, tuple},
 	mm
 };
 use serde::{Deserialize, Serialize};
@@ -94,6 +94,19 @@ pub struct PadOptions {
 	pub anchor: PadAnchor
 }
 
+/// The order in which [`Footprint::add_pad_array`](super::Footprint::add_pad_array)
+/// numbers the pads of a grid.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PadNumbering {
+	/// Number the pads of the first row from left to right, then those of the
+	/// next row.
+	#[default]
+	RowMajor,
+	/// Number the pads of the first column from top to bottom, then those of
+	/// the next column.
+	ColumnMajor
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "pad")]
 pub struct Pad {
@@ -103,11 +116,10 @@ pub struct Pad {
 
 	pub shape: PadShape,
 
-	pub position: Position,
-
-	#[serde(with = "option_unit")]
 	pub locked: bool,
 
+	pub position: Position,
+
 	pub size: PadSize,
 
 	#[serde(with = "serde_sexpr::Option")]
@@ -136,9 +148,6 @@ pub struct Pad {
 	#[serde(with = "serde_sexpr::Option")]
 	pub net: Option<(u32, String)>,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
-	pub tstamp: Uuid,
-
 	#[serde(with = "option_tuple")]
 	pub pinfunction: Option<String>,
 
@@ -160,12 +169,18 @@ pub struct Pad {
 	#[serde(with = "option_tuple")]
 	pub clearance: Option<mm>,
 
+	/// Override the zone connection of the parent footprint or zone. If not
+	/// present, the connection is inherited.
 	#[serde(with = "option_tuple")]
 	pub zone_connect: Option<ConnectPads>,
 
 	#[serde(with = "option_tuple")]
 	pub thermal_width: Option<mm>,
 
+	/// The angle of the thermal relief spokes in degrees.
+	#[serde(with = "option_tuple")]
+	pub thermal_bridge_angle: Option<f32>,
+
 	#[serde(with = "option_tuple")]
 	pub thermal_gap: Option<mm>,
 
@@ -173,7 +188,10 @@ pub struct Pad {
 	pub custom_pad_options: Option<PadOptions>,
 
 	#[serde(with = "serde_sexpr::Option")]
-	pub custom_pad_primitives: Option<Primitives>
+	pub custom_pad_primitives: Option<Primitives>,
+
+	#[serde(with = "tstamp", skip_serializing_if = "crate::skip_uuid")]
+	pub tstamp: Uuid
 }
 
 impl Pad {
@@ -215,17 +233,50 @@ impl Pad {
 			clearance: None,
 			zone_connect: None,
 			thermal_width: None,
+			thermal_bridge_angle: None,
 			thermal_gap: None,
 			custom_pad_options: None,
 			custom_pad_primitives: None
 		}
 	}
+
+	/// Turn this pad into a rounded rectangle pad. The corner radius is `ratio`
+	/// times the smaller side of the pad.
+	pub fn set_roundrect(&mut self, ratio: f32) {
+		self.shape = PadShape::RoundRect;
+		self.roundrect_rratio = Some(ratio);
+	}
+
+	/// Chamfer the given corners of this pad. KiCad treats chamfered pads as
+	/// rounded rectangles, so the shape is changed accordingly.
+	pub fn set_chamfer(&mut self, ratio: f32, corners: Vec<PadEdge>) {
+		if self.shape != PadShape::RoundRect {
+			self.set_roundrect(0.0);
+		}
+		self.chamfer_ratio = Some(ratio);
+		self.chamfer = Some(corners);
+	}
+
+	/// Turn this pad into a custom-shaped pad. The copper shape is the union of
+	/// the anchor pad and the graphic primitives.
+	pub fn set_custom_shape(&mut self, options: PadOptions, primitives: Primitives) {
+		self.shape = PadShape::Custom;
+		self.custom_pad_options = Some(options);
+		self.custom_pad_primitives = Some(primitives);
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{sexpr_test_case, Unit};
+	use crate::{
+		board::{
+			footprint::FillType,
+			graphic::{GraphicItem, Polygon}
+		},
+		common::PointList,
+		sexpr_test_case, Deg, Unit
+	};
 
 	sexpr_test_case! {
 		name: pad_simple,
@@ -240,4 +291,144 @@ mod tests {
 			Uuid::nil()
 		)
 	}
+
+	sexpr_test_case! {
+		name: pad_locked,
+		input: r#"(pad "1" smd rect locked (at 0 0 270) (size 0.3 0.7) (layers "F.Cu") (net 1 "/VBUS DEV") (pinfunction "A4") (pintype "no connect") (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: {
+			let mut pad = Pad::new(
+				"1",
+				PadType::Smd,
+				PadShape::Rect,
+				Position::new_with_angle(0.0.mm(), 0.0.mm(), 270.0.deg()),
+				PadSize::new(0.3.mm(), 0.7.mm()),
+				vec!["F.Cu".to_owned()],
+				Uuid::nil()
+			);
+			pad.locked = true;
+			pad.net = Some((1, "/VBUS DEV".to_owned()));
+			pad.pinfunction = Some("A4".to_owned());
+			pad.pintype = Some("no connect".to_owned());
+			pad
+		}
+	}
+
+	sexpr_test_case! {
+		name: pad_custom,
+		input: r#"(pad "1" smd custom (at 0 0) (size 0.5 0.5) (layers "F.Cu" "F.Mask") (options (clearance outline) (anchor rect)) (primitives (gr_poly (pts (xy 0 -0.5) (xy 1 0) (xy 0 0.5)) (width 0) (fill solid) (tstamp "00000000-0000-0000-0000-000000000000"))) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: {
+			let mut pad = Pad::new(
+				"1",
+				PadType::Smd,
+				PadShape::Rect,
+				Position::new(0.0.mm(), 0.0.mm()),
+				PadSize::new(0.5.mm(), 0.5.mm()),
+				vec!["F.Cu".to_owned(), "F.Mask".to_owned()],
+				Uuid::nil()
+			);
+			pad.set_custom_shape(
+				PadOptions {
+					clearance: PadClearanceType::Outline,
+					anchor: PadAnchor::Rect
+				},
+				Primitives {
+					items: vec![GraphicItem::Poly(Polygon {
+						locked: false,
+						pts: PointList::new(vec![
+							Point::new(0.0.mm(), (-0.5).mm()),
+							Point::new(1.0.mm(), 0.0.mm()),
+							Point::new(0.0.mm(), 0.5.mm())
+						]),
+						layer: None,
+						width: 0.0.mm(),
+						stroke_type: None,
+						fill: Some(FillType::Solid),
+						tstamp: Uuid::nil()
+					})],
+					width: None,
+					fill: false
+				}
+			);
+			pad
+		}
+	}
+
+	sexpr_test_case! {
+		name: pad_roundrect,
+		input: r#"(pad "1" smd roundrect (at 0 0) (size 1 1.2) (layers "F.Cu" "F.Paste" "F.Mask") (roundrect_rratio 0.25) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: {
+			let mut pad = Pad::new(
+				"1",
+				PadType::Smd,
+				PadShape::Rect,
+				Position::new(0.0.mm(), 0.0.mm()),
+				PadSize::new(1.0.mm(), 1.2.mm()),
+				vec!["F.Cu".to_owned(), "F.Paste".to_owned(), "F.Mask".to_owned()],
+				Uuid::nil()
+			);
+			pad.set_roundrect(0.25);
+			pad
+		}
+	}
+
+	sexpr_test_case! {
+		name: pad_chamfer,
+		input: r#"(pad "1" smd roundrect (at 0 0) (size 1 1) (layers "F.Cu") (roundrect_rratio 0) (chamfer_ratio 0.2) (chamfer top_left bottom_right) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: {
+			let mut pad = Pad::new(
+				"1",
+				PadType::Smd,
+				PadShape::Rect,
+				Position::new(0.0.mm(), 0.0.mm()),
+				PadSize::new(1.0.mm(), 1.0.mm()),
+				vec!["F.Cu".to_owned()],
+				Uuid::nil()
+			);
+			pad.set_chamfer(0.2, vec![PadEdge::TopLeft, PadEdge::BottomRight]);
+			pad
+		}
+	}
+
+	sexpr_test_case! {
+		name: pad_zone_connect_solid,
+		input: r#"(pad "1" smd rect (at 0 0) (size 2 2) (layers "F.Cu") (zone_connect 2) (thermal_bridge_angle 45) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Pad {
+			zone_connect: Some(ConnectPads::SolidFill),
+			thermal_bridge_angle: Some(45.0),
+			..Pad::new(
+				"1",
+				PadType::Smd,
+				PadShape::Rect,
+				Position::new(0.0.mm(), 0.0.mm()),
+				PadSize::new(2.0.mm(), 2.0.mm()),
+				vec!["F.Cu".to_owned()],
+				Uuid::nil()
+			)
+		}
+	}
+
+	sexpr_test_case! {
+		name: pad_thermal_width,
+		input: r#"(pad "1" thru_hole circle (at 0 0) (size 2 2) (drill 1) (layers "*.Cu") (zone_connect 1) (thermal_width 0.3) (thermal_gap 0.25) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Pad {
+			drill: Some(PadDrill {
+				oval: false,
+				diameter: Some(1.0.mm()),
+				width: None,
+				offset: None
+			}),
+			zone_connect: Some(ConnectPads::ThermalReliefs),
+			thermal_width: Some(0.3.mm()),
+			thermal_gap: Some(0.25.mm()),
+			..Pad::new(
+				"1",
+				PadType::ThroughHole,
+				PadShape::Circle,
+				Position::new(0.0.mm(), 0.0.mm()),
+				PadSize::new(2.0.mm(), 2.0.mm()),
+				vec!["*.Cu".to_owned()],
+				Uuid::nil()
+			)
+		}
+	}
 }
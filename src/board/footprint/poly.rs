@@ -1,30 +1,93 @@
-use super::FillType;
+use super::{
+	stroke::{self, Fill, InvalidStroke, StrokeDef},
+	FillType
+};
 use crate::{
 	board::Layer,
 	common::PointList,
-	internal::{option_tuple, option_unit, tuple, tuple_or_default},
-	mm
+	internal::{option_tuple, option_unit, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "fp_poly")]
+struct PolygonDef {
+	pts: PointList,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke_fill: Option<Fill>,
+
+	layer: Layer,
+
+	#[serde(with = "option_tuple")]
+	width: Option<mm>,
+
+	#[serde(with = "option_tuple")]
+	fill: Option<FillType>,
+
+	#[serde(with = "option_unit")]
+	locked: bool,
+
+	#[serde(with = "tstamp", skip_serializing_if = "crate::skip_uuid")]
+	tstamp: Uuid
+}
+
+impl TryFrom<PolygonDef> for Polygon {
+	type Error = InvalidStroke;
+
+	fn try_from(def: PolygonDef) -> Result<Self, InvalidStroke> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
+		Ok(Self {
+			pts: def.pts,
+			layer: def.layer,
+			width,
+			stroke_type,
+			fill: stroke::from_fill_def(def.stroke_fill, def.fill),
+			locked: def.locked,
+			tstamp: def.tstamp
+		})
+	}
+}
+
+impl From<Polygon> for PolygonDef {
+	fn from(poly: Polygon) -> Self {
+		let (stroke, width) = stroke::into_def(poly.width, poly.stroke_type);
+		let (stroke_fill, fill) = stroke::into_fill_def(poly.fill, poly.stroke_type);
+		Self {
+			pts: poly.pts,
+			stroke,
+			stroke_fill,
+			layer: poly.layer,
+			width,
+			fill,
+			locked: poly.locked,
+			tstamp: poly.tstamp
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "PolygonDef", into = "PolygonDef")]
 pub struct Polygon {
 	pub pts: PointList,
 
 	pub layer: Layer,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "option_tuple")]
+	/// See [`Line::stroke_type`](super::Line::stroke_type).
+	pub stroke_type: Option<StrokeType>,
+
 	pub fill: Option<FillType>,
 
-	#[serde(with = "option_unit")]
 	pub locked: bool,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
 	pub tstamp: Uuid
 }
 
@@ -45,9 +108,29 @@ mod tests {
 			]),
 			layer: Layer::new("F.SilkS"),
 			width: 0.12.mm(),
+			stroke_type: None,
 			fill: None,
 			locked: false,
 			tstamp: Uuid::nil()
 		}
 	}
+
+	sexpr_test_case! {
+		name: poly_stroke,
+		input: r#"(fp_poly (pts (xy -1.5 -1) (xy 1.5 -1) (xy 1.5 1) (xy -1.5 1)) (stroke (width 0.05) (type solid)) (fill none) (layer "F.CrtYd") (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Polygon {
+			pts: PointList::new(vec![
+				Point::new(-1.5.mm(), -1.0.mm()),
+				Point::new(1.5.mm(), -1.0.mm()),
+				Point::new(1.5.mm(), 1.0.mm()),
+				Point::new(-1.5.mm(), 1.0.mm())
+			]),
+			layer: Layer::new("F.CrtYd"),
+			width: 0.05.mm(),
+			stroke_type: Some(StrokeType::Solid),
+			fill: Some(FillType::None),
+			locked: false,
+			tstamp: Uuid::nil()
+		}
+	}
 }
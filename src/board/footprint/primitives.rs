@@ -1,6 +1,6 @@
 use crate::{
 	board::graphic::{
-		Arc, Circle, Curve, GraphicItem, Line, Polygon, Rectangle, Text
+		Arc, Circle, Curve, Dimension, GraphicItem, Line, Polygon, Rectangle, Text
 	},
 	mm
 };
@@ -25,6 +25,7 @@ serde_sexpr::untagged! {
 		Arc(Arc),
 		Circle(Circle),
 		Curve(Curve),
+		Dimension(Dimension),
 		Line(Line),
 		Poly(Polygon),
 		Rect(Rectangle),
@@ -50,6 +51,7 @@ impl From<Primitives> for PrimitivesDef {
 				GraphicItem::Arc(arc) => Content::Arc(arc),
 				GraphicItem::Circle(circle) => Content::Circle(circle),
 				GraphicItem::Curve(curve) => Content::Curve(curve),
+				GraphicItem::Dimension(dimension) => Content::Dimension(dimension),
 				GraphicItem::Line(line) => Content::Line(line),
 				GraphicItem::Poly(poly) => Content::Poly(poly),
 				GraphicItem::Rect(rect) => Content::Rect(rect),
@@ -76,6 +78,9 @@ impl From<PrimitivesDef> for Primitives {
 				Content::Arc(arc) => items.push(GraphicItem::Arc(arc)),
 				Content::Circle(circle) => items.push(GraphicItem::Circle(circle)),
 				Content::Curve(curve) => items.push(GraphicItem::Curve(curve)),
+				Content::Dimension(dimension) => {
+					items.push(GraphicItem::Dimension(dimension))
+				},
 				Content::Line(line) => items.push(GraphicItem::Line(line)),
 				Content::Poly(poly) => items.push(GraphicItem::Poly(poly)),
 				Content::Rect(rect) => items.push(GraphicItem::Rect(rect)),
@@ -110,10 +115,12 @@ mod tests {
 		input: r#"(primitives (gr_circle (center 1 1) (end 2 2) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000")))"#,
 		value: Primitives {
 			items: vec![GraphicItem::Circle(Circle {
+				locked: false,
 				center: Point::new(1.0.mm(), 1.0.mm()),
 				end: Point::new(2.0.mm(), 2.0.mm()),
 				layer: None,
 				width: 0.12.mm(),
+				stroke_type: None,
 				fill: None,
 				tstamp: Uuid::nil()
 			})],
@@ -0,0 +1,72 @@
+//! The `(stroke ..)` form that KiCad 7 uses for footprint and board graphics
+//! in place of the `(width ..)` of earlier versions.
+
+use super::FillType;
+use crate::{internal::tuple, mm, symbol::StrokeType};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename = "stroke")]
+pub(crate) struct StrokeDef {
+	#[serde(with = "tuple")]
+	width: mm,
+
+	#[serde(rename = "type", with = "tuple")]
+	ty: StrokeType
+}
+
+/// The `(fill ..)` that KiCad 7 writes right after the stroke, before the
+/// layer.
+#[derive(Deserialize, Serialize)]
+#[serde(rename = "fill")]
+pub(crate) struct Fill(FillType);
+
+#[derive(Debug, Error)]
+pub(crate) enum InvalidStroke {
+	#[error("Missing width or stroke definition")]
+	MissingWidth
+}
+
+/// Combine the stroke or legacy width of a graphic.
+pub(crate) fn from_def(
+	stroke: Option<StrokeDef>,
+	width: Option<mm>
+) -> Result<(mm, Option<StrokeType>), InvalidStroke> {
+	match (stroke, width) {
+		(Some(stroke), _) => Ok((stroke.width, Some(stroke.ty))),
+		(None, Some(width)) => Ok((width, None)),
+		(None, None) => Err(InvalidStroke::MissingWidth)
+	}
+}
+
+/// Split a graphic's width into either the stroke or the legacy
+/// width, depending on whether it has a stroke type.
+pub(crate) fn into_def(
+	width: mm,
+	stroke_type: Option<StrokeType>
+) -> (Option<StrokeDef>, Option<mm>) {
+	match stroke_type {
+		Some(ty) => (Some(StrokeDef { width, ty }), None),
+		None => (None, Some(width))
+	}
+}
+
+/// Split a graphic's fill the same way as [`into_def`].
+pub(crate) fn into_fill_def(
+	fill: Option<FillType>,
+	stroke_type: Option<StrokeType>
+) -> (Option<Fill>, Option<FillType>) {
+	match stroke_type {
+		Some(_) => (fill.map(Fill), None),
+		None => (None, fill)
+	}
+}
+
+/// Combine the fill of either form.
+pub(crate) fn from_fill_def(
+	stroke_fill: Option<Fill>,
+	fill: Option<FillType>
+) -> Option<FillType> {
+	stroke_fill.map(|Fill(fill)| fill).or(fill)
+}
@@ -1,8 +1,8 @@
 use crate::{
 	board::Layer,
-	common::{Effects, Position},
+	common::{Effects, Point, Position, RenderCache},
 	deg,
-	internal::{tuple, tuple_or_default},
+	internal::{tstamp, tuple},
 	mm
 };
 use serde::{Deserialize, Serialize};
@@ -26,6 +26,8 @@ struct UnlockablePosition {
 	#[serde(with = "serde_sexpr::Option")]
 	angle: Option<deg>,
 
+	// missing from the position of a serialized `Text`, e.g. in JSON
+	#[serde(default)]
 	unlocked: bool
 }
 
@@ -46,8 +48,11 @@ struct TextDef {
 
 	effects: Effects,
 
-	#[serde(with = "tuple_or_default")]
-	tstamp: Uuid
+	#[serde(with = "tstamp")]
+	tstamp: Uuid,
+
+	#[serde(with = "serde_sexpr::Option")]
+	render_cache: Option<RenderCache>
 }
 
 impl From<TextDef> for Text {
@@ -64,7 +69,8 @@ impl From<TextDef> for Text {
 			layer: def.layer,
 			hide: def.hide,
 			effects: def.effects,
-			tstamp: def.tstamp
+			tstamp: def.tstamp,
+			render_cache: def.render_cache
 		}
 	}
 }
@@ -76,6 +82,8 @@ pub struct Text {
 
 	pub text: String,
 
+	/// The position relative to the footprint origin. The angle is absolute and
+	/// already includes the rotation of the footprint.
 	pub position: Position,
 
 	/// Indicates if the text orientation can be anything other than the upright
@@ -89,13 +97,33 @@ pub struct Text {
 	pub effects: Effects,
 
 	#[serde(with = "tuple", skip_serializing_if = "crate::skip_uuid")]
-	pub tstamp: Uuid
+	pub tstamp: Uuid,
+
+	#[serde(with = "serde_sexpr::Option")]
+	pub render_cache: Option<RenderCache>
+}
+
+impl Text {
+	/// Return the position of the text in board coordinates, given the position
+	/// of the footprint it belongs to.
+	pub fn absolute_position(&self, footprint_origin: Position) -> Point {
+		let offset = Point::new(
+			footprint_origin.x + self.position.x,
+			footprint_origin.y + self.position.y
+		);
+		match footprint_origin.angle {
+			// KiCad's y axis points downwards, so positive angles rotate clockwise
+			// in the mathematical sense
+			Some(angle) => offset.rotate_around(footprint_origin.point(), -angle),
+			None => offset
+		}
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{common::Font, sexpr_test_case, Unit};
+	use crate::{common::Font, sexpr_test_case, Deg, Unit};
 
 	sexpr_test_case! {
 		name: text,
@@ -108,7 +136,34 @@ mod tests {
 			layer: Layer::new("F.SilkS"),
 			hide: false,
 			effects: Effects::new(Font::new(1.0.mm())),
-			tstamp: Uuid::nil()
+			tstamp: Uuid::nil(),
+			render_cache: None
 		}
 	}
+
+	#[test]
+	fn absolute_position() {
+		let text = Text {
+			ty: TextType::Reference,
+			text: "REF**".to_owned(),
+			position: Position::new_with_angle(0.0.mm(), -2.0.mm(), 90.0.deg()),
+			unlocked: false,
+			layer: Layer::new("F.SilkS"),
+			hide: false,
+			effects: Effects::new(Font::new(1.0.mm())),
+			tstamp: Uuid::nil(),
+			render_cache: None
+		};
+		let origin = Position::new(10.0.mm(), 10.0.mm());
+		assert_eq!(
+			text.absolute_position(origin),
+			Point::new(10.0.mm(), 8.0.mm())
+		);
+
+		let origin = Position::new_with_angle(10.0.mm(), 10.0.mm(), 90.0.deg());
+		assert_eq!(
+			text.absolute_position(origin).round_nm_precision(),
+			Point::new(8.0.mm(), 10.0.mm())
+		);
+	}
 }
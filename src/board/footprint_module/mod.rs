@@ -115,10 +115,11 @@ impl From<FootprintModule> for Footprint {
 			library_link: Some(module.library_link),
 			version: Some(Version::default()),
 			generator: Some("kicad-rs".to_owned()),
+			generator_version: None,
 			locked: false,
 			placed: false,
 			layer: module.layer,
-			tedit: module.tedit.unwrap_or(Timestamp(0)),
+			tedit: Some(module.tedit.unwrap_or(Timestamp(0))),
 			tstamp: None,
 			position: module.position,
 			description: module.description,
@@ -140,6 +141,8 @@ impl From<FootprintModule> for Footprint {
 				.or(module.attributes_bottom)
 				.unwrap_or_default()
 				.into(),
+			private_layers: Vec::new(),
+			net_tie_pad_groups: Vec::new(),
 			content
 		}
 	}
@@ -0,0 +1,85 @@
+//! The Gerber files that the layers of a board are conventionally exported to.
+
+use super::CanonicalLayer;
+
+/// The role of a Gerber file, returned by
+/// [`PCB::gerber_layer_map`](crate::pcb::PCB::gerber_layer_map).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GerberLayer {
+	TopCopper,
+	/// The inner copper layer with the given number, e.g. `1` for `In1.Cu`.
+	InnerCopper(u8),
+	BottomCopper,
+	TopSilkscreen,
+	BottomSilkscreen,
+	TopSolderMask,
+	BottomSolderMask,
+	TopPaste,
+	BottomPaste,
+	TopAdhesive,
+	BottomAdhesive,
+	/// The board outline from the `Edge.Cuts` layer.
+	Outline,
+	/// A layer without a conventional Gerber role, like the courtyard,
+	/// fabrication and user layers.
+	Other
+}
+
+impl GerberLayer {
+	pub fn for_layer(layer: &CanonicalLayer) -> Self {
+		match layer {
+			CanonicalLayer::FCu => Self::TopCopper,
+			CanonicalLayer::InCu(n) => Self::InnerCopper(*n),
+			CanonicalLayer::BCu => Self::BottomCopper,
+			CanonicalLayer::FSilkS => Self::TopSilkscreen,
+			CanonicalLayer::BSilkS => Self::BottomSilkscreen,
+			CanonicalLayer::FMask => Self::TopSolderMask,
+			CanonicalLayer::BMask => Self::BottomSolderMask,
+			CanonicalLayer::FPaste => Self::TopPaste,
+			CanonicalLayer::BPaste => Self::BottomPaste,
+			CanonicalLayer::FAdhes => Self::TopAdhesive,
+			CanonicalLayer::BAdhes => Self::BottomAdhesive,
+			CanonicalLayer::EdgeCuts => Self::Outline,
+			_ => Self::Other
+		}
+	}
+
+	/// Return the Protel file extension KiCad uses for this layer, without the
+	/// leading dot. Inner copper layers are numbered like KiCad does, starting
+	/// with `g2` for `In1.Cu`. Layers without a conventional role use `gbr`.
+	pub fn extension(&self) -> String {
+		let ext = match self {
+			Self::TopCopper => "gtl",
+			Self::InnerCopper(n) => return format!("g{}", n + 1),
+			Self::BottomCopper => "gbl",
+			Self::TopSilkscreen => "gto",
+			Self::BottomSilkscreen => "gbo",
+			Self::TopSolderMask => "gts",
+			Self::BottomSolderMask => "gbs",
+			Self::TopPaste => "gtp",
+			Self::BottomPaste => "gbp",
+			Self::TopAdhesive => "gta",
+			Self::BottomAdhesive => "gba",
+			Self::Outline => "gm1",
+			Self::Other => "gbr"
+		};
+		ext.to_owned()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn extension() {
+		let ext = |name| {
+			GerberLayer::for_layer(&CanonicalLayer::from_name(name)).extension()
+		};
+		assert_eq!(ext("F.Cu"), "gtl");
+		assert_eq!(ext("In1.Cu"), "g2");
+		assert_eq!(ext("B.Mask"), "gbs");
+		assert_eq!(ext("Edge.Cuts"), "gm1");
+		assert_eq!(ext("F.CrtYd"), "gbr");
+	}
+}
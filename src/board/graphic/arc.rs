@@ -1,16 +1,26 @@
 use crate::{
-	board::{footprint::arc, Layer},
-	common::Point,
+	board::{
+		footprint::{
+			arc,
+			stroke::{self, StrokeDef}
+		},
+		Layer
+	},
+	common::{Point, PointList},
 	deg,
-	internal::{option_tuple, rename, tuple, tuple_or_default},
-	mm
+	internal::{lenient_mm, option_tuple, option_unit, rename, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "gr_arc")]
 struct ArcDef {
+	#[serde(with = "option_unit")]
+	locked: bool,
+
 	#[serde(with = "rename::start")]
 	start: Point,
 
@@ -23,13 +33,20 @@ struct ArcDef {
 	#[serde(with = "option_tuple")]
 	angle: Option<deg>,
 
+	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
+
 	#[serde(with = "serde_sexpr::Option")]
 	layer: Option<Layer>,
 
-	#[serde(with = "tuple")]
-	width: mm,
+	#[serde(with = "lenient_mm::option_tuple")]
+	width: Option<mm>,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
 	tstamp: Uuid
 }
 
@@ -37,13 +54,16 @@ impl TryFrom<ArcDef> for Arc {
 	type Error = arc::InvalidArc;
 
 	fn try_from(def: ArcDef) -> Result<Self, Self::Error> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
 		match (def.mid, def.angle) {
 			(Some(mid), None) => Ok(Self {
+				locked: def.locked,
 				start: def.start,
 				mid,
 				end: def.end,
 				layer: def.layer,
-				width: def.width,
+				width,
+				stroke_type,
 				tstamp: def.tstamp
 			}),
 
@@ -51,11 +71,13 @@ impl TryFrom<ArcDef> for Arc {
 				let (start, mid) =
 					arc::start_mid_from_start_end_angle(def.start, def.end, angle);
 				Ok(Self {
+					locked: def.locked,
 					start,
 					mid,
 					end: def.end,
 					layer: def.layer,
-					width: def.width,
+					width,
+					stroke_type,
 					tstamp: def.tstamp
 				})
 			},
@@ -66,27 +88,66 @@ impl TryFrom<ArcDef> for Arc {
 	}
 }
 
+impl From<Arc> for ArcDef {
+	fn from(arc: Arc) -> Self {
+		let (stroke, width) = stroke::into_def(arc.width, arc.stroke_type);
+		Self {
+			locked: arc.locked,
+			start: arc.start,
+			mid: Some(arc.mid),
+			end: arc.end,
+			angle: None,
+			stroke,
+			layer: arc.layer,
+			width,
+			tstamp: arc.tstamp
+		}
+	}
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(try_from = "ArcDef", rename = "gr_arc")]
+#[serde(try_from = "ArcDef", into = "ArcDef")]
 pub struct Arc {
-	#[serde(with = "rename::start")]
+	/// Defines a flag to indicate the item cannot be edited.
+	pub locked: bool,
+
 	pub start: Point,
 
-	#[serde(with = "rename::mid")]
 	pub mid: Point,
 
-	#[serde(with = "rename::end")]
 	pub end: Point,
 
 	pub layer: Option<Layer>,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "tuple", skip_serializing_if = "crate::skip_uuid")]
+	/// See [`Line::stroke_type`](super::Line::stroke_type).
+	pub stroke_type: Option<StrokeType>,
+
 	pub tstamp: Uuid
 }
 
+impl Arc {
+	/// Create a new arc through `start`, `mid` and `end` without a layer.
+	pub fn new(start: Point, mid: Point, end: Point, width: mm) -> Self {
+		Self {
+			locked: false,
+			start,
+			mid,
+			end,
+			layer: None,
+			width,
+			stroke_type: None,
+			tstamp: Uuid::new_v4()
+		}
+	}
+
+	/// Approximate the arc with a polyline of `segments` line segments.
+	pub fn to_polyline(&self, segments: usize) -> PointList {
+		PointList::arc_polyline(self.start, self.mid, self.end, segments)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -96,11 +157,13 @@ mod tests {
 		name: arc_with_mid,
 		input: r#"(gr_arc (start 8.9 2) (mid -2.403861 0) (end 8.9 -2) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
 		value: Arc {
+			locked: false,
 			start: Point::new(8.9.mm(), 2.0.mm()),
 			mid: Point::new(-2.403861.mm(), 0.0.mm()),
 			end: Point::new(8.9.mm(), -2.0.mm()),
 			layer: None,
 			width: 0.12.mm(),
+			stroke_type: None,
 			tstamp: Uuid::nil()
 		}
 	}
@@ -109,11 +172,13 @@ mod tests {
 	fn test_deserialize_arc_with_angle() {
 		let input = r#"(gr_arc (start 3.425 0) (end 8.9 -2) (angle -319.8658258) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#;
 		let value = Arc {
+			locked: false,
 			start: Point::new(8.9.mm(), 2.0.mm()),
 			mid: Point::new(-2.403861.mm(), 0.0.mm()),
 			end: Point::new(8.9.mm(), -2.0.mm()),
 			layer: None,
 			width: 0.12.mm(),
+			stroke_type: None,
 			tstamp: Uuid::nil()
 		};
 
@@ -121,4 +186,18 @@ mod tests {
 			serde_sexpr::from_str(input).expect("Failed to parse input");
 		pretty_assertions::assert_eq!(parsed, value);
 	}
+
+	sexpr_test_case! {
+		name: arc_new,
+		input: r#"(gr_arc (start -1 0) (mid 0 -1) (end 1 0) (width 0.1) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Arc {
+			tstamp: Uuid::nil(),
+			..Arc::new(
+				Point::new((-1.0).mm(), 0.0.mm()),
+				Point::new(0.0.mm(), (-1.0).mm()),
+				Point::new(1.0.mm(), 0.0.mm()),
+				0.1.mm()
+			)
+		}
+	}
 }
@@ -1,34 +1,130 @@
 use crate::{
-	board::{footprint::FillType, Layer},
+	board::{
+		footprint::{
+			stroke::{self, Fill, InvalidStroke, StrokeDef},
+			FillType
+		},
+		Layer
+	},
 	common::Point,
-	internal::{option_tuple, rename, tuple, tuple_or_default},
-	mm
+	internal::{lenient_mm, option_tuple, option_unit, rename, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "gr_circle")]
-pub struct Circle {
+struct CircleDef {
+	#[serde(with = "option_unit")]
+	locked: bool,
+
 	#[serde(with = "rename::center")]
-	pub center: Point,
+	center: Point,
 
 	#[serde(with = "rename::end")]
-	pub end: Point,
+	end: Point,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
 
 	#[serde(with = "serde_sexpr::Option")]
+	stroke_fill: Option<Fill>,
+
+	#[serde(with = "serde_sexpr::Option")]
+	layer: Option<Layer>,
+
+	#[serde(with = "lenient_mm::option_tuple")]
+	width: Option<mm>,
+
+	#[serde(with = "option_tuple")]
+	fill: Option<FillType>,
+
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
+	tstamp: Uuid
+}
+
+impl TryFrom<CircleDef> for Circle {
+	type Error = InvalidStroke;
+
+	fn try_from(def: CircleDef) -> Result<Self, InvalidStroke> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
+		Ok(Self {
+			locked: def.locked,
+			center: def.center,
+			end: def.end,
+			layer: def.layer,
+			width,
+			stroke_type,
+			fill: stroke::from_fill_def(def.stroke_fill, def.fill),
+			tstamp: def.tstamp
+		})
+	}
+}
+
+impl From<Circle> for CircleDef {
+	fn from(circle: Circle) -> Self {
+		let (stroke, width) = stroke::into_def(circle.width, circle.stroke_type);
+		let (stroke_fill, fill) =
+			stroke::into_fill_def(circle.fill, circle.stroke_type);
+		Self {
+			locked: circle.locked,
+			center: circle.center,
+			end: circle.end,
+			stroke,
+			stroke_fill,
+			layer: circle.layer,
+			width,
+			fill,
+			tstamp: circle.tstamp
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "CircleDef", into = "CircleDef")]
+pub struct Circle {
+	/// Defines a flag to indicate the item cannot be edited.
+	pub locked: bool,
+
+	pub center: Point,
+
+	pub end: Point,
+
 	pub layer: Option<Layer>,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "option_tuple")]
+	/// See [`Line::stroke_type`](super::Line::stroke_type).
+	pub stroke_type: Option<StrokeType>,
+
 	pub fill: Option<FillType>,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
 	pub tstamp: Uuid
 }
 
+impl Circle {
+	/// Create a new, unfilled circle without a layer. The radius is given by the
+	/// distance between `center` and `end`.
+	pub fn new(center: Point, end: Point, width: mm) -> Self {
+		Self {
+			locked: false,
+			center,
+			end,
+			layer: None,
+			width,
+			stroke_type: None,
+			fill: None,
+			tstamp: Uuid::new_v4()
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -38,10 +134,12 @@ mod tests {
 		name: circle,
 		input: r#"(gr_circle (center 1 1) (end 2 2) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
 		value: Circle {
+			locked: false,
 			center: Point::new(1.0.mm(), 1.0.mm()),
 			end: Point::new(2.0.mm(), 2.0.mm()),
 			layer: None,
 			width: 0.12.mm(),
+			stroke_type: None,
 			fill: None,
 			tstamp: Uuid::nil()
 		}
@@ -51,12 +149,42 @@ mod tests {
 		name: circle_filled,
 		input: r#"(gr_circle (center 1 1) (end 2 2) (width 0.12) (fill solid) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
 		value: Circle {
+			locked: false,
 			center: Point::new(1.0.mm(), 1.0.mm()),
 			end: Point::new(2.0.mm(), 2.0.mm()),
 			layer: None,
 			width: 0.12.mm(),
+			stroke_type: None,
 			fill: Some(FillType::Solid),
 			tstamp: Uuid::nil()
 		}
 	}
+
+	sexpr_test_case! {
+		name: circle_new,
+		input: r#"(gr_circle (center 5 5) (end 6 5) (width 0.15) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Circle {
+			tstamp: Uuid::nil(),
+			..Circle::new(
+				Point::new(5.0.mm(), 5.0.mm()),
+				Point::new(6.0.mm(), 5.0.mm()),
+				0.15.mm()
+			)
+		}
+	}
+
+	sexpr_test_case! {
+		name: circle_stroke,
+		input: r#"(gr_circle (center 1 1) (end 2 2) (stroke (width 0.1) (type solid)) (fill none) (layer "F.SilkS") (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Circle {
+			locked: false,
+			center: Point::new(1.0.mm(), 1.0.mm()),
+			end: Point::new(2.0.mm(), 2.0.mm()),
+			layer: Some(Layer::new("F.SilkS")),
+			width: 0.1.mm(),
+			stroke_type: Some(StrokeType::Solid),
+			fill: Some(FillType::None),
+			tstamp: Uuid::nil()
+		}
+	}
 }
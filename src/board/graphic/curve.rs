@@ -1,27 +1,108 @@
 use crate::{
-	board::Layer,
+	board::{
+		footprint::stroke::{self, InvalidStroke, StrokeDef},
+		Layer
+	},
 	common::PointList,
-	internal::{tuple, tuple_or_default},
-	mm
+	internal::{lenient_mm, option_unit, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename = "gr_curve")]
+struct CurveDef {
+	#[serde(with = "option_unit")]
+	locked: bool,
+
+	pts: PointList,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
+
+	#[serde(with = "serde_sexpr::Option")]
+	layer: Option<Layer>,
+
+	#[serde(with = "lenient_mm::option_tuple")]
+	width: Option<mm>,
+
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
+	tstamp: Uuid
+}
+
+impl TryFrom<CurveDef> for Curve {
+	type Error = InvalidStroke;
+
+	fn try_from(def: CurveDef) -> Result<Self, InvalidStroke> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
+		Ok(Self {
+			locked: def.locked,
+			pts: def.pts,
+			layer: def.layer,
+			width,
+			stroke_type,
+			tstamp: def.tstamp
+		})
+	}
+}
+
+impl From<Curve> for CurveDef {
+	fn from(curve: Curve) -> Self {
+		let (stroke, width) = stroke::into_def(curve.width, curve.stroke_type);
+		Self {
+			locked: curve.locked,
+			pts: curve.pts,
+			stroke,
+			layer: curve.layer,
+			width,
+			tstamp: curve.tstamp
+		}
+	}
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(deny_unknown_fields, rename = "fp_curve")]
+#[serde(try_from = "CurveDef", into = "CurveDef")]
 pub struct Curve {
+	/// Defines a flag to indicate the item cannot be edited.
+	pub locked: bool,
+
 	pub pts: PointList,
 
-	#[serde(with = "serde_sexpr::Option")]
 	pub layer: Option<Layer>,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
+	/// See [`Line::stroke_type`](super::Line::stroke_type).
+	pub stroke_type: Option<StrokeType>,
+
 	pub tstamp: Uuid
 }
 
+impl Curve {
+	/// Create a new cubic bezier curve without a layer.
+	pub fn new(pts: PointList, width: mm) -> Self {
+		Self {
+			locked: false,
+			pts,
+			layer: None,
+			width,
+			stroke_type: None,
+			tstamp: Uuid::new_v4()
+		}
+	}
+
+	/// Approximate the curve with a polyline of `segments` line segments.
+	pub fn to_polyline(&self, segments: usize) -> PointList {
+		self.pts.cubic_bezier_polyline(segments)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -29,8 +110,9 @@ mod tests {
 
 	sexpr_test_case! {
 		name: curve,
-		input: r#"(fp_curve (pts (xy 1 1) (xy 1 2) (xy 2 2) (xy 2 1)) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		input: r#"(gr_curve (pts (xy 1 1) (xy 1 2) (xy 2 2) (xy 2 1)) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
 		value: Curve {
+			locked: false,
 			pts: PointList::new(vec![
 				Point::new(1.0.mm(), 1.0.mm()),
 				Point::new(1.0.mm(), 2.0.mm()),
@@ -39,7 +121,25 @@ mod tests {
 			]),
 			layer: None,
 			width: 0.12.mm(),
+			stroke_type: None,
 			tstamp: Uuid::nil()
 		}
 	}
+
+	sexpr_test_case! {
+		name: curve_new,
+		input: r#"(gr_curve (pts (xy 0 0) (xy 0 1) (xy 1 1) (xy 1 0)) (width 0.1) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Curve {
+			tstamp: Uuid::nil(),
+			..Curve::new(
+				PointList::new(vec![
+					Point::new(0.0.mm(), 0.0.mm()),
+					Point::new(0.0.mm(), 1.0.mm()),
+					Point::new(1.0.mm(), 1.0.mm()),
+					Point::new(1.0.mm(), 0.0.mm())
+				]),
+				0.1.mm()
+			)
+		}
+	}
 }
@@ -0,0 +1,180 @@
+use super::Text;
+use crate::{
+	board::Layer,
+	common::PointList,
+	internal::{option_tuple, option_unit, tstamp, tuple},
+	mm
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DimensionType {
+	Aligned,
+	Orthogonal,
+	Leader,
+	Center,
+	Radial
+}
+
+/// A dimension, measuring the distance between two points.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "dimension")]
+pub struct Dimension {
+	/// Defines a flag to indicate the item cannot be edited.
+	#[serde(with = "option_unit")]
+	pub locked: bool,
+
+	#[serde(with = "tuple", rename = "type")]
+	pub ty: DimensionType,
+
+	pub layer: Layer,
+
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
+	pub tstamp: Uuid,
+
+	/// The start and end point of the dimension.
+	pub pts: PointList,
+
+	/// The distance of the dimension line from the measured points. Only used by
+	/// aligned and orthogonal dimensions.
+	#[serde(with = "option_tuple")]
+	pub height: Option<mm>,
+
+	/// The orientation of orthogonal dimensions, `0` for horizontal and `1` for
+	/// vertical.
+	#[serde(with = "option_tuple")]
+	pub orientation: Option<u8>,
+
+	/// The length of the horizontal part of the leader of radial dimensions.
+	#[serde(with = "option_tuple")]
+	pub leader_length: Option<mm>,
+
+	/// The text of the dimension. Center dimensions have no text.
+	#[serde(with = "serde_sexpr::Option")]
+	pub text: Option<Text>,
+
+	/// The formatting of the text. Center dimensions have no text.
+	#[serde(with = "serde_sexpr::Option")]
+	pub format: Option<DimensionFormat>,
+
+	pub style: DimensionStyle
+}
+
+/// The formatting of the measured value of a [`Dimension`].
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "format")]
+pub struct DimensionFormat {
+	#[serde(with = "option_tuple")]
+	pub prefix: Option<String>,
+
+	#[serde(with = "option_tuple")]
+	pub suffix: Option<String>,
+
+	/// The units, `0` for inches, `1` for mils, `2` for millimeters and `3` for
+	/// automatic.
+	#[serde(with = "tuple")]
+	pub units: u8,
+
+	/// The format of the units, `0` for none, `1` for bare suffix and `2` for
+	/// the suffix in parentheses.
+	#[serde(with = "tuple")]
+	pub units_format: u8,
+
+	#[serde(with = "tuple")]
+	pub precision: u8,
+
+	/// The text to display instead of the measured value.
+	#[serde(with = "option_tuple")]
+	pub override_value: Option<String>,
+
+	pub suppress_zeroes: bool
+}
+
+/// The style of the lines and arrows of a [`Dimension`].
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "style")]
+pub struct DimensionStyle {
+	#[serde(with = "tuple")]
+	pub thickness: mm,
+
+	#[serde(with = "tuple")]
+	pub arrow_length: mm,
+
+	/// The position of the text, `0` for outside, `1` for inline and `2` for
+	/// manual placement.
+	#[serde(with = "tuple")]
+	pub text_position_mode: u8,
+
+	#[serde(with = "option_tuple")]
+	pub extension_height: Option<mm>,
+
+	/// The frame around the text of leader dimensions.
+	#[serde(with = "option_tuple")]
+	pub text_frame: Option<u8>,
+
+	#[serde(with = "option_tuple")]
+	pub extension_offset: Option<mm>,
+
+	pub keep_text_aligned: bool
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		common::{Effects, Font, Point, Position},
+		sexpr_test_case, Unit
+	};
+
+	sexpr_test_case! {
+		name: aligned_dimension,
+		input: r#"(dimension (type aligned) (layer "Dwgs.User") (tstamp "00000000-0000-0000-0000-000000000000") (pts (xy 100 100) (xy 120 100)) (height 5) (gr_text "20 mm" (at 110 93.85) (layer "Dwgs.User") (tstamp "00000000-0000-0000-0000-000000000000") (effects (font (size 1 1)))) (format (units 3) (units_format 1) (precision 4)) (style (thickness 0.15) (arrow_length 1.27) (text_position_mode 0) (extension_height 0.58642) (extension_offset 0) keep_text_aligned))"#,
+		value: Dimension {
+			locked: false,
+			ty: DimensionType::Aligned,
+			layer: Layer::new("Dwgs.User"),
+			tstamp: Uuid::nil(),
+			pts: PointList::new(vec![
+				Point::new(100.0.mm(), 100.0.mm()),
+				Point::new(120.0.mm(), 100.0.mm())
+			]),
+			height: Some(5.0.mm()),
+			orientation: None,
+			leader_length: None,
+			text: Some(Text {
+				text: "20 mm".to_owned(),
+				locked: false,
+				position: Position::new(110.0.mm(), 93.85.mm()),
+				layer: Some(Layer::new("Dwgs.User")),
+				knockout: false,
+				tstamp: Uuid::nil(),
+				effects: Effects::new(Font::new(1.0.mm())),
+				render_cache: None
+			}),
+			format: Some(DimensionFormat {
+				prefix: None,
+				suffix: None,
+				units: 3,
+				units_format: 1,
+				precision: 4,
+				override_value: None,
+				suppress_zeroes: false
+			}),
+			style: DimensionStyle {
+				thickness: 0.15.mm(),
+				arrow_length: 1.27.mm(),
+				text_position_mode: 0,
+				extension_height: Some(0.58642.mm()),
+				text_frame: None,
+				extension_offset: Some(0.0.mm()),
+				keep_text_aligned: true
+			}
+		}
+	}
+}
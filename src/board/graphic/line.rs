@@ -1,35 +1,122 @@
 use crate::{
-	board::Layer,
+	board::{
+		footprint::stroke::{self, InvalidStroke, StrokeDef},
+		Layer
+	},
 	common::Point,
 	deg,
-	internal::{option_tuple, rename, tuple, tuple_or_default},
-	mm
+	internal::{lenient_mm, option_tuple, option_unit, rename, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "gr_line")]
-pub struct Line {
+struct LineDef {
+	#[serde(with = "option_unit")]
+	locked: bool,
+
 	#[serde(with = "rename::start")]
-	pub start: Point,
+	start: Point,
 
 	#[serde(with = "rename::end")]
-	pub end: Point,
+	end: Point,
 
 	#[serde(with = "option_tuple")]
-	pub angle: Option<deg>,
+	angle: Option<deg>,
 
 	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
+
+	#[serde(with = "serde_sexpr::Option")]
+	layer: Option<Layer>,
+
+	#[serde(with = "lenient_mm::option_tuple")]
+	width: Option<mm>,
+
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
+	tstamp: Uuid
+}
+
+impl TryFrom<LineDef> for Line {
+	type Error = InvalidStroke;
+
+	fn try_from(def: LineDef) -> Result<Self, InvalidStroke> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
+		Ok(Self {
+			locked: def.locked,
+			start: def.start,
+			end: def.end,
+			angle: def.angle,
+			layer: def.layer,
+			width,
+			stroke_type,
+			tstamp: def.tstamp
+		})
+	}
+}
+
+impl From<Line> for LineDef {
+	fn from(line: Line) -> Self {
+		let (stroke, width) = stroke::into_def(line.width, line.stroke_type);
+		Self {
+			locked: line.locked,
+			start: line.start,
+			end: line.end,
+			angle: line.angle,
+			stroke,
+			layer: line.layer,
+			width,
+			tstamp: line.tstamp
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "LineDef", into = "LineDef")]
+pub struct Line {
+	/// Defines a flag to indicate the item cannot be edited.
+	pub locked: bool,
+
+	pub start: Point,
+
+	pub end: Point,
+
+	pub angle: Option<deg>,
+
 	pub layer: Option<Layer>,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
+	/// The stroke type of KiCad 7 boards. If this is `None`, the width is
+	/// written in the `(width ..)` form of earlier versions.
+	pub stroke_type: Option<StrokeType>,
+
 	pub tstamp: Uuid
 }
 
+impl Line {
+	/// Create a new line without a layer.
+	pub fn new(start: Point, end: Point, width: mm) -> Self {
+		Self {
+			locked: false,
+			start,
+			end,
+			angle: None,
+			layer: None,
+			width,
+			stroke_type: None,
+			tstamp: Uuid::new_v4()
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -39,11 +126,13 @@ mod tests {
 		name: line,
 		input: r#"(gr_line (start 1 0) (end 2 0) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
 		value: Line {
+			locked: false,
 			start: Point::new(1.0.mm(), 0.0.mm()),
 			end: Point::new(2.0.mm(), 0.0.mm()),
 			angle: None,
 			layer: None,
 			width: 0.12.mm(),
+			stroke_type: None,
 			tstamp: Uuid::nil()
 		}
 	}
@@ -52,11 +141,41 @@ mod tests {
 		name: line_with_angle,
 		input: r#"(gr_line (start 1 0) (end 2 0) (angle -90) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
 		value: Line {
+			locked: false,
 			start: Point::new(1.0.mm(), 0.0.mm()),
 			end: Point::new(2.0.mm(), 0.0.mm()),
 			angle: Some(-90.0.deg()),
 			layer: None,
 			width: 0.12.mm(),
+			stroke_type: None,
+			tstamp: Uuid::nil()
+		}
+	}
+
+	sexpr_test_case! {
+		name: line_new,
+		input: r#"(gr_line (start 0 0) (end 0 5) (width 0.1) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Line {
+			tstamp: Uuid::nil(),
+			..Line::new(
+				Point::new(0.0.mm(), 0.0.mm()),
+				Point::new(0.0.mm(), 5.0.mm()),
+				0.1.mm()
+			)
+		}
+	}
+
+	sexpr_test_case! {
+		name: line_stroke,
+		input: r#"(gr_line (start 1 0) (end 2 0) (stroke (width 0.1) (type default)) (layer "Edge.Cuts") (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Line {
+			locked: false,
+			start: Point::new(1.0.mm(), 0.0.mm()),
+			end: Point::new(2.0.mm(), 0.0.mm()),
+			angle: None,
+			layer: Some(Layer::new("Edge.Cuts")),
+			width: 0.1.mm(),
+			stroke_type: Some(StrokeType::Default),
 			tstamp: Uuid::nil()
 		}
 	}
@@ -1,20 +1,37 @@
 mod arc;
 mod circle;
 mod curve;
+mod dimension;
 mod line;
 mod poly;
 mod rect;
-mod text;
 mod segment;
+mod text;
+mod via;
 
 pub use arc::Arc;
 pub use circle::Circle;
 pub use curve::Curve;
+pub use dimension::{Dimension, DimensionFormat, DimensionStyle, DimensionType};
 pub use line::Line;
 pub use poly::Polygon;
 pub use rect::Rectangle;
-pub use text::Text;
 pub use segment::Segment;
+pub use text::Text;
+pub use via::{Via, ViaType};
+
+use crate::board::Layer;
+use uuid::Uuid;
+
+/// Convenient import surface for building board geometry, e.g.
+/// `use kicad_files::board::graphic::prelude::*;`.
+pub mod prelude {
+	pub use super::{
+		Arc, Circle, Curve, Dimension, DimensionFormat, DimensionStyle,
+		DimensionType, GraphicItem, Line, Polygon, Rectangle, Segment, Text, Via,
+		ViaType
+	};
+}
 
 serde_sexpr::untagged! {
 	#[derive(Clone, Debug, Eq, PartialEq)]
@@ -22,9 +39,167 @@ serde_sexpr::untagged! {
 		Arc(Arc),
 		Circle(Circle),
 		Curve(Curve),
+		Dimension(Dimension),
 		Line(Line),
 		Poly(Polygon),
 		Rect(Rectangle),
 		Text(Text)
 	}
 }
+
+macro_rules! graphic_item_from {
+	($($variant:ident($ty:ident)),+) => {
+		$(
+			impl From<$ty> for GraphicItem {
+				fn from(item: $ty) -> Self {
+					Self::$variant(item)
+				}
+			}
+		)+
+	};
+}
+
+graphic_item_from! {
+	Arc(Arc),
+	Circle(Circle),
+	Curve(Curve),
+	Dimension(Dimension),
+	Line(Line),
+	Poly(Polygon),
+	Rect(Rectangle),
+	Text(Text)
+}
+
+macro_rules! content_key {
+	($($ty:ident),+) => {
+		$(
+			impl $ty {
+				/// Return a key identifying the content of this item, i.e. its
+				/// s-expr text without the unique identifier. Items with the same
+				/// geometry, layer and stroke have the same key.
+				pub fn content_key(&self) -> String {
+					let item = Self {
+						tstamp: Uuid::nil(),
+						..self.clone()
+					};
+					serde_sexpr::to_string(&item)
+						.unwrap_or_else(|_| format!("{item:?}"))
+				}
+			}
+		)+
+	};
+}
+
+content_key!(Arc, Circle, Curve, Dimension, Line, Polygon, Rectangle, Text);
+
+impl GraphicItem {
+	/// Return a key identifying the content of this item, see e.g.
+	/// [`Circle::content_key`].
+	pub fn content_key(&self) -> String {
+		match self {
+			Self::Arc(arc) => arc.content_key(),
+			Self::Circle(circle) => circle.content_key(),
+			Self::Curve(curve) => curve.content_key(),
+			Self::Dimension(dimension) => dimension.content_key(),
+			Self::Line(line) => line.content_key(),
+			Self::Poly(poly) => poly.content_key(),
+			Self::Rect(rect) => rect.content_key(),
+			Self::Text(text) => text.content_key()
+		}
+	}
+
+	/// Return the layer of this item. Most items can omit their layer, only
+	/// dimensions always have one.
+	pub fn layer(&self) -> Option<&Layer> {
+		match self {
+			Self::Arc(arc) => arc.layer.as_ref(),
+			Self::Circle(circle) => circle.layer.as_ref(),
+			Self::Curve(curve) => curve.layer.as_ref(),
+			Self::Dimension(dimension) => Some(&dimension.layer),
+			Self::Line(line) => line.layer.as_ref(),
+			Self::Poly(poly) => poly.layer.as_ref(),
+			Self::Rect(rect) => rect.layer.as_ref(),
+			Self::Text(text) => text.layer.as_ref()
+		}
+	}
+
+	/// Move this item to the given layer.
+	pub fn set_layer(&mut self, layer: Layer) {
+		match self {
+			Self::Arc(arc) => arc.layer = Some(layer),
+			Self::Circle(circle) => circle.layer = Some(layer),
+			Self::Curve(curve) => curve.layer = Some(layer),
+			Self::Dimension(dimension) => dimension.layer = layer,
+			Self::Line(line) => line.layer = Some(layer),
+			Self::Poly(poly) => poly.layer = Some(layer),
+			Self::Rect(rect) => rect.layer = Some(layer),
+			Self::Text(text) => text.layer = Some(layer)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::prelude::*;
+	use crate::{board::Layer, common::Point, Unit};
+
+	#[test]
+	fn layer() {
+		let input = r#"(dimension (type aligned) (layer "Dwgs.User") (pts (xy 100 100) (xy 120 100)) (height 5) (format (units 3) (units_format 1) (precision 4)) (style (thickness 0.15) (arrow_length 1.27) (text_position_mode 0) (extension_height 0.58642) (extension_offset 0) keep_text_aligned))"#;
+		let dimension =
+			GraphicItem::Dimension(serde_sexpr::from_str(input).unwrap());
+		assert_eq!(dimension.layer(), Some(&Layer::new("Dwgs.User")));
+
+		let input = r#"(gr_circle (center 1 1) (end 2 2) (width 0.12))"#;
+		let circle = GraphicItem::Circle(serde_sexpr::from_str(input).unwrap());
+		assert_eq!(circle.layer(), None);
+	}
+
+	#[test]
+	fn set_layer() {
+		let input = r#"(gr_circle (center 1 1) (end 2 2) (width 0.12))"#;
+		let mut circle = GraphicItem::Circle(serde_sexpr::from_str(input).unwrap());
+		circle.set_layer(Layer::new("F.SilkS"));
+		assert_eq!(circle.layer(), Some(&Layer::new("F.SilkS")));
+	}
+
+	#[test]
+	fn from_primitives() {
+		let start = Point::new(0.0.mm(), 0.0.mm());
+		let end = Point::new(10.0.mm(), 5.0.mm());
+		let mut items: Vec<GraphicItem> = Vec::new();
+		items.push(Line::new(start, end, 0.12.mm()).into());
+		items.push(Circle::new(start, end, 0.12.mm()).into());
+		items.push(Rectangle::new(start, end, 0.12.mm()).into());
+
+		assert!(matches!(items[0], GraphicItem::Line(_)));
+		assert!(matches!(items[1], GraphicItem::Circle(_)));
+		assert!(matches!(items[2], GraphicItem::Rect(_)));
+		for item in &mut items {
+			item.set_layer(Layer::new("Edge.Cuts"));
+		}
+		assert!(items
+			.iter()
+			.all(|item| item.layer() == Some(&Layer::new("Edge.Cuts"))));
+	}
+
+	#[test]
+	fn content_key() {
+		let circle = |tstamp| Circle {
+			tstamp,
+			..Circle::new(
+				Point::new(0.0.mm(), 0.0.mm()),
+				Point::new(1.0.mm(), 0.0.mm()),
+				0.1.mm()
+			)
+		};
+		let a = GraphicItem::from(circle(uuid::Uuid::from_u128(1)));
+		let b = GraphicItem::from(circle(uuid::Uuid::from_u128(2)));
+		assert_ne!(a, b);
+		assert_eq!(a.content_key(), b.content_key());
+
+		let mut c = b.clone();
+		c.set_layer(Layer::new("F.SilkS"));
+		assert_ne!(b.content_key(), c.content_key());
+	}
+}
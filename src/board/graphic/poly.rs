@@ -1,30 +1,119 @@
 use crate::{
-	board::{footprint::FillType, Layer},
+	board::{
+		footprint::{
+			stroke::{self, Fill, InvalidStroke, StrokeDef},
+			FillType
+		},
+		Layer
+	},
 	common::PointList,
-	internal::{option_tuple, tuple, tuple_or_default},
-	mm
+	internal::{lenient_mm, option_tuple, option_unit, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "gr_poly")]
+struct PolygonDef {
+	#[serde(with = "option_unit")]
+	locked: bool,
+
+	pts: PointList,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke_fill: Option<Fill>,
+
+	#[serde(with = "serde_sexpr::Option")]
+	layer: Option<Layer>,
+
+	#[serde(with = "lenient_mm::option_tuple")]
+	width: Option<mm>,
+
+	#[serde(with = "option_tuple")]
+	fill: Option<FillType>,
+
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
+	tstamp: Uuid
+}
+
+impl TryFrom<PolygonDef> for Polygon {
+	type Error = InvalidStroke;
+
+	fn try_from(def: PolygonDef) -> Result<Self, InvalidStroke> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
+		Ok(Self {
+			locked: def.locked,
+			pts: def.pts,
+			layer: def.layer,
+			width,
+			stroke_type,
+			fill: stroke::from_fill_def(def.stroke_fill, def.fill),
+			tstamp: def.tstamp
+		})
+	}
+}
+
+impl From<Polygon> for PolygonDef {
+	fn from(poly: Polygon) -> Self {
+		let (stroke, width) = stroke::into_def(poly.width, poly.stroke_type);
+		let (stroke_fill, fill) = stroke::into_fill_def(poly.fill, poly.stroke_type);
+		Self {
+			locked: poly.locked,
+			pts: poly.pts,
+			stroke,
+			stroke_fill,
+			layer: poly.layer,
+			width,
+			fill,
+			tstamp: poly.tstamp
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "PolygonDef", into = "PolygonDef")]
 pub struct Polygon {
+	/// Defines a flag to indicate the item cannot be edited.
+	pub locked: bool,
+
 	pub pts: PointList,
 
-	#[serde(with = "serde_sexpr::Option")]
 	pub layer: Option<Layer>,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "option_tuple")]
+	/// See [`Line::stroke_type`](super::Line::stroke_type).
+	pub stroke_type: Option<StrokeType>,
+
 	pub fill: Option<FillType>,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
 	pub tstamp: Uuid
 }
 
+impl Polygon {
+	/// Create a new, unfilled polygon without a layer.
+	pub fn new(pts: PointList, width: mm) -> Self {
+		Self {
+			locked: false,
+			pts,
+			layer: None,
+			width,
+			stroke_type: None,
+			fill: None,
+			tstamp: Uuid::new_v4()
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -34,6 +123,7 @@ mod tests {
 		name: poly,
 		input: r#"(gr_poly (pts (xy 1 1) (xy 1 2) (xy 2 2) (xy 2 1)) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
 		value: Polygon {
+			locked: false,
 			pts: PointList::new(vec![
 				Point::new(1.0.mm(), 1.0.mm()),
 				Point::new(1.0.mm(), 2.0.mm()),
@@ -42,8 +132,25 @@ mod tests {
 			]),
 			layer: None,
 			width: 0.12.mm(),
+			stroke_type: None,
 			fill: None,
 			tstamp: Uuid::nil()
 		}
 	}
+
+	sexpr_test_case! {
+		name: poly_new,
+		input: r#"(gr_poly (pts (xy 0 0) (xy 1 0) (xy 0 1)) (width 0.1) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Polygon {
+			tstamp: Uuid::nil(),
+			..Polygon::new(
+				PointList::new(vec![
+					Point::new(0.0.mm(), 0.0.mm()),
+					Point::new(1.0.mm(), 0.0.mm()),
+					Point::new(0.0.mm(), 1.0.mm())
+				]),
+				0.1.mm()
+			)
+		}
+	}
 }
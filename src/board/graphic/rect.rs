@@ -1,34 +1,128 @@
 use crate::{
-	board::{footprint::FillType, Layer},
+	board::{
+		footprint::{
+			stroke::{self, Fill, InvalidStroke, StrokeDef},
+			FillType
+		},
+		Layer
+	},
 	common::Point,
-	internal::{option_tuple, rename, tuple, tuple_or_default},
-	mm
+	internal::{lenient_mm, option_tuple, option_unit, rename, tstamp},
+	mm,
+	symbol::StrokeType
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "gr_rect")]
-pub struct Rectangle {
+struct RectangleDef {
+	#[serde(with = "option_unit")]
+	locked: bool,
+
 	#[serde(with = "rename::start")]
-	pub start: Point,
+	start: Point,
 
 	#[serde(with = "rename::end")]
-	pub end: Point,
+	end: Point,
 
 	#[serde(with = "serde_sexpr::Option")]
+	stroke: Option<StrokeDef>,
+
+	#[serde(with = "serde_sexpr::Option")]
+	stroke_fill: Option<Fill>,
+
+	#[serde(with = "serde_sexpr::Option")]
+	layer: Option<Layer>,
+
+	#[serde(with = "lenient_mm::option_tuple")]
+	width: Option<mm>,
+
+	#[serde(with = "option_tuple")]
+	fill: Option<FillType>,
+
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
+	tstamp: Uuid
+}
+
+impl TryFrom<RectangleDef> for Rectangle {
+	type Error = InvalidStroke;
+
+	fn try_from(def: RectangleDef) -> Result<Self, InvalidStroke> {
+		let (width, stroke_type) = stroke::from_def(def.stroke, def.width)?;
+		Ok(Self {
+			locked: def.locked,
+			start: def.start,
+			end: def.end,
+			layer: def.layer,
+			width,
+			stroke_type,
+			fill: stroke::from_fill_def(def.stroke_fill, def.fill),
+			tstamp: def.tstamp
+		})
+	}
+}
+
+impl From<Rectangle> for RectangleDef {
+	fn from(rect: Rectangle) -> Self {
+		let (stroke, width) = stroke::into_def(rect.width, rect.stroke_type);
+		let (stroke_fill, fill) = stroke::into_fill_def(rect.fill, rect.stroke_type);
+		Self {
+			locked: rect.locked,
+			start: rect.start,
+			end: rect.end,
+			stroke,
+			stroke_fill,
+			layer: rect.layer,
+			width,
+			fill,
+			tstamp: rect.tstamp
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "RectangleDef", into = "RectangleDef")]
+pub struct Rectangle {
+	/// Defines a flag to indicate the item cannot be edited.
+	pub locked: bool,
+
+	pub start: Point,
+
+	pub end: Point,
+
 	pub layer: Option<Layer>,
 
-	#[serde(with = "tuple")]
 	pub width: mm,
 
-	#[serde(with = "option_tuple")]
+	/// See [`Line::stroke_type`](super::Line::stroke_type).
+	pub stroke_type: Option<StrokeType>,
+
 	pub fill: Option<FillType>,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
 	pub tstamp: Uuid
 }
 
+impl Rectangle {
+	/// Create a new, unfilled rectangle without a layer.
+	pub fn new(start: Point, end: Point, width: mm) -> Self {
+		Self {
+			locked: false,
+			start,
+			end,
+			layer: None,
+			width,
+			stroke_type: None,
+			fill: None,
+			tstamp: Uuid::new_v4()
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -38,10 +132,12 @@ mod tests {
 		name: rect,
 		input: r#"(gr_rect (start 1 1) (end 2 2) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
 		value: Rectangle {
+			locked: false,
 			start: Point::new(1.0.mm(), 1.0.mm()),
 			end: Point::new(2.0.mm(), 2.0.mm()),
 			layer: None,
 			width: 0.12.mm(),
+			stroke_type: None,
 			fill: None,
 			tstamp: Uuid::nil()
 		}
@@ -51,12 +147,27 @@ mod tests {
 		name: rect_filled,
 		input: r#"(gr_rect (start 1 1) (end 2 2) (width 0.12) (fill solid) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
 		value: Rectangle {
+			locked: false,
 			start: Point::new(1.0.mm(), 1.0.mm()),
 			end: Point::new(2.0.mm(), 2.0.mm()),
 			layer: None,
 			width: 0.12.mm(),
+			stroke_type: None,
 			fill: Some(FillType::Solid),
 			tstamp: Uuid::nil()
 		}
 	}
+
+	sexpr_test_case! {
+		name: rect_new,
+		input: r#"(gr_rect (start 0 0) (end 10 5) (width 0.1) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Rectangle {
+			tstamp: Uuid::nil(),
+			..Rectangle::new(
+				Point::new(0.0.mm(), 0.0.mm()),
+				Point::new(10.0.mm(), 5.0.mm()),
+				0.1.mm()
+			)
+		}
+	}
 }
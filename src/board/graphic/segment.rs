@@ -1,8 +1,8 @@
 use crate::{
 	board::Layer,
 	common::Point,
-	internal::{rename, tuple, tuple_or_default},
-	mm
+	internal::{lenient_mm, option_unit, rename, tstamp, tuple},
+	mm, Unit
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -10,33 +10,198 @@ use uuid::Uuid;
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "segment")]
 pub struct Segment {
+	/// Defines a flag to indicate the item cannot be edited.
+	#[serde(with = "option_unit")]
+	pub locked: bool,
+
 	#[serde(with = "rename::start")]
 	pub start: Point,
 
 	#[serde(with = "rename::end")]
 	pub end: Point,
 
-	#[serde(with = "tuple")]
+	#[serde(with = "lenient_mm::tuple")]
 	pub width: mm,
 
 	pub layer: Layer,
 
 	#[serde(with = "tuple")]
-	pub net: u8,
+	pub net: u32,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
 	pub tstamp: Uuid
 }
 
+impl Segment {
+	/// Create a new track segment on the copper `layer`, connected to `net`.
+	pub fn new(start: Point, end: Point, width: mm, layer: Layer, net: u32) -> Self {
+		Self {
+			locked: false,
+			start,
+			end,
+			width,
+			layer,
+			net,
+			tstamp: Uuid::new_v4()
+		}
+	}
+
+	/// Return the length of the segment.
+	pub fn length(&self) -> mm {
+		self.start.distance(self.end)
+	}
+
+	/// Return the edge-to-edge distance between this and the other segment,
+	/// taking the width of both tracks into account. Overlapping tracks have a
+	/// clearance of zero. The layers of the segments are not compared.
+	pub fn clearance(&self, other: &Segment) -> mm {
+		let center = if intersects(self, other) {
+			0.0
+		} else {
+			[
+				point_distance(self.start, other),
+				point_distance(self.end, other),
+				point_distance(other.start, self),
+				point_distance(other.end, self)
+			]
+			.into_iter()
+			.fold(f32::INFINITY, f32::min)
+		};
+		let edges = (self.width.raw_value() + other.width.raw_value()) / 2.0;
+		(center - edges).max(0.0).mm()
+	}
+
+	/// Split the segment at the point `p` into two segments with new tstamps.
+	/// Returns [`None`] if the point is not on the segment or if it is one of
+	/// its endpoints.
+	pub fn split_at(&self, p: Point) -> Option<(Segment, Segment)> {
+		if point_distance(p, self) > TOLERANCE
+			|| self.start.distance(p).raw_value() <= TOLERANCE
+			|| self.end.distance(p).raw_value() <= TOLERANCE
+		{
+			return None;
+		}
+		let first = Self {
+			end: p,
+			tstamp: Uuid::new_v4(),
+			..self.clone()
+		};
+		let second = Self {
+			start: p,
+			tstamp: Uuid::new_v4(),
+			..self.clone()
+		};
+		Some((first, second))
+	}
+
+	/// Join this segment with the other segment if both are on the same layer
+	/// and net, have the same width, share an endpoint and are collinear. The
+	/// joined segment keeps the tstamp of this segment.
+	pub fn join(&self, other: &Segment) -> Option<Segment> {
+		self.join_at(other).map(|(joined, _)| joined)
+	}
+
+	/// Like [`join`](Self::join), but also returns the shared endpoint.
+	pub(crate) fn join_at(&self, other: &Segment) -> Option<(Segment, Point)> {
+		if self.layer != other.layer
+			|| self.net != other.net
+			|| self.width != other.width
+		{
+			return None;
+		}
+		let touches = |a: Point, b: Point| a.distance(b).raw_value() <= TOLERANCE;
+		let far = |near: Point| {
+			if touches(other.start, near) {
+				Some(other.end)
+			} else if touches(other.end, near) {
+				Some(other.start)
+			} else {
+				None
+			}
+		};
+		let (start, end, shared) = if let Some(far) = far(self.end) {
+			(self.start, far, self.end)
+		} else if let Some(far) = far(self.start) {
+			(far, self.end, self.start)
+		} else {
+			return None;
+		};
+		let joined = Self {
+			start,
+			end,
+			..self.clone()
+		};
+		// the shared point has to lie between the far ends, otherwise the
+		// segments bend or fold back onto each other
+		(point_distance(shared, &joined) <= TOLERANCE).then_some((joined, shared))
+	}
+}
+
+/// The distance in millimeters up to which points are considered to be equal.
+const TOLERANCE: f32 = 0.0001;
+
+/// Return the distance between the point and the centerline of the segment.
+fn point_distance(point: Point, segment: &Segment) -> f32 {
+	let (px, py) = (point.x.raw_value(), point.y.raw_value());
+	let (ax, ay) = (segment.start.x.raw_value(), segment.start.y.raw_value());
+	let (bx, by) = (segment.end.x.raw_value(), segment.end.y.raw_value());
+	let (dx, dy) = (bx - ax, by - ay);
+	let len_sq = dx * dx + dy * dy;
+	let t = if len_sq == 0.0 {
+		0.0
+	} else {
+		(((px - ax) * dx + (py - ay) * dy) / len_sq).clamp(0.0, 1.0)
+	};
+	let (cx, cy) = (ax + t * dx - px, ay + t * dy - py);
+	(cx * cx + cy * cy).sqrt()
+}
+
+/// Check whether the centerlines of both segments properly cross each other.
+/// Touching endpoints are caught by [`point_distance`] instead.
+fn intersects(a: &Segment, b: &Segment) -> bool {
+	fn orientation(p: Point, q: Point, r: Point) -> f32 {
+		(q.x.raw_value() - p.x.raw_value()) * (r.y.raw_value() - p.y.raw_value())
+			- (q.y.raw_value() - p.y.raw_value()) * (r.x.raw_value() - p.x.raw_value())
+	}
+
+	let d1 = orientation(b.start, b.end, a.start);
+	let d2 = orientation(b.start, b.end, a.end);
+	let d3 = orientation(a.start, a.end, b.start);
+	let d4 = orientation(a.start, a.end, b.end);
+	d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Segment {
+	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+		use crate::internal::arbitrary::{copper_layer, mm, point, uuid};
+
+		Ok(Self {
+			locked: u.arbitrary()?,
+			start: point(u)?,
+			end: point(u)?,
+			width: mm(u)?,
+			layer: copper_layer(u)?,
+			net: u.arbitrary()?,
+			tstamp: uuid(u)?
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{sexpr_test_case, Unit};
+	use crate::sexpr_test_case;
 
 	sexpr_test_case! {
 		name: segment,
 		input: r#"(segment (start 1 0) (end 2 0) (width 0.12) (layer "F.Cu") (net 1) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
 		value: Segment {
+			locked: false,
 			start: Point::new(1.0.mm(), 0.0.mm()),
 			end: Point::new(2.0.mm(), 0.0.mm()),
 			width: 0.12.mm(),
@@ -45,4 +210,133 @@ mod tests {
 			tstamp: Uuid::nil()
 		}
 	}
+
+	sexpr_test_case! {
+		name: locked_segment,
+		input: r#"(segment (locked) (start 1 0) (end 2 0) (width 0.12) (layer "F.Cu") (net 1) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Segment {
+			locked: true,
+			start: Point::new(1.0.mm(), 0.0.mm()),
+			end: Point::new(2.0.mm(), 0.0.mm()),
+			width: 0.12.mm(),
+			layer: Layer(String::from("F.Cu")),
+			net: 1,
+			tstamp: Uuid::nil()
+		}
+	}
+
+	#[test]
+	fn length() {
+		let segment = Segment {
+			locked: false,
+			start: Point::new(1.0.mm(), 1.0.mm()),
+			end: Point::new(4.0.mm(), 5.0.mm()),
+			width: 0.2.mm(),
+			layer: Layer::new("F.Cu"),
+			net: 1,
+			tstamp: Uuid::nil()
+		};
+		assert_eq!(segment.length(), 5.0.mm());
+	}
+
+	sexpr_test_case! {
+		name: segment_new,
+		input: r#"(segment (start 0 0) (end 2.54 0) (width 0.25) (layer "B.Cu") (net 3) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Segment {
+			tstamp: Uuid::nil(),
+			..Segment::new(
+				Point::new(0.0.mm(), 0.0.mm()),
+				Point::new(2.54.mm(), 0.0.mm()),
+				0.25.mm(),
+				Layer::new("B.Cu"),
+				3
+			)
+		}
+	}
+
+	proptest::proptest! {
+		#[test]
+		fn segment_round_trip(segment in crate::internal::proptest::segment()) {
+			let written = serde_sexpr::to_string(&segment).unwrap();
+			let parsed: Segment = serde_sexpr::from_str(&written).unwrap();
+			proptest::prop_assert_eq!(parsed, segment, "{}", written);
+		}
+	}
+
+	#[test]
+	fn clearance() {
+		let segment = |start: (f32, f32), end: (f32, f32)| {
+			Segment::new(
+				Point::new(start.0.mm(), start.1.mm()),
+				Point::new(end.0.mm(), end.1.mm()),
+				0.2.mm(),
+				Layer::new("F.Cu"),
+				1
+			)
+		};
+		let a = segment((0.0, 0.0), (10.0, 0.0));
+		assert_eq!(a.clearance(&segment((0.0, 1.0), (10.0, 1.0))), 0.8.mm());
+		assert_eq!(a.clearance(&segment((12.0, 0.0), (15.0, 0.0))), 1.8.mm());
+		assert_eq!(a.clearance(&segment((5.0, -1.0), (5.0, 1.0))), 0.0.mm());
+	}
+
+	#[test]
+	fn tstamp_or_uuid() {
+		let tstamp = r#"(segment (start 1 0) (end 2 0) (width 0.12) (layer "F.Cu") (net 1) (tstamp "00000000-0000-0000-0000-000000000001"))"#;
+		let uuid = tstamp.replace("(tstamp ", "(uuid ");
+		let tstamp: Segment = serde_sexpr::from_str(tstamp).unwrap();
+		let uuid: Segment = serde_sexpr::from_str(&uuid).unwrap();
+		assert_eq!(tstamp.tstamp, Uuid::from_u128(1));
+		assert_eq!(uuid, tstamp);
+
+		let missing = r#"(segment (start 1 0) (end 2 0) (width 0.12) (layer "F.Cu") (net 1))"#;
+		let missing: Segment = serde_sexpr::from_str(missing).unwrap();
+		assert!(missing.tstamp.is_nil());
+	}
+
+	#[test]
+	fn split_at() {
+		let segment = Segment::new(
+			Point::new(0.0.mm(), 0.0.mm()),
+			Point::new(10.0.mm(), 0.0.mm()),
+			0.25.mm(),
+			Layer::new("F.Cu"),
+			1
+		);
+		let mid = Point::new(5.0.mm(), 0.0.mm());
+		let (first, second) = segment.split_at(mid).unwrap();
+		assert_eq!((first.start, first.end), (segment.start, mid));
+		assert_eq!((second.start, second.end), (mid, segment.end));
+		assert_eq!(first.length() + second.length(), segment.length());
+		assert_eq!((first.width, first.net), (segment.width, segment.net));
+		assert_ne!(first.tstamp, segment.tstamp);
+		assert_ne!(second.tstamp, first.tstamp);
+
+		assert!(segment.split_at(Point::new(5.0.mm(), 1.0.mm())).is_none());
+		assert!(segment.split_at(Point::new(11.0.mm(), 0.0.mm())).is_none());
+		assert!(segment.split_at(segment.end).is_none());
+	}
+
+	#[test]
+	fn join() {
+		let segment = |start: (f32, f32), end: (f32, f32)| {
+			Segment::new(
+				Point::new(start.0.mm(), start.1.mm()),
+				Point::new(end.0.mm(), end.1.mm()),
+				0.25.mm(),
+				Layer::new("F.Cu"),
+				1
+			)
+		};
+		let a = segment((0.0, 0.0), (5.0, 0.0));
+		let joined = a.join(&segment((10.0, 0.0), (5.0, 0.0))).unwrap();
+		assert_eq!(joined.start, Point::new(0.0.mm(), 0.0.mm()));
+		assert_eq!(joined.end, Point::new(10.0.mm(), 0.0.mm()));
+		assert_eq!(joined.tstamp, a.tstamp);
+
+		// bent, folded back and disconnected segments are not joined
+		assert!(a.join(&segment((5.0, 0.0), (5.0, 5.0))).is_none());
+		assert!(a.join(&segment((5.0, 0.0), (2.0, 0.0))).is_none());
+		assert!(a.join(&segment((6.0, 0.0), (10.0, 0.0))).is_none());
+	}
 }
@@ -1,25 +1,100 @@
 use crate::{
 	board::Layer,
-	common::{Effects, Position},
-	internal::tuple_or_default
+	common::{Effects, Position, RenderCache},
+	internal::tstamp
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+/// The layer of a text. Texts can be knocked out, i.e. cut out of a filled area
+/// on their layer.
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename = "layer")]
+struct TextLayer {
+	name: String,
+
+	knockout: bool
+}
+
+#[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields, rename = "gr_text")]
+struct TextDef {
+	locked: bool,
+
+	text: String,
+
+	position: Position,
+
+	#[serde(with = "serde_sexpr::Option")]
+	layer: Option<TextLayer>,
+
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
+	tstamp: Uuid,
+
+	effects: Effects,
+
+	#[serde(with = "serde_sexpr::Option")]
+	render_cache: Option<RenderCache>
+}
+
+impl From<TextDef> for Text {
+	fn from(def: TextDef) -> Self {
+		let knockout = def.layer.as_ref().is_some_and(|layer| layer.knockout);
+		Self {
+			text: def.text,
+			locked: def.locked,
+			position: def.position,
+			layer: def.layer.map(|layer| Layer(layer.name)),
+			knockout,
+			tstamp: def.tstamp,
+			effects: def.effects,
+			render_cache: def.render_cache
+		}
+	}
+}
+
+impl From<Text> for TextDef {
+	fn from(text: Text) -> Self {
+		Self {
+			locked: text.locked,
+			text: text.text,
+			position: text.position,
+			layer: text.layer.map(|layer| TextLayer {
+				name: layer.0,
+				knockout: text.knockout
+			}),
+			tstamp: text.tstamp,
+			effects: text.effects,
+			render_cache: text.render_cache
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(from = "TextDef", into = "TextDef")]
 pub struct Text {
 	pub text: String,
 
+	/// Defines a flag to indicate the item cannot be edited.
+	pub locked: bool,
+
 	pub position: Position,
 
-	#[serde(with = "serde_sexpr::Option")]
 	pub layer: Option<Layer>,
 
-	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
+	/// Cut the text out of a filled area on its layer. Only written by KiCad 7
+	/// and later.
+	pub knockout: bool,
+
 	pub tstamp: Uuid,
 
-	pub effects: Effects
+	pub effects: Effects,
+
+	pub render_cache: Option<RenderCache>
 }
 
 #[cfg(test)]
@@ -31,11 +106,32 @@ mod tests {
 		name: text,
 		input: r#"(gr_text "some text" (at 0 0) (tstamp "00000000-0000-0000-0000-000000000000") (effects (font (size 1 1))))"#,
 		value: Text {
+			locked: false,
 			text: "some text".to_owned(),
 			position: Position::new(0.0.mm(), 0.0.mm()),
 			layer: None,
+			knockout: false,
+			tstamp: Uuid::nil(),
+			effects: Effects::new(Font::new(1.0.mm())),
+			render_cache: None
+		}
+	}
+
+	sexpr_test_case! {
+		name: locked_knockout_text,
+		input: r#"(gr_text locked "Minnow R1" (at 168 68.75) (layer "F.SilkS" knockout) (tstamp "00000000-0000-0000-0000-000000000000") (effects (font (face "DejaVu Sans") (size 1 1))))"#,
+		value: Text {
+			locked: true,
+			text: "Minnow R1".to_owned(),
+			position: Position::new(168.0.mm(), 68.75.mm()),
+			layer: Some(Layer::new("F.SilkS")),
+			knockout: true,
 			tstamp: Uuid::nil(),
-			effects: Effects::new(Font::new(1.0.mm()))
+			effects: Effects::new(Font {
+				face: Some("DejaVu Sans".to_owned()),
+				..Font::new(1.0.mm())
+			}),
+			render_cache: None
 		}
 	}
 }
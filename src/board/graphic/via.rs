@@ -0,0 +1,196 @@
+use crate::{
+	board::CanonicalLayer,
+	common::Point,
+	internal::{option_unit, rename, tstamp, tuple},
+	mm
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The type of a [`Via`]. Through vias have no type keyword.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ViaType {
+	#[default]
+	Through,
+	/// A blind or buried via.
+	Blind,
+	Micro
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename = "via")]
+struct ViaDef {
+	blind: bool,
+
+	micro: bool,
+
+	locked: bool,
+
+	#[serde(with = "rename::at")]
+	position: Point,
+
+	#[serde(with = "tuple")]
+	size: mm,
+
+	#[serde(with = "tuple")]
+	drill: mm,
+
+	layers: Vec<String>,
+
+	#[serde(with = "option_unit")]
+	remove_unused_layers: bool,
+
+	#[serde(with = "option_unit")]
+	keep_end_layers: bool,
+
+	#[serde(with = "option_unit")]
+	free: bool,
+
+	#[serde(with = "tuple")]
+	net: u32,
+
+	#[serde(
+		default,
+		with = "tstamp",
+		skip_serializing_if = "crate::skip_uuid"
+	)]
+	tstamp: Uuid
+}
+
+impl From<ViaDef> for Via {
+	fn from(def: ViaDef) -> Self {
+		let via_type = match (def.blind, def.micro) {
+			(true, _) => ViaType::Blind,
+			(_, true) => ViaType::Micro,
+			_ => ViaType::Through
+		};
+		Self {
+			via_type,
+			locked: def.locked,
+			position: def.position,
+			size: def.size,
+			drill: def.drill,
+			layers: def.layers,
+			remove_unused_layers: def.remove_unused_layers,
+			keep_end_layers: def.keep_end_layers,
+			free: def.free,
+			net: def.net,
+			tstamp: def.tstamp
+		}
+	}
+}
+
+impl From<Via> for ViaDef {
+	fn from(via: Via) -> Self {
+		Self {
+			blind: via.via_type == ViaType::Blind,
+			micro: via.via_type == ViaType::Micro,
+			locked: via.locked,
+			position: via.position,
+			size: via.size,
+			drill: via.drill,
+			layers: via.layers,
+			remove_unused_layers: via.remove_unused_layers,
+			keep_end_layers: via.keep_end_layers,
+			free: via.free,
+			net: via.net,
+			tstamp: via.tstamp
+		}
+	}
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(from = "ViaDef", into = "ViaDef")]
+pub struct Via {
+	pub via_type: ViaType,
+
+	/// Defines a flag to indicate the item cannot be edited.
+	pub locked: bool,
+
+	pub position: Point,
+
+	/// The diameter of the via.
+	pub size: mm,
+
+	/// The drill diameter of the via.
+	pub drill: mm,
+
+	/// The two copper layers the via connects.
+	pub layers: Vec<String>,
+
+	pub remove_unused_layers: bool,
+
+	pub keep_end_layers: bool,
+
+	/// Defines a flag to indicate the via is not updated automatically to the
+	/// net of the tracks it connects.
+	pub free: bool,
+
+	pub net: u32,
+
+	pub tstamp: Uuid
+}
+
+impl Via {
+	/// Return `true` if neither of the layers the via connects is an outer
+	/// copper layer.
+	pub fn is_buried(&self) -> bool {
+		!self.layers.iter().any(|layer| {
+			matches!(
+				CanonicalLayer::from_name(layer),
+				CanonicalLayer::FCu | CanonicalLayer::BCu
+			)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{sexpr_test_case, Unit};
+
+	fn via(via_type: ViaType, layers: [&str; 2]) -> Via {
+		Via {
+			via_type,
+			locked: false,
+			position: Point::new(1.0.mm(), 2.0.mm()),
+			size: 0.5.mm(),
+			drill: 0.3.mm(),
+			layers: layers.map(String::from).to_vec(),
+			remove_unused_layers: false,
+			keep_end_layers: false,
+			free: false,
+			net: 1,
+			tstamp: Uuid::nil()
+		}
+	}
+
+	sexpr_test_case! {
+		name: through_via,
+		input: r#"(via (at 1 2) (size 0.5) (drill 0.3) (layers "F.Cu" "B.Cu") (net 1) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: via(ViaType::Through, ["F.Cu", "B.Cu"])
+	}
+
+	sexpr_test_case! {
+		name: micro_via,
+		input: r#"(via micro (at 1 2) (size 0.5) (drill 0.3) (layers "F.Cu" "In1.Cu") (net 1) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: via(ViaType::Micro, ["F.Cu", "In1.Cu"])
+	}
+
+	sexpr_test_case! {
+		name: locked_blind_via,
+		input: r#"(via blind locked (at 1 2) (size 0.5) (drill 0.3) (layers "In1.Cu" "In2.Cu") (free) (net 1) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Via {
+			locked: true,
+			free: true,
+			..via(ViaType::Blind, ["In1.Cu", "In2.Cu"])
+		}
+	}
+
+	#[test]
+	fn buried() {
+		assert!(!via(ViaType::Through, ["F.Cu", "B.Cu"]).is_buried());
+		assert!(!via(ViaType::Micro, ["F.Cu", "In1.Cu"]).is_buried());
+		assert!(via(ViaType::Blind, ["In1.Cu", "In2.Cu"]).is_buried());
+	}
+}
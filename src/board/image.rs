@@ -0,0 +1,125 @@
+use crate::{
+	board::Layer,
+	common::Point,
+	internal::{base64, option_tuple, rename, tstamp}
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
+
+/// A bitmap image embedded in the board, usually a logo on a silkscreen layer.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "image")]
+pub struct Image {
+	#[serde(with = "rename::at")]
+	pub position: Point,
+
+	pub layer: Layer,
+
+	#[serde(with = "option_tuple")]
+	pub scale: Option<f32>,
+
+	/// The base64 encoded PNG data of the image.
+	#[serde(with = "data")]
+	pub data: String,
+
+	#[serde(with = "tstamp", skip_serializing_if = "crate::skip_uuid")]
+	pub tstamp: Uuid
+}
+
+impl Image {
+	/// Create a new image from the raw PNG data.
+	pub fn new(position: Point, layer: Layer, bytes: &[u8]) -> Self {
+		Self {
+			position,
+			layer,
+			scale: None,
+			data: Self::encode(bytes),
+			tstamp: Uuid::new_v4()
+		}
+	}
+
+	/// Return the raw image data.
+	pub fn decode(&self) -> Vec<u8> {
+		base64::decode(&self.data)
+	}
+
+	/// Encode raw image data the way it is stored in the board file.
+	pub fn encode(bytes: &[u8]) -> String {
+		base64::encode(bytes)
+	}
+}
+
+/// The (de)serializer for the data of an image. KiCad splits the data into
+/// multiple chunks.
+mod data {
+	use super::*;
+
+	/// The number of characters per chunk, taken from KiCad.
+	const CHUNK_LEN: usize = 76;
+
+	#[derive(Deserialize, Serialize)]
+	#[serde(deny_unknown_fields, rename = "data")]
+	struct Data {
+		#[serde(default, rename = "")]
+		chunks: Vec<String>
+	}
+
+	pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+	where
+		D: Deserializer<'de>
+	{
+		Data::deserialize(deserializer).map(|data| data.chunks.concat())
+	}
+
+	pub(super) fn serialize<S>(this: &str, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		let chunks = this
+			.as_bytes()
+			.chunks(CHUNK_LEN)
+			.map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+			.collect();
+		Data { chunks }.serialize(serializer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{sexpr_test_case, Unit};
+
+	const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+	sexpr_test_case! {
+		name: image,
+		input: r#"(image (at 10 20) (layer "F.SilkS") (scale 0.5) (data "iVBORw0KGgo=") (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: Image {
+			position: Point::new(10.0.mm(), 20.0.mm()),
+			layer: Layer::new("F.SilkS"),
+			scale: Some(0.5),
+			data: "iVBORw0KGgo=".to_owned(),
+			tstamp: Uuid::nil()
+		}
+	}
+
+	#[test]
+	fn decode_image() {
+		let input = r#"(image (at 0 0) (layer "F.SilkS") (data "iVBO" "Rw0KGgo="))"#;
+		let image: Image = serde_sexpr::from_str(input).unwrap();
+		assert_eq!(image.decode(), PNG_HEADER);
+	}
+
+	#[test]
+	fn encode_image() {
+		let bytes: Vec<u8> = (0..=255).collect();
+		let image = Image::new(
+			Point::new(0.0.mm(), 0.0.mm()),
+			Layer::new("F.SilkS"),
+			&bytes
+		);
+		let written = serde_sexpr::to_string(&image).unwrap();
+		let parsed: Image = serde_sexpr::from_str(&written).unwrap();
+		assert_eq!(parsed.decode(), bytes);
+	}
+}
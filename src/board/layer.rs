@@ -1,3 +1,4 @@
+use super::CanonicalLayer;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -11,6 +12,10 @@ impl Layer {
 	{
 		Self(layer.into())
 	}
+
+	pub fn canonical(&self) -> CanonicalLayer {
+		CanonicalLayer::from_name(&self.0)
+	}
 }
 
 #[cfg(test)]
@@ -23,4 +28,21 @@ mod tests {
 		input: r#"(layer "F.Cu")"#,
 		value: Layer::new("F.Cu")
 	}
+
+	#[test]
+	fn canonical() {
+		assert_eq!(
+			Layer::new("Edge.Cuts").canonical(),
+			CanonicalLayer::EdgeCuts
+		);
+	}
+
+	proptest::proptest! {
+		#[test]
+		fn layer_round_trip(layer in crate::internal::proptest::layer()) {
+			let written = serde_sexpr::to_string(&layer).unwrap();
+			let parsed: Layer = serde_sexpr::from_str(&written).unwrap();
+			proptest::prop_assert_eq!(parsed, layer, "{}", written);
+		}
+	}
 }
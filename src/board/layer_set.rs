@@ -0,0 +1,128 @@
+use super::pcb::{Layer, LayersList};
+use std::{
+	convert::Infallible,
+	fmt::{self, Display, Formatter},
+	str::FromStr
+};
+
+/// A layer specification as used by pads, which may name a single layer or a
+/// set of layers using wildcards, like `*.Cu` for all copper layers or
+/// `F&B.Cu` for the front and back copper layers.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum LayerSet {
+	/// `*.<suffix>`, all layers with the suffix, e.g. `*.Mask`.
+	All(String),
+	/// `F&B.<suffix>`, the front and back layer with the suffix.
+	FrontAndBack(String),
+	/// A single layer, e.g. `F.Cu`.
+	Single(String)
+}
+
+impl LayerSet {
+	pub fn from_spec(spec: &str) -> Self {
+		if let Some(suffix) = spec.strip_prefix("*.") {
+			Self::All(suffix.to_owned())
+		} else if let Some(suffix) = spec.strip_prefix("F&B.") {
+			Self::FrontAndBack(suffix.to_owned())
+		} else {
+			Self::Single(spec.to_owned())
+		}
+	}
+
+	/// Return `true` if the layer with the given name is part of this set.
+	pub fn contains(&self, name: &str) -> bool {
+		match self {
+			Self::All(suffix) => name
+				.split_once('.')
+				.is_some_and(|(_, layer_suffix)| layer_suffix == suffix),
+			Self::FrontAndBack(suffix) => {
+				name.strip_prefix("F.").or_else(|| name.strip_prefix("B."))
+					== Some(suffix.as_str())
+			},
+			Self::Single(layer) => layer == name
+		}
+	}
+
+	/// Resolve this set against the layers of a board, returning the layers
+	/// that are part of the set in the order of the board's layer stack.
+	pub fn expand(&self, available: &LayersList) -> Vec<Layer> {
+		available
+			.layers
+			.iter()
+			.filter(|layer| self.contains(layer.name()))
+			.cloned()
+			.collect()
+	}
+}
+
+impl Display for LayerSet {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::All(suffix) => write!(f, "*.{suffix}"),
+			Self::FrontAndBack(suffix) => write!(f, "F&B.{suffix}"),
+			Self::Single(layer) => f.write_str(layer)
+		}
+	}
+}
+
+impl FromStr for LayerSet {
+	type Err = Infallible;
+
+	fn from_str(s: &str) -> Result<Self, Infallible> {
+		Ok(Self::from_spec(s))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::board::pcb::LayerType;
+
+	fn layers() -> LayersList {
+		LayersList {
+			layers: vec![
+				Layer::new(0, "F.Cu", LayerType::Signal),
+				Layer::new(1, "In1.Cu", LayerType::Power),
+				Layer::new(2, "In2.Cu", LayerType::Power),
+				Layer::new(31, "B.Cu", LayerType::Signal),
+				Layer::new(38, "B.Mask", LayerType::User),
+				Layer::new(39, "F.Mask", LayerType::User),
+				Layer::new(44, "Edge.Cuts", LayerType::User),
+			]
+		}
+	}
+
+	fn names(layers: Vec<Layer>) -> Vec<String> {
+		layers.iter().map(|layer| layer.name().to_owned()).collect()
+	}
+
+	#[test]
+	fn all_copper() {
+		let set: LayerSet = "*.Cu".parse().unwrap();
+		assert_eq!(set, LayerSet::All("Cu".to_owned()));
+		assert_eq!(names(set.expand(&layers())), [
+			"F.Cu", "In1.Cu", "In2.Cu", "B.Cu"
+		]);
+		assert_eq!(set.to_string(), "*.Cu");
+	}
+
+	#[test]
+	fn front_and_back_copper() {
+		let set = LayerSet::from_spec("F&B.Cu");
+		assert_eq!(names(set.expand(&layers())), ["F.Cu", "B.Cu"]);
+		assert_eq!(set.to_string(), "F&B.Cu");
+	}
+
+	#[test]
+	fn all_mask() {
+		let set = LayerSet::from_spec("*.Mask");
+		assert_eq!(names(set.expand(&layers())), ["B.Mask", "F.Mask"]);
+	}
+
+	#[test]
+	fn single() {
+		let set = LayerSet::from_spec("Edge.Cuts");
+		assert_eq!(names(set.expand(&layers())), ["Edge.Cuts"]);
+		assert!(LayerSet::from_spec("F.SilkS").expand(&layers()).is_empty());
+	}
+}
@@ -3,15 +3,39 @@
 //! This module defines all syntax that is shared across the footprint library and
 //! printed circuit board file formats.
 
+mod canonical_layer;
 mod connect_pads;
+mod diff;
 pub mod footprint;
 mod footprint_module;
+mod gerber;
 pub mod graphic;
+mod image;
 mod layer;
-mod timestamp;
+mod layer_set;
+mod netlist;
 pub mod pcb;
+mod pcb_reader;
+mod sections;
+mod stackup;
+mod stats;
+mod timestamp;
+mod upgrade;
+pub mod zone;
 
+pub use canonical_layer::{CanonicalLayer, LayerRole};
 pub use connect_pads::ConnectPads;
+pub use diff::{Change, DiffEntry, FieldChange, PcbDiff};
 pub use footprint::Footprint;
+pub use gerber::GerberLayer;
+pub use image::Image;
 pub use layer::Layer;
-pub use timestamp::Timestamp;
+pub use layer_set::LayerSet;
+pub use netlist::{Netlist, NetlistNet, PadRef};
+pub use pcb_reader::{PcbItem, PcbReader};
+pub use sections::PcbSections;
+pub use stackup::{StackUp, StackupLayer};
+pub use stats::BoardStats;
+pub use timestamp::{Regenerate, Timestamp, UuidRemap};
+pub use upgrade::UpgradeReport;
+pub use zone::Zone;
@@ -0,0 +1,65 @@
+//! The connectivity of a board, derived from the net assignments of its pads.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A pad of a footprint, identified by the reference designator of the
+/// footprint and the pad number, e.g. `R1` pad `2`.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct PadRef {
+	pub footprint: String,
+	pub pad: String
+}
+
+impl PadRef {
+	pub fn new<F, P>(footprint: F, pad: P) -> Self
+	where
+		F: Into<String>,
+		P: Into<String>
+	{
+		Self {
+			footprint: footprint.into(),
+			pad: pad.into()
+		}
+	}
+}
+
+/// A net of the [`Netlist`] and the pads connected to it.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct NetlistNet {
+	pub name: String,
+	pub pads: BTreeSet<PadRef>
+}
+
+/// The pads connected to each net of a board, returned by
+/// [`PCB::netlist`](crate::pcb::PCB::netlist).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Netlist {
+	/// The nets, keyed by their number.
+	pub nets: BTreeMap<u32, NetlistNet>
+}
+
+impl Netlist {
+	/// Return the net with the given number.
+	pub fn get(&self, number: u32) -> Option<&NetlistNet> {
+		self.nets.get(&number)
+	}
+
+	/// Return the net with the given name.
+	pub fn get_by_name(&self, name: &str) -> Option<&NetlistNet> {
+		self.nets.values().find(|net| net.name == name)
+	}
+
+	/// Add a pad to the net with the given number, creating the net if it does
+	/// not exist yet.
+	pub(crate) fn connect(&mut self, number: u32, name: &str, pad: PadRef) {
+		self.nets
+			.entry(number)
+			.or_insert_with(|| NetlistNet {
+				name: name.to_owned(),
+				pads: BTreeSet::new()
+			})
+			.pads
+			.insert(pad);
+	}
+}
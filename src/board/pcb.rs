@@ -1,340 +1,3520 @@
 //! Parser for the .kicad_pcb [`PCB`] file format and it's sub sections
 //!
 //! Refers to the [KiCad Board File Format](https://dev-docs.kicad.org/en/file-formats/sexpr-pcb/).
-use std::fmt;
-use std::str::FromStr;
-use serde::{Deserialize, Serialize};
+use serde::{
+	de::Error as _,
+	ser::{SerializeSeq, SerializeStruct, SerializeTupleStruct},
+	Deserialize, Deserializer, Serialize, Serializer
+};
+use std::{
+	collections::{BTreeMap, BTreeSet, HashSet},
+	fmt,
+	str::FromStr
+};
+use thiserror::Error;
 // use serde::de::Deserializer;
 use serde_sexpr::untagged;
+use uuid::Uuid;
 
-use crate::mm;
-use crate::internal::{tuple, option_tuple};
-use crate::common::{Paper, TitleBlock};
-use crate::board::graphic::{Arc, Circle, Curve, Line, Polygon, Rectangle, Text, Segment};
-use crate::board::footprint::Footprint;
+#[cfg(feature = "json")]
+use crate::internal::json;
+use crate::{
+	board::{
+		diff,
+		footprint::{self, Footprint},
+		graphic::{
+			Arc, Circle, Curve, Dimension, GraphicItem, Line, Polygon, Rectangle,
+			Segment, Text, Via
+		},
+		upgrade, BoardStats, CanonicalLayer, GerberLayer, Image, LayerRole, Netlist,
+		PadRef, PcbDiff, PcbSections, Regenerate, StackUp, UpgradeReport, UuidRemap,
+		Zone
+	},
+	common::{
+		point_list, EmbeddedFiles, Paper, PaperSize, Point, PointList, Position,
+		TitleBlock
+	},
+	deg,
+	error::{from_node, missing_field},
+	internal::{
+		option_tuple,
+		raw_node::{self, RawNode},
+		scanner, tuple, u32_dec
+	},
+	mm,
+	symbol::StrokeType,
+	Unit
+};
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(
+	Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize,
+)]
 #[serde(deny_unknown_fields, rename = "version")]
 pub struct Version(u32);
 
 impl Default for Version {
-    fn default() -> Self {
-        Self(20211123)
-    }
+	fn default() -> Self {
+		Self(20211123)
+	}
 }
 
 impl Version {
-    pub fn new() -> Self {
-        Self::default()
-    }
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Return the version number, which is the date the format was changed,
+	/// e.g. `20221018`.
+	pub fn number(self) -> u32 {
+		self.0
+	}
+}
+
+impl From<u32> for Version {
+	fn from(number: u32) -> Self {
+		Self(number)
+	}
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename = "general")]
 pub struct General {
-    #[serde(with = "tuple")]
-    pub thickness: mm,
-    #[serde(with = "option_tuple")]
-    pub area: Option<(f32, f32, f32, f32)>,
-    #[serde(with = "option_tuple")]
-    pub no_connects: Option<u32>,
-    #[serde(with = "option_tuple")]
-    pub links: Option<u32>,
-    #[serde(with = "option_tuple")]
-    pub drawings: Option<u32>,
-    #[serde(with = "option_tuple")]
-    pub tracks: Option<u32>,
-    #[serde(with = "option_tuple")]
-    pub zones: Option<u32>,
-    #[serde(with = "option_tuple")]
-    pub modules: Option<u32>,
-    #[serde(with = "option_tuple")]
-    pub nets: Option<u32>,
-}
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+	#[serde(with = "tuple")]
+	pub thickness: mm,
+	#[serde(with = "option_tuple")]
+	pub area: Option<(f32, f32, f32, f32)>,
+	#[serde(with = "option_tuple")]
+	pub no_connects: Option<u32>,
+	#[serde(with = "option_tuple")]
+	pub links: Option<u32>,
+	#[serde(with = "option_tuple")]
+	pub drawings: Option<u32>,
+	#[serde(with = "option_tuple")]
+	pub tracks: Option<u32>,
+	#[serde(with = "option_tuple")]
+	pub zones: Option<u32>,
+	#[serde(with = "option_tuple")]
+	pub modules: Option<u32>,
+	#[serde(with = "option_tuple")]
+	pub nets: Option<u32>
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LayerType {
-    User,
-    Signal,
-    Jumper,
-    Mixed,
-    Power,
+	#[default]
+	User,
+	Signal,
+	Jumper,
+	Mixed,
+	Power
 }
 
 impl fmt::Display for LayerType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            LayerType::User => write!(f, "user"),
-            LayerType::Signal => write!(f, "signal"),
-            LayerType::Jumper => write!(f, "jumper"),
-            LayerType::Mixed => write!(f, "mixed"),
-            LayerType::Power => write!(f, "power"),
-        }
-    }
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			LayerType::User => write!(f, "user"),
+			LayerType::Signal => write!(f, "signal"),
+			LayerType::Jumper => write!(f, "jumper"),
+			LayerType::Mixed => write!(f, "mixed"),
+			LayerType::Power => write!(f, "power")
+		}
+	}
 }
 
-impl Default for LayerType {
-    fn default() -> Self {
-        Self::User
-    }
-}
-
-// TODO Custom serializer/deserializer for LayerList because it has no name and rename "" does not work (leaves space char)
-#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+/// A layer definition inside the [`LayersList`]. The s-expr of a layer definition
+/// has no name, it starts with the layer number instead, so it can only be
+/// deserialized using [`FromStr`].
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
 #[serde(rename = "")]
 pub struct Layer {
-    number: u32,
-    name: String,
-    layer_type: LayerType,
-    #[serde(with = "serde_sexpr::Option")]
-    user: Option<String>, // this changed in KiCad 6, but there's no documentation yet
+	number: u32,
+	name: String,
+	layer_type: LayerType,
+	// this changed in KiCad 6, but there's no documentation yet
+	#[serde(with = "serde_sexpr::Option")]
+	user: Option<String>,
+	/// The layer type as written in the file, if it is not a known [`LayerType`].
+	#[serde(skip)]
+	unknown_type: Option<String>
+}
+
+impl Layer {
+	pub fn new<T>(number: u32, name: T, layer_type: LayerType) -> Self
+	where
+		T: Into<String>
+	{
+		Self {
+			number,
+			name: name.into(),
+			layer_type,
+			user: None,
+			unknown_type: None
+		}
+	}
+
+	pub fn number(&self) -> u32 {
+		self.number
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	pub fn layer_type(&self) -> LayerType {
+		self.layer_type
+	}
+
+	/// The user-visible name of the layer, if it differs from the canonical name.
+	pub fn user_name(&self) -> Option<&str> {
+		self.user.as_deref()
+	}
+
+	/// The layer type as written in the file, if it was not recognised. The
+	/// layer is treated as a [`LayerType::User`] layer in that case.
+	pub fn unknown_type(&self) -> Option<&str> {
+		self.unknown_type.as_deref()
+	}
+
+	/// The layer type as written in the file.
+	fn type_name(&self) -> String {
+		match &self.unknown_type {
+			Some(layer_type) => layer_type.clone(),
+			None => self.layer_type.to_string()
+		}
+	}
+}
+
+/// Parse a layer type, returning the type name as well if it is unknown.
+fn parse_layer_type(layer_type: &str) -> (LayerType, Option<String>) {
+	match layer_type {
+		"user" => (LayerType::User, None),
+		"signal" => (LayerType::Signal, None),
+		"jumper" => (LayerType::Jumper, None),
+		"mixed" => (LayerType::Mixed, None),
+		"power" => (LayerType::Power, None),
+		_ => (LayerType::default(), Some(layer_type.to_owned()))
+	}
 }
 
 impl FromStr for Layer {
-    type Err = serde_sexpr_base::Error;
-
-    fn from_str(s: &str) -> Result<Self, serde_sexpr_base::Error> {
-        match serde_sexpr_base::from_str::<(u32, String, String, String)>(s) {
-            Ok(layer_tuple) => {
-                Ok(Self {
-                    number: layer_tuple.0,
-                    name: layer_tuple.1.replace("\"", ""),
-                    layer_type: match layer_tuple.2.as_str() {
-                        "user" => LayerType::User,
-                        "signal" => LayerType::Signal,
-                        "jumper" => LayerType::Jumper,
-                        "mixed" => LayerType::Mixed,
-                        "power" => LayerType::Power,
-                        _ => LayerType::default(),
-                    },
-                    user: Some(layer_tuple.3),
-                })
-            }
-            Err(_) => {
-                let layer_tuple = serde_sexpr_base::from_str::<(u32, String, String)>(s)?;
-                Ok(Self {
-                    number: layer_tuple.0,
-                    name: layer_tuple.1.replace("\"", ""),
-                    layer_type: match layer_tuple.2.as_str() {
-                        "user" => LayerType::User,
-                        "signal" => LayerType::Signal,
-                        "jumper" => LayerType::Jumper,
-                        "mixed" => LayerType::Mixed,
-                        "power" => LayerType::Power,
-                        _ => LayerType::default(),
-                    },
-                    user: None,
-                })
-            }
-        }
-    }
-}
-
-impl ToString for Layer {
-    fn to_string(&self) -> String {
-        match self.user {
-            Some(ref user) => format!("({} \"{}\" {} {})", self.number, self.name, self.layer_type, user),
-            None => format!("({} \"{}\" {})", self.number, self.name, self.layer_type),
-        }
-    }
+	type Err = serde_sexpr::de::Error;
+
+	fn from_str(s: &str) -> Result<Self, serde_sexpr::de::Error> {
+		let (number, elements) = scanner::elements(s)?;
+		let number = number.parse().map_err(serde_sexpr::de::Error::custom)?;
+		let atoms = elements
+			.iter()
+			.map(|element| match element.name {
+				"" => Ok(scanner::unquote(element.text)),
+				name => Err(serde_sexpr::de::Error::custom(format!(
+					"unexpected s-expr {name} in layer definition"
+				)))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		let (name, layer_type, user) = match atoms.as_slice() {
+			[name, layer_type] => (name, layer_type, None),
+			[name, layer_type, user] => (name, layer_type, Some(user.to_string())),
+			_ => {
+				return Err(serde_sexpr::de::Error::custom(
+					"expected layer number, name, type and optional user name"
+				))
+			},
+		};
+		let (layer_type, unknown_type) = parse_layer_type(layer_type);
+		Ok(Self {
+			number,
+			name: name.to_string(),
+			layer_type,
+			user,
+			unknown_type
+		})
+	}
+}
+
+impl fmt::Display for Layer {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"({} {} {}",
+			self.number,
+			scanner::quote(&self.name),
+			self.type_name()
+		)?;
+		if let Some(user) = &self.user {
+			write!(f, " {}", scanner::quote(user))?;
+		}
+		f.write_str(")")
+	}
+}
+
+impl Serialize for Layer {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		// the layer number takes the place of the s-expr name
+		let len = if self.user.is_some() { 3 } else { 2 };
+		let mut st = serializer.serialize_tuple_struct(u32_dec(self.number), len)?;
+		st.serialize_field(&self.name)?;
+		match &self.unknown_type {
+			Some(layer_type) => st.serialize_field(layer_type)?,
+			None => st.serialize_field(&self.layer_type)?
+		}
+		if let Some(user) = &self.user {
+			st.serialize_field(user)?;
+		}
+		st.end()
+	}
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename = "layers")]
 pub struct LayersList {
-    #[serde(default, rename = "")]
-    pub layers: Vec<Layer>
+	#[serde(default, rename = "")]
+	pub layers: Vec<Layer>
+}
+
+impl FromStr for LayersList {
+	type Err = serde_sexpr::de::Error;
+
+	fn from_str(s: &str) -> Result<Self, serde_sexpr::de::Error> {
+		let (name, nodes) = scanner::children(s)?;
+		if name != "layers" {
+			return Err(serde_sexpr::de::Error::custom(format!(
+				"expected s-expr layers, found {name}"
+			)));
+		}
+		let layers = nodes
+			.into_iter()
+			.map(|node| Layer::from_str(node.text))
+			.collect::<Result<_, _>>()
+			.map_err(serde_sexpr::de::Error::custom)?;
+		Ok(Self { layers })
+	}
+}
+
+impl LayersList {
+	/// Check the layer definitions for duplicate numbers and names, and for
+	/// layer types that are not known.
+	pub fn validate(&self) -> Result<(), Vec<LayerError>> {
+		let mut errors = Vec::new();
+
+		let mut numbers: BTreeMap<u32, usize> = BTreeMap::new();
+		let mut names: BTreeMap<&str, usize> = BTreeMap::new();
+		for layer in &self.layers {
+			*numbers.entry(layer.number).or_default() += 1;
+			*names.entry(&layer.name).or_default() += 1;
+		}
+		errors.extend(
+			numbers.into_iter().filter(|(_, count)| *count > 1).map(
+				|(number, count)| LayerError::DuplicateNumber { number, count }
+			)
+		);
+		errors.extend(names.into_iter().filter(|(_, count)| *count > 1).map(
+			|(name, count)| LayerError::DuplicateName {
+				name: name.to_owned(),
+				count
+			}
+		));
+		errors.extend(self.layers.iter().filter_map(|layer| {
+			layer
+				.unknown_type
+				.as_ref()
+				.map(|layer_type| LayerError::UnknownType {
+					number: layer.number,
+					layer_type: layer_type.clone()
+				})
+		}));
+
+		match errors.is_empty() {
+			true => Ok(()),
+			false => Err(errors)
+		}
+	}
+
+	/// Return a copy of this list with the layers ordered by their number.
+	pub fn sorted(&self) -> Self {
+		let mut layers = self.layers.clone();
+		layers.sort_by_key(|layer| layer.number);
+		Self { layers }
+	}
+}
+
+/// A problem with the layer definitions, returned by [`LayersList::validate`].
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum LayerError {
+	#[error("Layer number {number} is defined {count} times")]
+	DuplicateNumber { number: u32, count: usize },
+
+	#[error("Layer {name:?} is defined {count} times")]
+	DuplicateName { name: String, count: usize },
+
+	#[error("Layer {number} has unknown type {layer_type:?}")]
+	UnknownType { number: u32, layer_type: String }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename = "setup")]
-// TODO
+// TODO the remaining design rules and plot parameters
 pub struct Setup {
+	#[serde(with = "serde_sexpr::Option")]
+	pub stackup: Option<StackUp>,
+	/// The sections that are not modelled (yet), recorded as their s-expr text
+	/// when parsing in [`ParseMode::Lenient`]. These are written back after the
+	/// modelled sections.
+	#[serde(
+		rename = "",
+		skip_deserializing,
+		serialize_with = "raw_node::serialize",
+		skip_serializing_if = "Vec::is_empty"
+	)]
+	pub unknown: Vec<String>
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename = "property")]
-pub struct Property {
-    pub key: String,
-    pub value: String,
+impl Setup {
+	/// Parse the board setup. Sections that cannot be parsed (yet) are recorded
+	/// in [`unknown`](Self::unknown) or rejected, depending on the `mode`.
+	pub fn from_str_with(s: &str, mode: ParseMode) -> Result<Self, crate::Error> {
+		let (name, nodes) = scanner::children(s)?;
+		if name != "setup" {
+			return Err(serde_sexpr::de::Error::custom(format!(
+				"expected s-expr setup, found {name}"
+			))
+			.into());
+		}
+		let mut setup = Self::default();
+		for node in nodes {
+			if node.name == "stackup" {
+				setup.stackup = Some(serde_sexpr::from_str(node.text)?);
+			} else if mode == ParseMode::Strict {
+				return Err(unknown_node(node.name));
+			} else {
+				setup.unknown.push(raw_node::canonical(node.text)?);
+			}
+		}
+		Ok(setup)
+	}
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename = "zone")]
-// TODO
-pub struct Zone {
+impl FromStr for Setup {
+	type Err = serde_sexpr::de::Error;
+
+	/// Parse the board setup. Sections that cannot be parsed (yet) are recorded
+	/// in [`unknown`](Self::unknown).
+	fn from_str(s: &str) -> Result<Self, serde_sexpr::de::Error> {
+		Self::from_str_with(s, ParseMode::Lenient).map_err(|err| match err {
+			crate::Error::Parse { source, .. } => source
+		})
+	}
 }
 
+/// A property of the board or a footprint, e.g. a variant name used by text
+/// variables. Board properties only have a key and a value, the remaining
+/// fields are used by footprint properties like the reference.
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename = "dimension")]
-// TODO
-pub struct Dimension {
+#[serde(rename = "property")]
+pub struct Property {
+	pub key: String,
+	pub value: String,
+	#[serde(with = "option_tuple")]
+	pub id: Option<u32>,
+	#[serde(rename = "at", with = "serde_sexpr::Option")]
+	pub position: Option<Position>,
+	#[serde(with = "serde_sexpr::Option")]
+	pub layer: Option<crate::board::Layer>,
+	pub hide: bool
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename = "net")]
 pub struct Net {
-    pub number: u8,
-    pub name: String,
+	pub number: u32,
+
+	/// The name of the net. Some tools write the unconnected net without a
+	/// name, so an empty name is omitted when writing the net.
+	#[serde(
+		default,
+		deserialize_with = "deserialize_net_name",
+		skip_serializing_if = "String::is_empty"
+	)]
+	pub name: String
 }
 
-untagged! {
-    #[derive(Clone, Debug, PartialEq)]
-    /// Parts of the PCB file which are not always present
-    pub enum PCBContent {
-        #[serde(skip)]
-        Setup(Setup),
-        Property(Property),
-        #[serde(skip)]
-        Net(Net),
-        #[serde(skip)]
-        Footprint(Footprint),
-        // Would be nice to use GraphicItem for this but untagged inside untagged does not seem to work
-        GrArc(Arc),
-        GrCircle(Circle),
-        GrCurve(Curve),
-        GrLine(Line),
-        GrPoly(Polygon),
-        GrRect(Rectangle),
-        GrText(Text),
-        #[serde(skip)]
-        Segment(Segment),
-        #[serde(skip)]
-        Dimension(Dimension),
-        #[serde(skip)]
-        Zone(Zone)
-        // Images(Images),
-        // Tracks(Tracks),
-        // Zones(Zones),
-    }
+fn deserialize_net_name<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+	D: Deserializer<'de>
+{
+	serde_sexpr::deserialize_option(deserializer).map(Option::unwrap_or_default)
+}
+
+impl Net {
+	/// Return `true` if this is net `0`, which KiCad assigns to everything that
+	/// is not connected to any net.
+	pub fn is_unconnected(&self) -> bool {
+		self.number == 0
+	}
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Net {
+	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+		Ok(Self {
+			number: u.arbitrary()?,
+			name: u.arbitrary()?
+		})
+	}
 }
 
+/// A problem with the nets of a board, returned by [`PCB::validate_nets`].
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum NetError {
+	#[error("Net {number} is defined {count} times")]
+	DuplicateNumber { number: u32, count: usize },
+
+	#[error("Net {0} is missing, net numbers are not sequential")]
+	MissingNumber(u32),
+
+	#[error("Undefined net {0} is referenced")]
+	UndefinedNet(u32),
+
+	#[error("Net {number} is referenced as {found:?} but defined as {expected:?}")]
+	NameMismatch {
+		number: u32,
+		expected: String,
+		found: String
+	},
+
+	#[error("Net name {name:?} is already used by net {number}")]
+	NameInUse { name: String, number: u32 },
+
+	#[error("All net numbers are in use")]
+	NoNumberLeft
+}
+
+/// A group of board items, referenced by their unique identifiers.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "GroupDef", into = "GroupDef")]
+pub struct Group {
+	pub name: String,
+	pub id: Uuid,
+	pub members: Vec<Uuid>
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename = "group")]
+struct GroupDef {
+	name: String,
+	#[serde(with = "tuple")]
+	id: Uuid,
+	members: GroupMembers
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename = "members")]
+struct GroupMembers {
+	#[serde(default, rename = "")]
+	members: Vec<Uuid>
+}
+
+impl From<GroupDef> for Group {
+	fn from(def: GroupDef) -> Self {
+		Self {
+			name: def.name,
+			id: def.id,
+			members: def.members.members
+		}
+	}
+}
+
+impl From<Group> for GroupDef {
+	fn from(group: Group) -> Self {
+		Self {
+			name: group.name,
+			id: group.id,
+			members: GroupMembers {
+				members: group.members
+			}
+		}
+	}
+}
+
+untagged! {
+	#[derive(Clone, Debug, PartialEq)]
+	/// Parts of the PCB file which are not always present
+	pub enum PCBContent {
+		Setup(Setup),
+		Property(Property),
+		Net(Net),
+		Footprint(Footprint),
+		// Would be nice to use GraphicItem for this but untagged inside untagged does not seem to work
+		GrArc(Arc),
+		GrCircle(Circle),
+		GrCurve(Curve),
+		GrLine(Line),
+		GrPoly(Polygon),
+		GrRect(Rectangle),
+		GrText(Text),
+		Segment(Segment),
+		Via(Via),
+		Dimension(Dimension),
+		Zone(Zone),
+		Group(Group),
+		Image(Image),
+		EmbeddedFiles(EmbeddedFiles)
+		// Tracks(Tracks),
+	}
+}
+
+impl From<GraphicItem> for PCBContent {
+	fn from(item: GraphicItem) -> Self {
+		match item {
+			GraphicItem::Arc(arc) => Self::GrArc(arc),
+			GraphicItem::Circle(circle) => Self::GrCircle(circle),
+			GraphicItem::Curve(curve) => Self::GrCurve(curve),
+			GraphicItem::Dimension(dimension) => Self::Dimension(dimension),
+			GraphicItem::Line(line) => Self::GrLine(line),
+			GraphicItem::Poly(poly) => Self::GrPoly(poly),
+			GraphicItem::Rect(rect) => Self::GrRect(rect),
+			GraphicItem::Text(text) => Self::GrText(text)
+		}
+	}
+}
+
+impl PCBContent {
+	/// Return the unique identifier of this item, unless it has none or it is nil.
+	pub fn tstamp(&self) -> Option<Uuid> {
+		let tstamp = match self {
+			Self::GrArc(arc) => arc.tstamp,
+			Self::GrCircle(circle) => circle.tstamp,
+			Self::GrCurve(curve) => curve.tstamp,
+			Self::GrLine(line) => line.tstamp,
+			Self::GrPoly(poly) => poly.tstamp,
+			Self::GrRect(rect) => rect.tstamp,
+			Self::GrText(text) => text.tstamp,
+			Self::Segment(segment) => segment.tstamp,
+			Self::Via(via) => via.tstamp,
+			Self::Dimension(dimension) => dimension.tstamp,
+			Self::Zone(zone) => zone.tstamp,
+			Self::Image(image) => image.tstamp,
+			Self::Group(group) => group.id,
+			Self::Footprint(footprint) => footprint.tstamp?,
+			Self::Setup(_)
+			| Self::Property(_)
+			| Self::Net(_)
+			| Self::EmbeddedFiles(_) => return None
+		};
+		(!tstamp.is_nil()).then_some(tstamp)
+	}
+
+	/// Return the stroke type of this item, if it is a graphic drawn with a
+	/// stroke.
+	pub fn stroke_type_mut(&mut self) -> Option<&mut Option<StrokeType>> {
+		match self {
+			Self::GrArc(arc) => Some(&mut arc.stroke_type),
+			Self::GrCircle(circle) => Some(&mut circle.stroke_type),
+			Self::GrCurve(curve) => Some(&mut curve.stroke_type),
+			Self::GrLine(line) => Some(&mut line.stroke_type),
+			Self::GrPoly(poly) => Some(&mut poly.stroke_type),
+			Self::GrRect(rect) => Some(&mut rect.stroke_type),
+			_ => None
+		}
+	}
+}
+
+/// How [`PCB::from_str_with`] handles nodes that cannot be parsed (yet).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ParseMode {
+	/// Fail on the first node that cannot be parsed.
+	Strict,
+	/// Skip unknown nodes, recording their s-expr text in the `unknown` field of
+	/// the containing section. Known nodes that cannot be parsed are still
+	/// rejected.
+	#[default]
+	Lenient
+}
+
+fn unknown_node(name: &str) -> crate::Error {
+	crate::Error::from(serde_sexpr::de::Error::custom(format!(
+		"unknown node `{name}`"
+	)))
+}
+
+/// A top-level node of a board that is not modelled (yet), recorded as its
+/// s-expr text.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename = "unknown")]
+pub struct UnknownNode {
+	/// The number of [`PCB::pcb_content`] items that precede the node, i.e. the
+	/// node is written back in front of the item with this index.
+	pub index: usize,
+
+	pub text: String
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename = "kicad_pcb")]
 pub struct PCB {
-    pub version: Version,
-    #[serde(with = "tuple", alias = "host")]
-    pub generator: String,
-    pub general: General,
-    pub page: Paper,
-    pub title_block: TitleBlock,
-    #[serde(skip, rename = "layers")]
-    pub layers: Vec<Layer>,
-    #[serde(rename = "")]
-    pub pcb_content: Vec<PCBContent>,
+	pub version: Version,
+	#[serde(with = "tuple")]
+	pub generator: String,
+	/// The version of the program that generated the file, e.g. `8.0`. Only
+	/// written by KiCad 8 and later.
+	#[serde(with = "option_tuple")]
+	pub generator_version: Option<String>,
+	pub general: General,
+	pub page: Paper,
+	pub title_block: TitleBlock,
+	#[serde(skip_deserializing, rename = "layers")]
+	pub layers: Vec<Layer>,
+	#[serde(default, rename = "")]
+	pub pcb_content: Vec<PCBContent>,
+	/// The top-level nodes that are not modelled (yet), recorded when parsing
+	/// in [`ParseMode::Lenient`]. These are written back in between the content
+	/// at their original position, sorted by their index.
+	#[serde(skip)]
+	pub unknown: Vec<UnknownNode>
+}
+
+/// The s-expr of a [`PCB`], which writes the unknown nodes in between the
+/// content.
+#[derive(Serialize)]
+#[serde(rename = "kicad_pcb")]
+struct PcbRef<'a> {
+	version: Version,
+	#[serde(with = "tuple")]
+	generator: &'a str,
+	#[serde(with = "option_tuple")]
+	generator_version: Option<&'a str>,
+	general: &'a General,
+	page: &'a Paper,
+	title_block: &'a TitleBlock,
+	#[serde(
+		rename = "layers",
+		serialize_with = "serialize_layers",
+		skip_serializing_if = "<[Layer]>::is_empty"
+	)]
+	layers: &'a [Layer],
+	#[serde(rename = "")]
+	pcb_content: ContentRef<'a>
+}
+
+impl<'a> PcbRef<'a> {
+	fn new(pcb: &'a PCB, unknown: &'a [UnknownNode]) -> Self {
+		Self {
+			version: pcb.version,
+			generator: &pcb.generator,
+			generator_version: pcb.generator_version.as_deref(),
+			general: &pcb.general,
+			page: &pcb.page,
+			title_block: &pcb.title_block,
+			layers: &pcb.layers,
+			pcb_content: ContentRef {
+				content: &pcb.pcb_content,
+				unknown
+			}
+		}
+	}
+}
+
+struct ContentRef<'a> {
+	content: &'a [PCBContent],
+	unknown: &'a [UnknownNode]
+}
+
+impl Serialize for ContentRef<'_> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		let len = self.content.len() + self.unknown.len();
+		let mut seq = serializer.serialize_seq(Some(len))?;
+		let mut unknown = self.unknown.iter().peekable();
+		for (index, content) in self.content.iter().enumerate() {
+			while let Some(node) = unknown.next_if(|node| node.index <= index) {
+				seq.serialize_element(&RawNode(&node.text))?;
+			}
+			seq.serialize_element(content)?;
+		}
+		for node in unknown {
+			seq.serialize_element(&RawNode(&node.text))?;
+		}
+		seq.end()
+	}
+}
+
+impl Serialize for PCB {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		PcbRef::new(self, &self.unknown).serialize(serializer)
+	}
+}
+
+fn serialize_layers<S>(layers: &[Layer], serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: Serializer
+{
+	let mut st = serializer.serialize_struct("layers", 1)?;
+	st.serialize_field("", layers)?;
+	st.end()
+}
+
+impl PCB {
+	pub fn builder() -> PcbBuilder {
+		PcbBuilder::new()
+	}
+
+	/// Return the outline of the board, stitched together from the lines, arcs,
+	/// rectangles and polygons on the `Edge.Cuts` layer. Arcs are approximated
+	/// by their chords. The first point is repeated at the end to close the
+	/// ring. Returns `None` if the edges do not form a single closed ring, e.g.
+	/// if the outline is open or the board has cutouts.
+	pub fn board_outline(&self) -> Option<PointList> {
+		let is_edge = |layer: &Option<crate::board::Layer>| {
+			layer.as_ref().is_some_and(|layer| {
+				CanonicalLayer::from_name(&layer.0) == CanonicalLayer::EdgeCuts
+			})
+		};
+		let mut segments = Vec::new();
+		for content in &self.pcb_content {
+			match content {
+				PCBContent::GrLine(line) if is_edge(&line.layer) => {
+					segments.push((line.start, line.end));
+				},
+				PCBContent::GrArc(arc) if is_edge(&arc.layer) => {
+					segments.push((arc.start, arc.mid));
+					segments.push((arc.mid, arc.end));
+				},
+				PCBContent::GrRect(rect) if is_edge(&rect.layer) => {
+					let (start, end) = (rect.start, rect.end);
+					let corners = [
+						start,
+						Point::new(end.x, start.y),
+						end,
+						Point::new(start.x, end.y)
+					];
+					for i in 0..4 {
+						segments.push((corners[i], corners[(i + 1) % 4]));
+					}
+				},
+				PCBContent::GrPoly(poly) if is_edge(&poly.layer) => {
+					let pts = &poly.pts;
+					for i in 0..pts.len() {
+						segments.push((pts[i], pts[(i + 1) % pts.len()]));
+					}
+				},
+				_ => {}
+			}
+		}
+		let mut outline = point_list::stitch(&segments)?;
+		let first = outline[0];
+		outline.push(first);
+		Some(outline)
+	}
+
+	/// Return the layers of the board that have the given role, e.g. all
+	/// silkscreen layers.
+	pub fn layers_with_role(&self, role: LayerRole) -> impl Iterator<Item = &Layer> {
+		self.layers
+			.iter()
+			.filter(move |layer| CanonicalLayer::from(*layer).role() == role)
+	}
+
+	/// Associate each layer of the board with the Gerber file it is
+	/// conventionally exported to, e.g. `F.Cu` with the top copper layer.
+	pub fn gerber_layer_map(&self) -> Vec<(Layer, GerberLayer)> {
+		self.layers
+			.iter()
+			.map(|layer| {
+				let gerber = GerberLayer::for_layer(&CanonicalLayer::from(layer));
+				(layer.clone(), gerber)
+			})
+			.collect()
+	}
+
+	/// Return all nets of the board, including the unconnected net `0`.
+	pub fn nets(&self) -> impl Iterator<Item = &Net> {
+		self.pcb_content.iter().filter_map(|content| match content {
+			PCBContent::Net(net) => Some(net),
+			_ => None
+		})
+	}
+
+	/// Return all nets of the board except the unconnected net `0`.
+	pub fn connected_nets(&self) -> impl Iterator<Item = &Net> {
+		self.nets().filter(|net| !net.is_unconnected())
+	}
+
+	/// Return the lowest net number that is not used by any net of the board.
+	/// Fails if all net numbers are in use.
+	pub fn next_net_number(&self) -> Result<u32, NetError> {
+		let used: BTreeSet<u32> = self.nets().map(|net| net.number).collect();
+		(0..=u32::MAX)
+			.find(|number| !used.contains(number))
+			.ok_or(NetError::NoNumberLeft)
+	}
+
+	/// Add a net with the given name and return its number. The net is given
+	/// the lowest unused number and is added after the existing nets. If a net
+	/// with that name exists already, its number is returned instead.
+	pub fn add_net(&mut self, name: &str) -> Result<u32, NetError> {
+		if let Some(net) = self.nets().find(|net| net.name == name) {
+			return Ok(net.number);
+		}
+
+		let number = self.next_net_number()?;
+		let index = self
+			.pcb_content
+			.iter()
+			.rposition(|content| {
+				matches!(
+					content,
+					PCBContent::Net(_)
+						| PCBContent::Property(_)
+						| PCBContent::Setup(_)
+				)
+			})
+			.map_or(0, |i| i + 1);
+		self.pcb_content.insert(
+			index,
+			PCBContent::Net(Net {
+				number,
+				name: name.to_owned()
+			})
+		);
+		Ok(number)
+	}
+
+	/// Place a footprint, e.g. from a footprint library, on the board. The
+	/// footprint is moved to `at` and rotated to `rotation`, and flipped if
+	/// `layer` is on the other side of the board. The footprint and all of its
+	/// items receive fresh unique identifiers, so the same footprint can be added
+	/// multiple times. Returns the identifier of the added footprint.
+	pub fn add_footprint(
+		&mut self,
+		mut footprint: Footprint,
+		at: Point,
+		rotation: deg,
+		layer: CanonicalLayer
+	) -> Uuid {
+		// the version and generator are only written in footprint libraries
+		footprint.version = None;
+		footprint.generator = None;
+		footprint.generator_version = None;
+
+		footprint.place(at, rotation);
+		let current = footprint.layer.canonical();
+		if current != layer && current.flipped() == layer {
+			footprint.flip();
+		}
+		footprint.layer = crate::board::Layer::new(layer.name());
+
+		footprint.regenerate_tstamps_with(&mut UuidRemap::new(Regenerate::All));
+		let tstamp = Uuid::new_v4();
+		footprint.tstamp = Some(tstamp);
+
+		self.pcb_content.push(PCBContent::Footprint(footprint));
+		tstamp
+	}
+
+	/// Add a track through `points` on the copper `layer`, connected to `net`.
+	/// A segment with a fresh unique identifier is added between each pair of
+	/// consecutive points. Returns the identifiers of the added segments, or
+	/// [`None`] if there are fewer than two points.
+	pub fn route(
+		&mut self,
+		points: &[Point],
+		width: mm,
+		layer: &str,
+		net: u32
+	) -> Option<Vec<Uuid>> {
+		if points.len() < 2 {
+			return None;
+		}
+		let segments: Vec<Segment> = points
+			.windows(2)
+			.map(|pair| {
+				let layer = crate::board::Layer::new(layer);
+				Segment::new(pair[0], pair[1], width, layer, net)
+			})
+			.collect();
+		let tstamps = segments.iter().map(|segment| segment.tstamp).collect();
+		self.pcb_content
+			.extend(segments.into_iter().map(PCBContent::Segment));
+		Some(tstamps)
+	}
+
+	/// Return the value of the top-level board property `key`.
+	pub fn property(&self, key: &str) -> Option<&str> {
+		self.pcb_content.iter().find_map(|content| match content {
+			PCBContent::Property(property) if property.key == key => {
+				Some(property.value.as_str())
+			},
+			_ => None
+		})
+	}
+
+	/// Set the value of the top-level board property `key`. New properties are
+	/// added after the existing properties, or after the setup if there are none,
+	/// which is where KiCad writes them.
+	pub fn set_property<K, V>(&mut self, key: K, value: V)
+	where
+		K: Into<String>,
+		V: Into<String>
+	{
+		let (key, value) = (key.into(), value.into());
+		let existing =
+			self.pcb_content
+				.iter_mut()
+				.find_map(|content| match content {
+					PCBContent::Property(property) if property.key == key => {
+						Some(property)
+					},
+					_ => None
+				});
+		if let Some(property) = existing {
+			property.value = value;
+			return;
+		}
+
+		let index = self
+			.pcb_content
+			.iter()
+			.rposition(|content| {
+				matches!(content, PCBContent::Property(_) | PCBContent::Setup(_))
+			})
+			.map_or(0, |i| i + 1);
+		self.pcb_content.insert(
+			index,
+			PCBContent::Property(Property {
+				key,
+				value,
+				..Default::default()
+			})
+		);
+	}
+
+	/// Join consecutive track segments that are collinear and share their net,
+	/// layer and width, see [`Segment::join`]. Segments are not joined where
+	/// another segment or a via is connected to the shared point. Pads are not
+	/// taken into account. Returns the number of joins.
+	pub fn merge_collinear_segments(&mut self) -> usize {
+		let mut merged = 0;
+		while let Some((keep, remove, joined)) = self.find_collinear_segments() {
+			self.pcb_content[keep] = PCBContent::Segment(joined);
+			self.pcb_content.remove(remove);
+			merged += 1;
+		}
+		merged
+	}
+
+	fn find_collinear_segments(&self) -> Option<(usize, usize, Segment)> {
+		let segments: Vec<(usize, &Segment)> = self
+			.pcb_content
+			.iter()
+			.enumerate()
+			.filter_map(|(i, content)| match content {
+				PCBContent::Segment(segment) => Some((i, segment)),
+				_ => None
+			})
+			.collect();
+		let vias: Vec<Point> = self
+			.pcb_content
+			.iter()
+			.filter_map(|content| match content {
+				PCBContent::Via(via) => Some(via.position),
+				_ => None
+			})
+			.collect();
+
+		for (n, &(i, a)) in segments.iter().enumerate() {
+			for &(j, b) in &segments[n + 1 ..] {
+				let Some((joined, shared)) = a.join_at(b) else {
+					continue;
+				};
+				let junction = vias.contains(&shared)
+					|| segments.iter().any(|&(k, c)| {
+						k != i
+							&& k != j && c.layer == a.layer
+							&& (c.start == shared || c.end == shared)
+					});
+				if !junction {
+					return Some((i, j, joined));
+				}
+			}
+		}
+		None
+	}
+
+	/// Snap every coordinate of the board to a grid with the given spacing, see
+	/// [`Point::snap_to_grid`]. Footprints are moved as a whole, so their pads
+	/// and graphics keep their position relative to the footprint. A grid
+	/// without spacing leaves the board unchanged.
+	pub fn snap_all(&mut self, grid: mm) {
+		fn snap<'a>(points: impl IntoIterator<Item = &'a mut Point>, grid: mm) {
+			for point in points {
+				*point = point.snap_to_grid(grid);
+			}
+		}
+		let snap_position = |position: &mut Position| {
+			let point = position.point().snap_to_grid(grid);
+			position.x = point.x;
+			position.y = point.y;
+		};
+
+		for content in &mut self.pcb_content {
+			match content {
+				PCBContent::Footprint(footprint) => {
+					if let Some(position) = &mut footprint.position {
+						snap_position(position);
+					}
+				},
+				PCBContent::GrArc(arc) => {
+					snap([&mut arc.start, &mut arc.mid, &mut arc.end], grid)
+				},
+				PCBContent::GrCircle(circle) => {
+					snap([&mut circle.center, &mut circle.end], grid)
+				},
+				PCBContent::GrCurve(curve) => snap(&mut curve.pts.pts, grid),
+				PCBContent::GrLine(line) => {
+					snap([&mut line.start, &mut line.end], grid)
+				},
+				PCBContent::GrPoly(poly) => snap(&mut poly.pts.pts, grid),
+				PCBContent::GrRect(rect) => {
+					snap([&mut rect.start, &mut rect.end], grid)
+				},
+				PCBContent::GrText(text) => snap_position(&mut text.position),
+				PCBContent::Segment(segment) => {
+					snap([&mut segment.start, &mut segment.end], grid)
+				},
+				PCBContent::Via(via) => snap([&mut via.position], grid),
+				PCBContent::Dimension(dimension) => {
+					snap(&mut dimension.pts.pts, grid);
+					if let Some(text) = &mut dimension.text {
+						snap_position(&mut text.position);
+					}
+				},
+				PCBContent::Zone(zone) => {
+					snap(&mut zone.polygon.pts.pts, grid);
+					for filled in &mut zone.filled_polygons {
+						snap(&mut filled.pts.pts, grid);
+					}
+				},
+				PCBContent::Image(image) => snap([&mut image.position], grid),
+				PCBContent::Setup(_)
+				| PCBContent::Property(_)
+				| PCBContent::Net(_)
+				| PCBContent::Group(_)
+				| PCBContent::EmbeddedFiles(_) => {}
+			}
+		}
+	}
+
+	/// Sum up the length of all track segments on the given net. Vias do not
+	/// contribute to the length. Segments on the unconnected net `0` are not
+	/// a net of their own, so their length is always zero, see
+	/// [`total_unconnected_track_length`](Self::total_unconnected_track_length).
+	pub fn total_track_length_for_net(&self, net: u32) -> mm {
+		if net == 0 {
+			return mm::default();
+		}
+		self.track_length(net)
+	}
+
+	/// Sum up the length of all track segments that are not connected to any
+	/// net, i.e. that are on net `0`.
+	pub fn total_unconnected_track_length(&self) -> mm {
+		self.track_length(0)
+	}
+
+	fn track_length(&self, net: u32) -> mm {
+		self.pcb_content
+			.iter()
+			.filter_map(|content| match content {
+				PCBContent::Segment(segment) if segment.net == net => {
+					Some(segment.length())
+				},
+				_ => None
+			})
+			.fold(mm::default(), |sum, length| sum + length)
+	}
+
+	/// Find the closest pair of track segments on the same layer but on
+	/// different nets, and return their edge-to-edge clearance together with
+	/// the tstamps of both segments. Returns [`None`] if no such pair exists.
+	/// Vias, pads and zones are not considered.
+	pub fn min_track_clearance(&self) -> Option<(mm, Uuid, Uuid)> {
+		let segments: Vec<&Segment> = self
+			.pcb_content
+			.iter()
+			.filter_map(|content| match content {
+				PCBContent::Segment(segment) => Some(segment),
+				_ => None
+			})
+			.collect();
+
+		let mut min: Option<(mm, Uuid, Uuid)> = None;
+		for (i, a) in segments.iter().enumerate() {
+			for b in &segments[i + 1 ..] {
+				if a.layer != b.layer || a.net == b.net {
+					continue;
+				}
+				let clearance = a.clearance(b);
+				if min.is_none_or(|(min, ..)| clearance < min) {
+					min = Some((clearance, a.tstamp, b.tstamp));
+				}
+			}
+		}
+		min
+	}
+
+	/// Sum up the area of copper on the given layer in square millimeters, e.g.
+	/// to estimate the copper weight. This includes zones, see
+	/// [`Zone::copper_area_on_layer`], and track segments, which contribute their
+	/// length times their width. Overlapping copper is counted multiple times,
+	/// and keepout areas are not subtracted from the zones.
+	pub fn copper_area_on_layer(&self, layer: &str) -> mm {
+		self.pcb_content
+			.iter()
+			.map(|content| match content {
+				PCBContent::Zone(zone) => zone.copper_area_on_layer(layer),
+				PCBContent::Segment(segment) if segment.layer.0 == layer => {
+					(segment.length().raw_value() * segment.width.raw_value()).mm()
+				},
+				_ => mm::default()
+			})
+			.fold(mm::default(), |sum, area| sum + area)
+	}
+
+	/// Sort the contents of the board by their type in a single pass. This is
+	/// cheaper than filtering [`pcb_content`](Self::pcb_content) repeatedly
+	/// when several kinds of items are needed.
+	pub fn sections(&self) -> PcbSections<'_> {
+		let mut sections = PcbSections {
+			layers: &self.layers,
+			..Default::default()
+		};
+		for content in &self.pcb_content {
+			match content {
+				PCBContent::Setup(setup) => sections.setup = Some(setup),
+				PCBContent::Property(property) => sections.properties.push(property),
+				PCBContent::Net(net) => sections.nets.push(net),
+				PCBContent::Footprint(footprint) => {
+					sections.footprints.push(footprint)
+				},
+				PCBContent::GrArc(arc) => {
+					sections.graphics.push(arc.clone().into())
+				},
+				PCBContent::GrCircle(circle) => {
+					sections.graphics.push(circle.clone().into())
+				},
+				PCBContent::GrCurve(curve) => {
+					sections.graphics.push(curve.clone().into())
+				},
+				PCBContent::GrLine(line) => {
+					sections.graphics.push(line.clone().into())
+				},
+				PCBContent::GrPoly(poly) => {
+					sections.graphics.push(poly.clone().into())
+				},
+				PCBContent::GrRect(rect) => {
+					sections.graphics.push(rect.clone().into())
+				},
+				PCBContent::GrText(text) => {
+					sections.graphics.push(text.clone().into())
+				},
+				PCBContent::Dimension(dimension) => {
+					sections.graphics.push(dimension.clone().into())
+				},
+				PCBContent::Segment(segment) => sections.segments.push(segment),
+				PCBContent::Via(via) => sections.vias.push(via),
+				PCBContent::Zone(zone) => sections.zones.push(zone),
+				PCBContent::Group(group) => sections.groups.push(group),
+				PCBContent::Image(image) => sections.images.push(image),
+				PCBContent::EmbeddedFiles(_) => {}
+			}
+		}
+		sections
+	}
+
+	/// Count the nets, layers, tracks, zones and graphic items of the board and
+	/// sum up the length of all track segments.
+	pub fn statistics(&self) -> BoardStats {
+		let mut stats = BoardStats {
+			layers: self.layers.len(),
+			..Default::default()
+		};
+		for content in &self.pcb_content {
+			match content {
+				PCBContent::Net(_) => stats.nets += 1,
+				PCBContent::Segment(segment) => {
+					stats.tracks += 1;
+					stats.track_length += segment.length();
+				},
+				PCBContent::Via(_) => stats.tracks += 1,
+				PCBContent::Zone(_) => stats.zones += 1,
+				PCBContent::GrArc(_)
+				| PCBContent::GrCircle(_)
+				| PCBContent::GrCurve(_)
+				| PCBContent::GrLine(_)
+				| PCBContent::GrPoly(_)
+				| PCBContent::GrRect(_)
+				| PCBContent::GrText(_)
+				| PCBContent::Dimension(_) => stats.graphics += 1,
+				_ => {}
+			}
+		}
+		stats
+	}
+
+	/// Collect the pads connected to each net from the net assignments of the
+	/// footprint pads. Nets without any pads are included as well. Pads that are
+	/// not connected, i.e. have no net or net `0`, are skipped.
+	pub fn netlist(&self) -> Netlist {
+		let mut netlist = Netlist::default();
+		for content in &self.pcb_content {
+			if let PCBContent::Net(net) = content {
+				netlist.nets.entry(net.number).or_default().name = net.name.clone();
+			}
+		}
+		for content in &self.pcb_content {
+			let PCBContent::Footprint(footprint) = content else {
+				continue;
+			};
+			let reference = footprint.reference().unwrap_or_default();
+			for pad in footprint.pads() {
+				if let Some((number, name)) =
+					pad.net.as_ref().filter(|(n, _)| *n != 0)
+				{
+					netlist.connect(
+						*number,
+						name,
+						PadRef::new(reference, &pad.number)
+					);
+				}
+			}
+		}
+		netlist
+	}
+
+	/// Compare this board with `other`, which is treated as the newer version.
+	/// Items that cannot be written as s-expr are ignored.
+	pub fn diff(&self, other: &PCB) -> PcbDiff {
+		fn nets(pcb: &PCB) -> Vec<(String, String)> {
+			pcb.pcb_content
+				.iter()
+				.filter_map(|content| match content {
+					PCBContent::Net(net) => {
+						Some((net.name.clone(), serde_sexpr::to_string(net).ok()?))
+					},
+					_ => None
+				})
+				.collect()
+		}
+		fn layers(pcb: &PCB) -> Vec<(String, String)> {
+			pcb.layers
+				.iter()
+				.map(|layer| (layer.name.clone(), layer.to_string()))
+				.collect()
+		}
+		fn items(pcb: &PCB) -> Vec<(String, String)> {
+			pcb.pcb_content
+				.iter()
+				.filter(|content| !matches!(content, PCBContent::Net(_)))
+				.filter_map(|content| {
+					let text = serde_sexpr::to_string(content).ok()?;
+					let key = match content.tstamp() {
+						Some(tstamp) => tstamp.to_string(),
+						None => text.clone()
+					};
+					Some((key, text))
+				})
+				.collect()
+		}
+
+		PcbDiff {
+			nets: diff::diff_entries(&nets(self), &nets(other)),
+			layers: diff::diff_entries(&layers(self), &layers(other)),
+			items: diff::diff_entries(&items(self), &items(other))
+		}
+	}
+
+	/// Check the net definitions for duplicate numbers and gaps in the numbering,
+	/// and check that all nets referenced by tracks and zones are defined.
+	pub fn validate_nets(&self) -> Vec<NetError> {
+		let mut errors = Vec::new();
+
+		let mut nets: BTreeMap<u32, Vec<&str>> = BTreeMap::new();
+		for content in &self.pcb_content {
+			if let PCBContent::Net(net) = content {
+				nets.entry(net.number).or_default().push(&net.name);
+			}
+		}
+		for (number, names) in &nets {
+			if names.len() > 1 {
+				errors.push(NetError::DuplicateNumber {
+					number: *number,
+					count: names.len()
+				});
+			}
+		}
+		if let Some(max) = nets.keys().next_back() {
+			errors.extend(
+				(0..*max)
+					.filter(|number| !nets.contains_key(number))
+					.map(NetError::MissingNumber)
+			);
+		}
+
+		let mut undefined = BTreeSet::new();
+		for content in &self.pcb_content {
+			let (number, name) = match content {
+				PCBContent::Segment(segment) => (segment.net, None),
+				PCBContent::Via(via) => (via.net, None),
+				PCBContent::Zone(zone) => (zone.net, Some(&zone.net_name)),
+				_ => continue
+			};
+			match nets.get(&number) {
+				None => {
+					undefined.insert(number);
+				},
+				Some(names) => {
+					if let Some(name) =
+						name.filter(|name| !names.contains(&name.as_str()))
+					{
+						errors.push(NetError::NameMismatch {
+							number,
+							expected: names[0].to_owned(),
+							found: name.to_owned()
+						});
+					}
+				}
+			}
+		}
+		errors.extend(undefined.into_iter().map(NetError::UndefinedNet));
+
+		errors
+	}
+
+	/// Rename net `number` to `new_name`, updating the net names referenced by
+	/// zones and footprint pads as well. Fails if the net is not defined or if
+	/// another net already has the name `new_name`.
+	pub fn rename_net(
+		&mut self,
+		number: u32,
+		new_name: &str
+	) -> Result<(), NetError> {
+		let mut defined = false;
+		for content in &self.pcb_content {
+			match content {
+				PCBContent::Net(net) if net.number == number => defined = true,
+				PCBContent::Net(net) if net.name == new_name => {
+					return Err(NetError::NameInUse {
+						name: new_name.to_owned(),
+						number: net.number
+					});
+				},
+				_ => {}
+			}
+		}
+		if !defined {
+			return Err(NetError::UndefinedNet(number));
+		}
+
+		for content in &mut self.pcb_content {
+			match content {
+				PCBContent::Net(net) if net.number == number => {
+					net.name = new_name.to_owned()
+				},
+				PCBContent::Zone(zone) if zone.net == number => {
+					zone.net_name = new_name.to_owned()
+				},
+				PCBContent::Footprint(footprint) => {
+					for (_, name) in footprint
+						.pads_mut()
+						.filter_map(|pad| pad.net.as_mut())
+						.filter(|(n, _)| *n == number)
+					{
+						*name = new_name.to_owned();
+					}
+				},
+				_ => {}
+			}
+		}
+		Ok(())
+	}
+
+	/// Move all graphic items, tracks, zones, footprint items and pads from layer
+	/// `from` to layer `to`, returning the number of items changed. If `to` is
+	/// not defined in the layer list yet, the definition of `from` is renamed.
+	pub fn remap_layer(&mut self, from: &str, to: &str) -> usize {
+		let mut changed = 0;
+		for content in &mut self.pcb_content {
+			let layer = match content {
+				PCBContent::GrArc(arc) => arc.layer.as_mut(),
+				PCBContent::GrCircle(circle) => circle.layer.as_mut(),
+				PCBContent::GrCurve(curve) => curve.layer.as_mut(),
+				PCBContent::GrLine(line) => line.layer.as_mut(),
+				PCBContent::GrPoly(poly) => poly.layer.as_mut(),
+				PCBContent::GrRect(rect) => rect.layer.as_mut(),
+				PCBContent::GrText(text) => text.layer.as_mut(),
+				PCBContent::Segment(segment) => Some(&mut segment.layer),
+				PCBContent::Image(image) => Some(&mut image.layer),
+				PCBContent::Dimension(dimension) => {
+					if let Some(layer) = dimension
+						.text
+						.as_mut()
+						.and_then(|text| text.layer.as_mut())
+						.filter(|layer| layer.0 == from)
+					{
+						layer.0 = to.to_owned();
+					}
+					Some(&mut dimension.layer)
+				},
+				PCBContent::Footprint(footprint) => {
+					changed += footprint.remap_layer(from, to);
+					None
+				},
+				PCBContent::Zone(zone) => {
+					changed += zone.remap_layer(from, to);
+					None
+				},
+				PCBContent::Via(via) => {
+					for layer in via.layers.iter_mut().filter(|layer| *layer == from)
+					{
+						*layer = to.to_owned();
+						changed += 1;
+					}
+					None
+				},
+				_ => None
+			};
+			if let Some(layer) = layer.filter(|layer| layer.0 == from) {
+				layer.0 = to.to_owned();
+				changed += 1;
+			}
+		}
+
+		if !self.layers.iter().any(|layer| layer.name == to) {
+			if let Some(layer) =
+				self.layers.iter_mut().find(|layer| layer.name == from)
+			{
+				layer.name = to.to_owned();
+			}
+		}
+		changed
+	}
+
+	/// Remove the item with the unique identifier `id`, returning whether an item
+	/// was removed. The identifier is removed from any group as well.
+	pub fn remove_by_tstamp(&mut self, id: Uuid) -> bool {
+		let Some(idx) = self
+			.pcb_content
+			.iter()
+			.position(|content| content.tstamp() == Some(id))
+		else {
+			return false;
+		};
+		self.pcb_content.remove(idx);
+		for content in &mut self.pcb_content {
+			if let PCBContent::Group(group) = content {
+				group.members.retain(|member| *member != id);
+			}
+		}
+		true
+	}
+
+	/// Remove graphic items that have the same content as an earlier graphic
+	/// item, see [`GraphicItem::content_key`], returning the number of items
+	/// removed. The identifiers of removed items are removed from any group.
+	pub fn dedup_graphics(&mut self) -> usize {
+		let before = self.pcb_content.len();
+		let mut seen = HashSet::new();
+		let mut removed = HashSet::new();
+		self.pcb_content.retain(|content| {
+			let key = match content {
+				PCBContent::GrArc(arc) => arc.content_key(),
+				PCBContent::GrCircle(circle) => circle.content_key(),
+				PCBContent::GrCurve(curve) => curve.content_key(),
+				PCBContent::GrLine(line) => line.content_key(),
+				PCBContent::GrPoly(poly) => poly.content_key(),
+				PCBContent::GrRect(rect) => rect.content_key(),
+				PCBContent::GrText(text) => text.content_key(),
+				PCBContent::Dimension(dimension) => dimension.content_key(),
+				_ => return true
+			};
+			if seen.insert(key) {
+				return true;
+			}
+			removed.extend(content.tstamp());
+			false
+		});
+		for content in &mut self.pcb_content {
+			if let PCBContent::Group(group) = content {
+				group.members.retain(|member| !removed.contains(member));
+			}
+		}
+		before - self.pcb_content.len()
+	}
+
+	/// Keep only the board content for which `keep` returns `true`.
+	pub fn retain_content<F>(&mut self, keep: F)
+	where
+		F: FnMut(&PCBContent) -> bool
+	{
+		self.pcb_content.retain(keep);
+	}
+
+	/// Remove all graphic items, tracks, images, dimensions and zones on `layer`,
+	/// returning the number of items removed. Zones spanning several layers,
+	/// vias and footprints are kept.
+	pub fn clear_layer(&mut self, layer: &str) -> usize {
+		let on_layer =
+			|l: Option<&crate::board::Layer>| l.is_some_and(|l| l.0 == layer);
+		let before = self.pcb_content.len();
+		self.pcb_content.retain(|content| {
+			!match content {
+				PCBContent::GrArc(arc) => on_layer(arc.layer.as_ref()),
+				PCBContent::GrCircle(circle) => on_layer(circle.layer.as_ref()),
+				PCBContent::GrCurve(curve) => on_layer(curve.layer.as_ref()),
+				PCBContent::GrLine(line) => on_layer(line.layer.as_ref()),
+				PCBContent::GrPoly(poly) => on_layer(poly.layer.as_ref()),
+				PCBContent::GrRect(rect) => on_layer(rect.layer.as_ref()),
+				PCBContent::GrText(text) => on_layer(text.layer.as_ref()),
+				PCBContent::Segment(segment) => on_layer(Some(&segment.layer)),
+				PCBContent::Image(image) => on_layer(Some(&image.layer)),
+				PCBContent::Dimension(dimension) => {
+					on_layer(Some(&dimension.layer))
+				},
+				PCBContent::Zone(zone) => match &zone.layers {
+					Some(layers) => layers.iter().all(|l| l == layer),
+					None => on_layer(zone.layer.as_ref())
+				},
+				_ => false
+			}
+		});
+		before - self.pcb_content.len()
+	}
+
+	/// Remove the definition of net `number`, returning whether it was defined.
+	/// If `reassign` is set, tracks, vias, zones and footprint pads on the net are
+	/// moved to the unconnected net 0, otherwise their references are left as
+	/// they are.
+	pub fn remove_net(&mut self, number: u32, reassign: bool) -> bool {
+		let before = self.pcb_content.len();
+		self.pcb_content.retain(|content| match content {
+			PCBContent::Net(net) => net.number != number,
+			_ => true
+		});
+		let removed = self.pcb_content.len() != before;
+		if !reassign {
+			return removed;
+		}
+
+		for content in &mut self.pcb_content {
+			match content {
+				PCBContent::Segment(segment) if segment.net == number => {
+					segment.net = 0
+				},
+				PCBContent::Via(via) if via.net == number => via.net = 0,
+				PCBContent::Zone(zone) if zone.net == number => {
+					zone.net = 0;
+					zone.net_name = String::new();
+				},
+				PCBContent::Footprint(footprint) => {
+					for pad in footprint.pads_mut() {
+						if pad.net.as_ref().is_some_and(|(n, _)| *n == number) {
+							pad.net = None;
+						}
+					}
+				},
+				_ => {}
+			}
+		}
+		removed
+	}
+
+	/// Replace the unique identifiers of all board items with freshly generated
+	/// ones. Depending on `mode`, either only nil identifiers or all identifiers
+	/// are replaced. References to replaced identifiers, like group members, are
+	/// updated to match.
+	pub fn regenerate_tstamps(&mut self, mode: Regenerate) {
+		let mut remap = UuidRemap::new(mode);
+		for content in &mut self.pcb_content {
+			let tstamp = match content {
+				PCBContent::GrArc(arc) => &mut arc.tstamp,
+				PCBContent::GrCircle(circle) => &mut circle.tstamp,
+				PCBContent::GrCurve(curve) => &mut curve.tstamp,
+				PCBContent::GrLine(line) => &mut line.tstamp,
+				PCBContent::GrPoly(poly) => &mut poly.tstamp,
+				PCBContent::GrRect(rect) => &mut rect.tstamp,
+				PCBContent::GrText(text) => &mut text.tstamp,
+				PCBContent::Segment(segment) => &mut segment.tstamp,
+				PCBContent::Via(via) => &mut via.tstamp,
+				PCBContent::Zone(zone) => &mut zone.tstamp,
+				PCBContent::Image(image) => &mut image.tstamp,
+				PCBContent::Group(group) => &mut group.id,
+				PCBContent::Dimension(dimension) => {
+					if let Some(text) = dimension.text.as_mut() {
+						remap.regenerate(&mut text.tstamp);
+					}
+					&mut dimension.tstamp
+				},
+				PCBContent::Footprint(footprint) => {
+					footprint.regenerate_tstamps_with(&mut remap);
+					continue;
+				},
+				PCBContent::Setup(_)
+				| PCBContent::Property(_)
+				| PCBContent::Net(_)
+				| PCBContent::EmbeddedFiles(_) => continue
+			};
+			remap.regenerate(tstamp);
+		}
+
+		for content in &mut self.pcb_content {
+			if let PCBContent::Group(group) = content {
+				group.members.iter_mut().for_each(|m| remap.remap(m));
+			}
+		}
+	}
+
+	/// Upgrade the board to a newer file format version. Only one migration is
+	/// implemented: the `(width ..)` of board and footprint graphics is
+	/// converted into the `(stroke (width ..) (type solid))` introduced with
+	/// KiCad 7. Other known format changes are left as they are and listed in
+	/// the returned report. Upgrading to an older or the same version does
+	/// nothing.
+	pub fn upgrade_to(&mut self, target: Version) -> UpgradeReport {
+		let from = self.version;
+		let mut report = UpgradeReport {
+			from,
+			to: from,
+			converted_strokes: 0,
+			not_migrated: Vec::new()
+		};
+		if target <= from {
+			return report;
+		}
+
+		let crosses = |version: u32| from.0 < version && version <= target.0;
+		if crosses(upgrade::STROKE_VERSION) {
+			let mut convert = |stroke_type: Option<&mut Option<StrokeType>>| {
+				if let Some(stroke_type @ None) = stroke_type {
+					*stroke_type = Some(StrokeType::Solid);
+					report.converted_strokes += 1;
+				}
+			};
+			for content in &mut self.pcb_content {
+				if let PCBContent::Footprint(footprint) = content {
+					for content in &mut footprint.content {
+						convert(content.stroke_type_mut());
+					}
+				}
+				convert(content.stroke_type_mut());
+			}
+		}
+		report.not_migrated = upgrade::NOT_MIGRATED
+			.iter()
+			.filter(|(version, _)| crosses(*version))
+			.map(|&(version, change)| (Version(version), change))
+			.collect();
+
+		self.version = target;
+		report.to = target;
+		report
+	}
+}
+
+/// A builder to construct a [`PCB`] programmatically.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PcbBuilder {
+	pcb: PCB
+}
+
+impl Default for PcbBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl PcbBuilder {
+	pub fn new() -> Self {
+		Self {
+			pcb: PCB {
+				version: Version::default(),
+				generator: env!("CARGO_PKG_NAME").to_owned(),
+				generator_version: None,
+				general: General::default(),
+				page: PaperSize::A4.landscape(),
+				title_block: TitleBlock::default(),
+				layers: Vec::new(),
+				pcb_content: Vec::new(),
+				unknown: Vec::new()
+			}
+		}
+	}
+
+	pub fn version(mut self, version: u32) -> Self {
+		self.pcb.version = Version(version);
+		self
+	}
+
+	pub fn generator<T>(mut self, generator: T) -> Self
+	where
+		T: Into<String>
+	{
+		self.pcb.generator = generator.into();
+		self
+	}
+
+	pub fn thickness(mut self, thickness: mm) -> Self {
+		self.pcb.general.thickness = thickness;
+		self
+	}
+
+	pub fn paper(mut self, paper: Paper) -> Self {
+		self.pcb.page = paper;
+		self
+	}
+
+	pub fn title<T>(mut self, title: T) -> Self
+	where
+		T: Into<String>
+	{
+		self.pcb.title_block.title = Some(title.into());
+		self
+	}
+
+	pub fn add_net<T>(mut self, number: u32, name: T) -> Self
+	where
+		T: Into<String>
+	{
+		self.pcb.pcb_content.push(PCBContent::Net(Net {
+			number,
+			name: name.into()
+		}));
+		self
+	}
+
+	pub fn add_graphic<T>(mut self, item: T) -> Self
+	where
+		T: Into<GraphicItem>
+	{
+		self.pcb.pcb_content.push(item.into().into());
+		self
+	}
+
+	pub fn add_layer(mut self, layer: Layer) -> Self {
+		self.pcb.layers.push(layer);
+		self
+	}
+
+	pub fn build(self) -> PCB {
+		self.pcb
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename = "generator")]
+pub(crate) struct Generator(pub(crate) String);
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename = "generator_version")]
+pub(crate) struct GeneratorVersion(pub(crate) String);
+
+impl PCB {
+	/// Parse a board file. The header sections are required, the handling of
+	/// all other sections that cannot be parsed (yet) depends on the `mode`.
+	/// Line comments starting with `;` are ignored.
+	pub fn from_str_with(s: &str, mode: ParseMode) -> Result<Self, crate::Error> {
+		let s = scanner::strip_comments(s);
+		let (name, nodes) = scanner::children(&s)?;
+		if name != "kicad_pcb" {
+			return Err(serde_sexpr::de::Error::custom(format!(
+				"expected s-expr kicad_pcb, found {name}"
+			))
+			.into());
+		}
+
+		let mut version = None;
+		let mut generator = None;
+		let mut generator_version = None;
+		let mut general = None;
+		let mut page = None;
+		let mut title_block = None;
+		let mut layers = Vec::new();
+		let mut pcb_content = Vec::new();
+		let mut unknown = Vec::new();
+		for node in nodes {
+			let within = |err: crate::Error| err.within(node.name).within(name);
+			match node.name {
+				"version" => version = Some(from_node(node.text).map_err(within)?),
+				"generator" => {
+					let Generator(g) = from_node(node.text).map_err(within)?;
+					generator = Some(g);
+				},
+				"generator_version" => {
+					let GeneratorVersion(v) =
+						from_node(node.text).map_err(within)?;
+					generator_version = Some(v);
+				},
+				"general" => general = Some(from_node(node.text).map_err(within)?),
+				"paper" => page = Some(from_node(node.text).map_err(within)?),
+				"title_block" => {
+					title_block = Some(from_node(node.text).map_err(within)?)
+				},
+				"layers" => {
+					layers = LayersList::from_str(node.text)
+						.map_err(|err| within(err.into()))?
+						.layers
+				},
+				_ => match parse_content(name, node, mode)? {
+					Some(content) => pcb_content.push(content),
+					None => {
+						let text = raw_node::canonical(node.text)
+							.map_err(|err| within(err.into()))?;
+						unknown.push(UnknownNode {
+							index: pcb_content.len(),
+							text
+						});
+					}
+				}
+			}
+		}
+
+		Ok(Self {
+			version: version.ok_or_else(|| missing_field("version", name))?,
+			generator: generator.ok_or_else(|| missing_field("generator", name))?,
+			generator_version,
+			general: general.unwrap_or_default(),
+			page: page.ok_or_else(|| missing_field("paper", name))?,
+			title_block: title_block.unwrap_or_default(),
+			layers,
+			pcb_content,
+			unknown
+		})
+	}
 }
 
 impl FromStr for PCB {
-    type Err = serde_sexpr::de::Error;
+	type Err = crate::Error;
+
+	/// Parse a board file in [`ParseMode::Lenient`]. The header sections are
+	/// required, all other sections that are not modelled (yet) are recorded in
+	/// [`unknown`](Self::unknown). Line comments starting with `;` are ignored.
+	fn from_str(s: &str) -> Result<Self, crate::Error> {
+		Self::from_str_with(s, ParseMode::Lenient)
+	}
+}
+
+/// Parse a content node of the board file `root`. Returns `None` for nodes
+/// that are not modelled (yet).
+pub(crate) fn content_from_node(
+	root: &str,
+	node: scanner::Node<'_>
+) -> Result<Option<PCBContent>, crate::Error> {
+	parse_content(root, node, ParseMode::Lenient)
+}
 
-    fn from_str(s: &str) -> Result<Self, serde_sexpr::de::Error> {
-        serde_sexpr::from_str(s)
-    }
+fn parse_content(
+	root: &str,
+	node: scanner::Node<'_>,
+	mode: ParseMode
+) -> Result<Option<PCBContent>, crate::Error> {
+	if node.name == "setup" {
+		let setup = Setup::from_str_with(node.text, mode)
+			.map_err(|err| err.within(node.name).within(root))?;
+		return Ok(Some(PCBContent::Setup(setup)));
+	}
+	let content = footprint::content_after_header(node.text)
+		.map_err(crate::Error::from)
+		.and_then(|text| from_node(&text))
+		.map(Some)
+		.map_err(|err| err.within(node.name).within(root));
+	match mode {
+		ParseMode::Lenient if !MODELLED_NODES.contains(&node.name) => {
+			Ok(content.ok().flatten())
+		},
+		_ => content
+	}
+}
+
+/// The nodes that are rejected if they cannot be parsed, even when parsing in
+/// [`ParseMode::Lenient`].
+const MODELLED_NODES: &[&str] = &[
+	"property",
+	"net",
+	"footprint",
+	"gr_arc",
+	"gr_circle",
+	"gr_curve",
+	"gr_line",
+	"gr_poly",
+	"gr_rect",
+	"gr_text",
+	"segment",
+	"via",
+	"dimension",
+	"zone",
+	"group",
+	"image",
+	"embedded_files"
+];
+
+/// The JSON representation of a [`Layer`]. Unlike the s-expr, this names the
+/// layer number.
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+#[serde(rename = "")]
+struct LayerJson<'a> {
+	number: u32,
+	name: &'a str,
+	layer_type: LayerType,
+	user: Option<&'a str>
+}
+
+#[cfg(feature = "json")]
+impl PCB {
+	/// Write this board as JSON.
+	pub fn to_json(&self) -> Result<String, serde_json::Error> {
+		// the unknown nodes are unnamed in the s-expr like the content, so they are
+		// written separately as their s-expr text instead
+		let mut value = json::to_value(&PcbRef::new(self, &[]))?;
+		if let serde_json::Value::Object(map) = &mut value {
+			let layers: Vec<_> = self
+				.layers
+				.iter()
+				.map(|layer| LayerJson {
+					number: layer.number,
+					name: &layer.name,
+					layer_type: layer.layer_type,
+					user: layer.user.as_deref()
+				})
+				.collect();
+			map.insert("layers".to_owned(), json::to_value(&layers)?);
+			if !self.unknown.is_empty() {
+				map.insert("unknown".to_owned(), json::to_value(&self.unknown)?);
+			}
+			let children = map.get_mut("children");
+			if let Some(serde_json::Value::Array(children)) = children {
+				for (child, content) in children.iter_mut().zip(&self.pcb_content) {
+					let serde_json::Value::Object(child) = child else {
+						continue;
+					};
+					let PCBContent::Setup(setup) = content else {
+						continue;
+					};
+					if !setup.unknown.is_empty() {
+						child.remove("children");
+						let unknown = json::to_value(&setup.unknown)?;
+						child.insert("unknown".to_owned(), unknown);
+					}
+				}
+			}
+		}
+		serde_json::to_string(&value)
+	}
+
+	/// Parse a board from JSON written by [`PCB::to_json`].
+	pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+		let mut value: serde_json::Value = serde_json::from_str(s)?;
+		let layers = value.as_object_mut().and_then(|map| map.remove("layers"));
+		let unknown = value.as_object_mut().and_then(|map| map.remove("unknown"));
+		let mut pcb: PCB = json::from_value(&value)?;
+		if let Some(layers) = layers {
+			pcb.layers = json::from_value(&layers)?;
+		}
+		if let Some(unknown) = unknown {
+			pcb.unknown = json::from_value(&unknown)?;
+		}
+		if let Some(serde_json::Value::Array(children)) = value.get("children") {
+			for (content, child) in pcb.pcb_content.iter_mut().zip(children) {
+				if let (PCBContent::Setup(setup), Some(unknown)) =
+					(content, child.get("unknown"))
+				{
+					setup.unknown = json::from_value(unknown)?;
+				}
+			}
+		}
+		Ok(pcb)
+	}
 }
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
-    use std::fs;
-    use crate::Unit;
-    use crate::common::{PaperSize, Point};
-    use uuid::Uuid;
-    use super::*;
-
-    sexpr_test_case! {
-        name: general,
-        input: r#"(general (thickness 0.89))"#,
-        value: General { thickness: 0.89.mm(), ..Default::default() }
-    }
-
-    #[test]
-    fn test_tuple_layer_sexpr() {
-        let input = r#"(0 "F.Cu" signal)"#;
-        let expected = Layer { number: 0, name: "F.Cu".to_string(), layer_type: LayerType::Signal, user: None };
-        let actual = Layer::from_str(input).unwrap();
-        assert_eq!(actual, expected);
-
-        assert_eq!(actual.to_string(), input);
-    }
-
-    sexpr_test_case! {
-        name: pcb_layer,
-        input: r#"(0 "F.Cu" signal)"#,
-        value: Layer { number: 0, name: "F.Cu".to_string(), layer_type: LayerType::Signal, user: None }
-    }
-
-    sexpr_test_case! {
-        name: layers_list,
-        input: r#"(layers (0 "F.Cu" signal) (31 "B.Cu" signal) (40 "Dwgs.User" user "User.Drawings"))"#,
-        value: LayersList { 
-            layers: vec![
-                Layer { number: 0, name: "F.Cu".to_string(), layer_type: LayerType::Signal, user: None },
-                Layer { number: 31, name: "B.Cu".to_string(), layer_type: LayerType::Signal, user: None },
-                Layer { number: 40, name: "Dwgs.User".to_string(), layer_type: LayerType::User, user: Some("User.Drawings".to_string()) }
-            ]
-        }
-    }
-
-    sexpr_test_case! {
-        name: net,
-        input: r#"(net 1 "+3V3")"#,
-        value: Net { number: 1, name: "+3V3".to_string() }
-    }
-
-    sexpr_test_case! {
-        name: pcb_content,
-        input: r#"(gr_circle (center 1 1) (end 2 2) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
-        value: PCBContent::GrCircle(
-            Circle {
-                center: Point::new(1.0.mm(), 1.0.mm()),
-                end: Point::new(2.0.mm(), 2.0.mm()),
-                layer: None,
-                width: 0.12.mm(),
-                fill: None,
-                tstamp: Uuid::nil()
-            }
-        )
-    }
-
-
-    sexpr_test_case! {
-        name: kicad_pcb,
-        input: r#"(kicad_pcb (version 20221018) (generator pcbnew) (general (thickness 0.89)) (paper A4) (title_block (title Minnow)))"#,
-        value: PCB {
-            version: Version(20221018),
-            generator: "pcbnew".to_string(),
-            general: General {
-                thickness: 0.89.mm(),
-                ..Default::default()
-            },
-            page: Paper {
-                size: PaperSize::A4,
-                portrait: false,
-            },
-            title_block: TitleBlock {
-                title: Some("Minnow".to_string()),
-                date: None,
-                revision: None,
-                company: None,
-                comments: vec![],
-            },
-            layers: vec![],
-            pcb_content: vec![]
-        }
-    }
-
-    #[test]
-    fn test_deserialize_kicad_pcb_file() {
-        let cargo_dir: PathBuf = env!("CARGO_MANIFEST_DIR").parse().unwrap();
-        let filepath = cargo_dir.join("tests").join("sample.kicad_pcb");
-        let mut contents =
-            fs::read_to_string(filepath).expect("Test .kicad_pcb file missing or unreadable");
-        contents = contents.trim().to_string();
-
-        let result = contents.parse::<PCB>().unwrap();
-
-        assert!(result.version == Version(20221018));
-        assert!(result.generator == "pcbnew");
-    }
+	use super::*;
+	use crate::{
+		board::{
+			footprint::{Pad, PadShape, PadSize, PadType},
+			Change, DiffEntry, FieldChange, StackupLayer
+		},
+		common::{PaperSize, Point},
+		sexpr_test_case, Deg, Unit
+	};
+	use std::{fs, path::PathBuf};
+	use uuid::Uuid;
+
+	sexpr_test_case! {
+		name: general,
+		input: r#"(general (thickness 0.89))"#,
+		value: General { thickness: 0.89.mm(), ..Default::default() }
+	}
+
+	sexpr_test_case! {
+		name: net_without_name,
+		input: "(net 0)",
+		value: Net { number: 0, name: String::new() }
+	}
+
+	sexpr_test_case! {
+		name: board_property,
+		input: "(property VARIANT full)",
+		value: Property {
+			key: "VARIANT".to_owned(),
+			value: "full".to_owned(),
+			..Default::default()
+		}
+	}
+
+	sexpr_test_case! {
+		name: footprint_reference_property,
+		input: r#"(property Reference "R1" (at 0 -1.43) (layer "F.SilkS") hide)"#,
+		value: Property {
+			key: "Reference".to_owned(),
+			value: "R1".to_owned(),
+			id: None,
+			position: Some(Position::new(0.0.mm(), (-1.43).mm())),
+			layer: Some(crate::board::Layer::new("F.SilkS")),
+			hide: true
+		}
+	}
+
+	sexpr_test_case! {
+		name: property_with_id,
+		input: r#"(property Value "10k" (id 1) (at 1 2 90) (layer "F.Fab"))"#,
+		value: Property {
+			key: "Value".to_owned(),
+			value: "10k".to_owned(),
+			id: Some(1),
+			position: Some(Position {
+				x: 1.0.mm(),
+				y: 2.0.mm(),
+				angle: Some(90.0.deg())
+			}),
+			layer: Some(crate::board::Layer::new("F.Fab")),
+			hide: false
+		}
+	}
+
+	sexpr_test_case! {
+		name: net_with_name,
+		input: r#"(net 1 "+3V3")"#,
+		value: Net { number: 1, name: "+3V3".to_owned() }
+	}
+
+	#[test]
+	fn test_tuple_layer_sexpr() {
+		let input = r#"(0 "F.Cu" signal)"#;
+		let expected = Layer {
+			number: 0,
+			name: "F.Cu".to_string(),
+			layer_type: LayerType::Signal,
+			user: None,
+			unknown_type: None
+		};
+		let actual = Layer::from_str(input).unwrap();
+		assert_eq!(actual, expected);
+
+		assert_eq!(actual.to_string(), input);
+	}
+
+	#[test]
+	fn test_layer_display_round_trip() {
+		let layers = [
+			Layer::new(0, "F.Cu", LayerType::Signal),
+			Layer::new(1, "In1.Cu", LayerType::Power),
+			Layer::new(31, "B.Cu", LayerType::Mixed),
+			Layer {
+				user: Some("B.Silkscreen".to_owned()),
+				..Layer::new(36, "B.SilkS", LayerType::User)
+			},
+			Layer {
+				user: Some("My fancy layer".to_owned()),
+				..Layer::new(50, "User.1", LayerType::User)
+			},
+			Layer::new(51, "Quote \"this\" \\ that", LayerType::User),
+			"(52 User.3 front)".parse().unwrap()
+		];
+		for layer in layers {
+			let text = layer.to_string();
+			assert_eq!(Layer::from_str(&text).unwrap(), layer, "{text}");
+		}
+	}
+
+	#[test]
+	fn test_layer_with_escaped_quote() {
+		let input = r#"(50 "User \"1\"" user "A \"quoted\" name")"#;
+		let layer = Layer::from_str(input).unwrap();
+		assert_eq!(layer.name(), r#"User "1""#);
+		assert_eq!(layer.user_name(), Some(r#"A "quoted" name"#));
+		assert_eq!(layer.to_string(), input);
+	}
+
+	#[test]
+	fn test_deserialize_pcb_layer() {
+		let input = r#"(0 "F.Cu" signal)"#;
+		let expected = Layer {
+			number: 0,
+			name: "F.Cu".to_string(),
+			layer_type: LayerType::Signal,
+			user: None,
+			unknown_type: None
+		};
+		assert_eq!(Layer::from_str(input).unwrap(), expected);
+	}
+
+	#[test]
+	fn test_serialize_pcb_layer() {
+		let value = Layer {
+			number: 0,
+			name: "F.Cu".to_string(),
+			layer_type: LayerType::Signal,
+			user: None,
+			unknown_type: None
+		};
+		assert_eq!(
+			serde_sexpr::to_string(&value).unwrap(),
+			r#"(0 "F.Cu" signal)"#
+		);
+	}
+
+	fn layers_list() -> LayersList {
+		LayersList {
+			layers: vec![
+				Layer {
+					number: 0,
+					name: "F.Cu".to_string(),
+					layer_type: LayerType::Signal,
+					user: None,
+					unknown_type: None
+				},
+				Layer {
+					number: 31,
+					name: "B.Cu".to_string(),
+					layer_type: LayerType::Signal,
+					user: None,
+					unknown_type: None
+				},
+				Layer {
+					number: 40,
+					name: "Dwgs.User".to_string(),
+					layer_type: LayerType::User,
+					user: Some("User.Drawings".to_string()),
+					unknown_type: None
+				},
+			]
+		}
+	}
+
+	const LAYERS_LIST: &str = r#"(layers (0 "F.Cu" signal) (31 "B.Cu" signal) (40 "Dwgs.User" user "User.Drawings"))"#;
+
+	#[test]
+	fn test_setup_skips_unknown_sections() {
+		let input = r#"(setup (stackup (layer "F.Cu" (type "copper") (thickness 0.035)) (copper_finish "ENIG")) (pad_to_mask_clearance 0) (pcbplotparams (mode 1)))"#;
+		let setup = Setup::from_str(input).unwrap();
+		let stackup = setup.stackup.unwrap();
+		assert_eq!(stackup.layers, vec![StackupLayer {
+			thickness: Some(0.035.mm()),
+			..StackupLayer::new("F.Cu", "copper")
+		}]);
+		assert_eq!(stackup.copper_finish.as_deref(), Some("ENIG"));
+	}
+
+	const UNMODELED_SETUP: &str = r#"(kicad_pcb (version 20221018) (generator pcbnew) (paper "A4") (setup (stackup (layer "F.Cu" (type "copper"))) (pad_to_mask_clearance 0)) (net 0 ""))"#;
+
+	#[test]
+	fn test_parse_mode_strict() {
+		let err =
+			PCB::from_str_with(UNMODELED_SETUP, ParseMode::Strict).unwrap_err();
+		assert_eq!(err.path(), ["kicad_pcb", "setup"]);
+		assert_eq!(
+			err.to_string(),
+			"unknown node `pad_to_mask_clearance` in `kicad_pcb` > `setup`"
+		);
+	}
+
+	#[test]
+	fn test_parse_mode_lenient() {
+		let pcb = PCB::from_str_with(UNMODELED_SETUP, ParseMode::Lenient).unwrap();
+		let PCBContent::Setup(setup) = &pcb.pcb_content[0] else {
+			panic!("expected setup, found {:?}", pcb.pcb_content[0]);
+		};
+		assert!(setup.stackup.is_some());
+		assert_eq!(setup.unknown, ["(pad_to_mask_clearance 0)"]);
+		assert_eq!(pcb.nets().count(), 1);
+		assert!(pcb.unknown.is_empty());
+
+		// parsing without a mode is lenient as well
+		let parsed: PCB = UNMODELED_SETUP.parse().unwrap();
+		assert_eq!(parsed, pcb);
+	}
+
+	#[test]
+	fn test_parse_mode_unknown_content() {
+		let input = r#"(kicad_pcb (version 20221018) (generator pcbnew) (paper "A4") (bogus 1 2))"#;
+		let pcb = PCB::from_str_with(input, ParseMode::Lenient).unwrap();
+		assert_eq!(pcb.unknown, [UnknownNode {
+			index: 0,
+			text: "(bogus 1 2)".to_owned()
+		}]);
+		assert!(pcb.pcb_content.is_empty());
+		assert!(PCB::from_str_with(input, ParseMode::Strict).is_err());
+		assert_eq!(input.parse::<PCB>().unwrap(), pcb);
+	}
+
+	#[test]
+	fn test_unknown_content_round_trip() {
+		let input = r#"(kicad_pcb (version 20221018) (generator pcbnew) (paper "A4")
+			(bogus
+				(name "a") 1.50 (empty "")))"#;
+		let pcb: PCB = input.parse().unwrap();
+		assert_eq!(pcb.unknown[0].text, r#"(bogus (name a) 1.50 (empty ""))"#);
+		let written = serde_sexpr::to_string(&pcb).unwrap();
+		assert_eq!(written.parse::<PCB>().unwrap(), pcb);
+	}
+
+	#[test]
+	fn test_unknown_content_position() {
+		let input = r#"(kicad_pcb (version 20221018) (generator pcbnew) (paper "A4") (bogus 0) (net 0 "") (bogus 1) (net 1 "GND") (bogus 2))"#;
+		let pcb: PCB = input.parse().unwrap();
+		let indices: Vec<_> = pcb.unknown.iter().map(|node| node.index).collect();
+		assert_eq!(indices, [0, 1, 2]);
+		let written = serde_sexpr::to_string(&pcb).unwrap();
+		assert!(
+			written.ends_with("(bogus 0) (net 0) (bogus 1) (net 1 GND) (bogus 2))"),
+			"{written}"
+		);
+	}
+
+	#[test]
+	fn test_parse_mode_lenient_invalid_node() {
+		let input = r#"(kicad_pcb (version 20221018) (generator pcbnew) (paper "A4") (net 99999999999 "GND"))"#;
+		let err = PCB::from_str_with(input, ParseMode::Lenient).unwrap_err();
+		assert_eq!(err.path()[..2], ["kicad_pcb", "net"]);
+		assert!(input.parse::<PCB>().is_err());
+	}
+
+	#[test]
+	fn test_parse_mode_lenient_round_trip() {
+		let input = r#"(kicad_pcb (version 20221018) (generator pcbnew) (paper "A4") (setup (stackup (layer "F.Cu" (type "copper"))) (pad_to_mask_clearance 0)) (net 0 "") (bogus 1 2))"#;
+		let pcb = PCB::from_str_with(input, ParseMode::Lenient).unwrap();
+		let written = serde_sexpr::to_string(&pcb).unwrap();
+		let reparsed = PCB::from_str_with(&written, ParseMode::Lenient).unwrap();
+		assert_eq!(reparsed.unknown[0].text, "(bogus 1 2)");
+		let PCBContent::Setup(setup) = &reparsed.pcb_content[0] else {
+			panic!("expected setup, found {:?}", reparsed.pcb_content[0]);
+		};
+		assert_eq!(setup.unknown, ["(pad_to_mask_clearance 0)"]);
+		assert_eq!(reparsed, pcb);
+	}
+
+	#[test]
+	fn test_deserialize_layers_list() {
+		assert_eq!(LayersList::from_str(LAYERS_LIST).unwrap(), layers_list());
+	}
+
+	#[test]
+	fn test_serialize_layers_list() {
+		assert_eq!(serde_sexpr::to_string(&layers_list()).unwrap(), LAYERS_LIST);
+	}
+
+	#[test]
+	fn test_validate_layers_list() {
+		assert_eq!(layers_list().validate(), Ok(()));
+	}
+
+	#[test]
+	fn test_validate_layers_list_duplicate_number() {
+		let list = LayersList::from_str(
+			r#"(layers (0 "F.Cu" signal) (0 "In1.Cu" signal) (31 "B.Cu" bogus))"#
+		)
+		.unwrap();
+		assert_eq!(
+			list.validate(),
+			Err(vec![
+				LayerError::DuplicateNumber {
+					number: 0,
+					count: 2
+				},
+				LayerError::UnknownType {
+					number: 31,
+					layer_type: "bogus".to_owned()
+				},
+			])
+		);
+	}
+
+	#[test]
+	fn test_sorted_layers_list() {
+		let mut list = layers_list();
+		list.layers.reverse();
+		let numbers: Vec<_> =
+			list.sorted().layers.iter().map(Layer::number).collect();
+		assert_eq!(numbers, vec![0, 31, 40]);
+	}
+
+	sexpr_test_case! {
+		name: net,
+		input: r#"(net 1 "+3V3")"#,
+		value: Net { number: 1, name: "+3V3".to_string() }
+	}
+
+	sexpr_test_case! {
+		name: pcb_content,
+		input: r#"(gr_circle (center 1 1) (end 2 2) (width 0.12) (tstamp "00000000-0000-0000-0000-000000000000"))"#,
+		value: PCBContent::GrCircle(
+			Circle {
+				locked: false,
+				center: Point::new(1.0.mm(), 1.0.mm()),
+				end: Point::new(2.0.mm(), 2.0.mm()),
+				layer: None,
+				width: 0.12.mm(),
+				stroke_type: None,
+				fill: None,
+				tstamp: Uuid::nil()
+			}
+		)
+	}
+
+	sexpr_test_case! {
+		name: group,
+		input: r#"(group "" (id "00000000-0000-0000-0000-000000000001") (members "00000000-0000-0000-0000-000000000002" "00000000-0000-0000-0000-000000000003"))"#,
+		value: Group {
+			name: String::new(),
+			id: Uuid::from_u128(1),
+			members: vec![Uuid::from_u128(2), Uuid::from_u128(3)]
+		}
+	}
+
+	sexpr_test_case! {
+		name: kicad_pcb,
+		input: r#"(kicad_pcb (version 20221018) (generator pcbnew) (general (thickness 0.89)) (paper A4) (title_block (title Minnow)))"#,
+		value: PCB {
+			version: Version(20221018),
+			generator: "pcbnew".to_string(),
+			generator_version: None,
+			general: General {
+				thickness: 0.89.mm(),
+				..Default::default()
+			},
+			page: Paper {
+				size: PaperSize::A4,
+				portrait: false,
+			},
+			title_block: TitleBlock {
+				title: Some("Minnow".to_string()),
+				date: None,
+				revision: None,
+				company: None,
+				comments: vec![],
+			},
+			layers: vec![],
+			pcb_content: vec![],
+			unknown: vec![]
+		}
+	}
+
+	#[test]
+	fn test_generator_version() {
+		let input = r#"(kicad_pcb (version 20240108) (generator "pcbnew") (generator_version "8.0") (general (thickness 1.6)) (paper "A4"))"#;
+		let pcb: PCB = input.parse().unwrap();
+		assert_eq!(pcb.generator_version.as_deref(), Some("8.0"));
+		let written = serde_sexpr::to_string(&pcb).unwrap();
+		assert!(written
+			.contains(r#"(generator pcbnew) (generator_version "8.0") (general"#));
+
+		let pcb = PcbBuilder::new().build();
+		let written = serde_sexpr::to_string(&pcb).unwrap();
+		assert!(!written.contains("generator_version"));
+	}
+
+	#[test]
+	fn test_error_names_field() {
+		let input = r#"(kicad_pcb (version 20221018) (generator pcbnew) (general (thickness thick)) (paper "A4"))"#;
+		let err = input.parse::<PCB>().unwrap_err();
+		assert_eq!(err.path(), ["kicad_pcb", "general", "thickness"]);
+		assert!(err
+			.to_string()
+			.contains("`kicad_pcb` > `general` > `thickness`"));
+
+		let err = "(kicad_pcb (version 20221018) (generator pcbnew))"
+			.parse::<PCB>()
+			.unwrap_err();
+		assert_eq!(err.path(), ["kicad_pcb"]);
+	}
+
+	#[test]
+	fn test_embedded_files() {
+		let input = "(kicad_pcb (version 20240108) (generator pcbnew) (paper \"A4\")
+			(embedded_files
+				(file (name \"logo.txt\") (type other)
+					(data |KLUv/QBYKQAA
+						aGVsbG8=|)
+					(checksum \"5D41402ABC4B2A76B9719D911017C592\"))))";
+		let board: PCB = input.parse().unwrap();
+		let files = board
+			.pcb_content
+			.iter()
+			.find_map(|content| match content {
+				PCBContent::EmbeddedFiles(files) => Some(files),
+				_ => None
+			})
+			.unwrap();
+		assert_eq!(files.files.len(), 1);
+		assert_eq!(files.files[0].data, "KLUv/QBYKQAAaGVsbG8=");
+	}
+
+	fn edge(start: (f32, f32), end: (f32, f32)) -> PCBContent {
+		PCBContent::GrLine(Line {
+			locked: false,
+			start: Point::new(start.0.mm(), start.1.mm()),
+			end: Point::new(end.0.mm(), end.1.mm()),
+			angle: None,
+			layer: Some(crate::board::Layer::new("Edge.Cuts")),
+			width: 0.1.mm(),
+			stroke_type: None,
+			tstamp: Uuid::nil()
+		})
+	}
+
+	#[test]
+	fn test_board_outline() {
+		// the edges are neither ordered nor oriented consistently
+		let board = pcb(vec![
+			edge((0.0, 0.0), (50.0, 0.0)),
+			edge((0.0, 30.0), (50.0, 30.0)),
+			edge((50.0, 0.0), (50.0, 30.0)),
+			edge((0.0, 30.0), (0.0, 0.0)),
+			segment((0.0, 0.0), (60.0, 0.0), 1),
+		]);
+		let outline = board.board_outline().unwrap();
+		assert!(outline.closed());
+		assert_eq!(outline.len(), 5);
+		assert!(outline.contains(&Point::new(50.0.mm(), 30.0.mm())));
+
+		let open = pcb(vec![
+			edge((0.0, 0.0), (50.0, 0.0)),
+			edge((50.0, 0.0), (50.0, 30.0)),
+			edge((50.0, 30.0), (0.0, 30.0)),
+		]);
+		assert_eq!(open.board_outline(), None);
+	}
+
+	#[test]
+	fn test_sections() {
+		let cargo_dir: PathBuf = env!("CARGO_MANIFEST_DIR").parse().unwrap();
+		let filepath = cargo_dir.join("tests").join("minnow.kicad_pcb");
+		let pcb: PCB = fs::read_to_string(filepath).unwrap().parse().unwrap();
+
+		let sections = pcb.sections();
+		assert_eq!(sections.layers.len(), 29);
+		assert!(sections.setup.is_some());
+		assert_eq!(sections.nets.len(), 58);
+		assert!(sections.nets[0].is_unconnected());
+		assert_eq!(sections.nets[1].name, "+3V3");
+		assert_eq!(sections.graphics.len(), 63);
+		assert!(sections.graphics.iter().all(|item| matches!(
+			item,
+			GraphicItem::Arc(_)
+				| GraphicItem::Line(_)
+				| GraphicItem::Text(_)
+				| GraphicItem::Dimension(_)
+		)));
+		let texts = sections
+			.graphics
+			.iter()
+			.filter(|item| matches!(item, GraphicItem::Text(_)))
+			.count();
+		assert_eq!(texts, 41);
+		assert_eq!(sections.segments.len(), 599);
+		assert_eq!(sections.vias.len(), 106);
+		assert_eq!(sections.zones.len(), 1);
+
+		let stats = pcb.statistics();
+		assert_eq!(sections.nets.len(), stats.nets);
+		assert_eq!(sections.graphics.len(), stats.graphics);
+	}
+
+	#[test]
+	fn test_minnow_keeps_all_nodes() {
+		let cargo_dir: PathBuf = env!("CARGO_MANIFEST_DIR").parse().unwrap();
+		let filepath = cargo_dir.join("tests").join("minnow.kicad_pcb");
+		let pcb: PCB = fs::read_to_string(filepath).unwrap().parse().unwrap();
+
+		let count = |f: fn(&PCBContent) -> bool| {
+			pcb.pcb_content.iter().filter(|content| f(content)).count()
+		};
+		assert_eq!(count(|c| matches!(c, PCBContent::Footprint(_))), 69);
+		assert_eq!(count(|c| matches!(c, PCBContent::GrText(_))), 41);
+		// text boxes are not modelled yet
+		assert_eq!(pcb.unknown.len(), 1);
+		assert!(pcb.unknown[0].text.starts_with("(gr_text_box "));
+	}
+
+	#[test]
+	// sample.kicad_pcb is a KiCad 4 board, with (host ..) instead of
+	// (generator ..) and a (general ..) section starting with (links ..)
+	#[ignore = "KiCad 4 boards cannot be parsed yet"]
+	fn test_deserialize_kicad_pcb_file() {
+		let cargo_dir: PathBuf = env!("CARGO_MANIFEST_DIR").parse().unwrap();
+		let filepath = cargo_dir.join("tests").join("sample.kicad_pcb");
+		let mut contents = fs::read_to_string(filepath)
+			.expect("Test .kicad_pcb file missing or unreadable");
+		contents = contents.trim().to_string();
+
+		let result = contents.parse::<PCB>().unwrap();
+
+		assert!(result.version == Version(20221018));
+		assert!(result.generator == "pcbnew");
+	}
+
+	#[test]
+	fn test_deserialize_minnow_file() {
+		let cargo_dir: PathBuf = env!("CARGO_MANIFEST_DIR").parse().unwrap();
+		let filepath = cargo_dir.join("tests").join("minnow.kicad_pcb");
+		let contents = fs::read_to_string(filepath)
+			.expect("Test .kicad_pcb file missing or unreadable");
+
+		let result = contents.parse::<PCB>().unwrap();
+
+		assert!(result.version == Version(20221018));
+		assert!(result.generator == "pcbnew");
+		assert_eq!(result.layers.len(), 29);
+		let copper: Vec<_> = result
+			.layers_with_role(LayerRole::Copper)
+			.map(Layer::name)
+			.collect();
+		assert_eq!(copper, ["F.Cu", "B.Cu"]);
+		assert_eq!(result.layers_with_role(LayerRole::Silkscreen).count(), 2);
+
+		let stackup = result
+			.pcb_content
+			.iter()
+			.find_map(|content| match content {
+				PCBContent::Setup(setup) => setup.stackup.as_ref(),
+				_ => None
+			})
+			.unwrap();
+		assert_eq!(stackup.layers.len(), 9);
+		assert_eq!(stackup.layers[4].material.as_deref(), Some("FR4"));
+		assert_eq!(stackup.copper_finish.as_deref(), Some("ENIG"));
+
+		let dimensions = result
+			.pcb_content
+			.iter()
+			.filter(|content| matches!(content, PCBContent::Dimension(_)))
+			.count();
+		assert_eq!(dimensions, 2);
+
+		let vias = result
+			.pcb_content
+			.iter()
+			.filter(|content| matches!(content, PCBContent::Via(_)))
+			.count();
+		assert_eq!(vias, 106);
+	}
+
+	#[test]
+	fn test_add_net() {
+		let net = |number, name: &str| {
+			PCBContent::Net(Net {
+				number,
+				name: name.to_owned()
+			})
+		};
+		let mut board = pcb(vec![
+			net(0, ""),
+			net(1, "GND"),
+			net(3, "VCC"),
+			segment((0.0, 0.0), (1.0, 0.0), 1),
+		]);
+		assert_eq!(board.next_net_number(), Ok(2));
+		assert_eq!(board.add_net("SDA"), Ok(2));
+		assert_eq!(board.add_net("SCL"), Ok(4));
+		assert_eq!(board.add_net("GND"), Ok(1));
+		assert_eq!(board.nets().count(), 5);
+		assert_eq!(board.pcb_content[3], net(2, "SDA"));
+		assert_eq!(board.pcb_content[4], net(4, "SCL"));
+		assert!(matches!(board.pcb_content[5], PCBContent::Segment(_)));
+		assert!(board.validate_nets().is_empty());
+	}
+
+	#[test]
+	fn test_add_footprint() {
+		let pad = Pad::new(
+			"1",
+			PadType::Smd,
+			PadShape::Rect,
+			Position::new(0.8.mm(), 0.0.mm()),
+			PadSize::new(0.8.mm(), 0.8.mm()),
+			vec!["F.Cu".to_owned()],
+			Uuid::nil()
+		);
+		let fp = Footprint::builder("R_0603").add_pad(pad).build();
+		let mut board = pcb(vec![]);
+		let tstamp = board.add_footprint(
+			fp.clone(),
+			Point::new(10.0.mm(), 20.0.mm()),
+			90.0.deg(),
+			CanonicalLayer::BCu
+		);
+		assert_ne!(Some(tstamp), fp.tstamp);
+
+		let placed = match &board.pcb_content[..] {
+			[PCBContent::Footprint(placed)] => placed,
+			content => panic!("unexpected content {content:?}")
+		};
+		assert_eq!(placed.tstamp, Some(tstamp));
+		assert_eq!(placed.version, None);
+		assert_eq!(placed.layer, crate::board::Layer::new("B.Cu"));
+		assert_eq!(
+			placed.position,
+			Some(Position {
+				x: 10.0.mm(),
+				y: 20.0.mm(),
+				angle: Some(90.0.deg())
+			})
+		);
+
+		let pad = placed.pads().next().unwrap();
+		assert_eq!(pad.layers, ["B.Cu"]);
+		assert_eq!(pad.position.x, (-0.8).mm());
+		assert_ne!(pad.tstamp, Uuid::nil());
+	}
+
+	#[test]
+	fn test_large_net_number() {
+		let input = r#"(kicad_pcb (version 20221018) (generator pcbnew) (paper "A4")
+			(net 0 "") (net 512 "GND")
+			(segment (start 0 0) (end 1 0) (width 0.2) (layer "F.Cu") (net 512) (tstamp "00000000-0000-0000-0000-000000000000")))"#;
+		let board: PCB = input.parse().unwrap();
+		assert_eq!(board.pcb_content[1], net(512, "GND"));
+		assert_eq!(board.pcb_content[2], segment((0.0, 0.0), (1.0, 0.0), 512));
+		assert_eq!(board.total_track_length_for_net(512), 1.0.mm());
+
+		let written = serde_sexpr::to_string(&board).unwrap();
+		assert!(written.contains("(net 512 GND)"), "{written}");
+		assert_eq!(written.parse::<PCB>().unwrap(), board);
+	}
+
+	#[test]
+	fn test_properties() {
+		let mut board = pcb(vec![
+			PCBContent::Setup(Setup::default()),
+			PCBContent::Net(Net {
+				number: 0,
+				name: String::new()
+			}),
+		]);
+		board.set_property("VARIANT", "lite");
+		board.set_property("board_id", "0123");
+		board.set_property("VARIANT", "full");
+		assert!(matches!(board.pcb_content[1], PCBContent::Property(_)));
+		assert!(matches!(board.pcb_content[3], PCBContent::Net(_)));
+
+		let written = serde_sexpr::to_string(&board).unwrap();
+		let parsed: PCB = written.parse().unwrap();
+		assert_eq!(parsed, board);
+		assert_eq!(parsed.property("VARIANT"), Some("full"));
+		assert_eq!(parsed.property("board_id"), Some("0123"));
+		assert_eq!(parsed.property("missing"), None);
+	}
+
+	#[test]
+	fn test_copper_area_on_layer() {
+		let zone = |layer: &str, filled: &str| -> PCBContent {
+			let input = format!(
+				r#"(zone (net 1) (net_name GND) (layer "{layer}") (tstamp "00000000-0000-0000-0000-000000000000") (hatch edge 0.5) (min_thickness 0.25) (fill yes (thermal_gap 0.5) (thermal_bridge_width 0.5)) (polygon (pts (xy 0 0) (xy 10 0) (xy 10 5) (xy 0 5))) {filled})"#
+			);
+			PCBContent::Zone(serde_sexpr::from_str(&input).unwrap())
+		};
+		let board = pcb(vec![
+			zone("F.Cu", ""),
+			zone(
+				"B.Cu",
+				r#"(filled_polygon (layer "B.Cu") (pts (xy 1 1) (xy 9 1) (xy 9 4) (xy 1 4)))"#
+			),
+			segment((0.0, 0.0), (10.0, 0.0), 1),
+		]);
+
+		// the rectangular zone outline and a 10mm long, 0.2mm wide track
+		let area = board.copper_area_on_layer("F.Cu");
+		assert!((area - 52.0.mm()).abs() < 1e-4.mm(), "{area:?}");
+		// only the filled polygon of the filled zone
+		assert_eq!(board.copper_area_on_layer("B.Cu"), 24.0.mm());
+		assert_eq!(board.copper_area_on_layer("In1.Cu"), 0.0.mm());
+	}
+
+	#[test]
+	fn test_gerber_layer_map() {
+		let names = [
+			"F.Cu",
+			"B.Cu",
+			"F.SilkS",
+			"B.SilkS",
+			"F.Mask",
+			"B.Mask",
+			"F.Paste",
+			"B.Paste",
+			"Edge.Cuts",
+			"F.Fab"
+		];
+		let mut builder = PCB::builder();
+		for name in names {
+			let layer = CanonicalLayer::from_name(name);
+			builder = builder.add_layer(layer.definition().unwrap());
+		}
+		let map: Vec<_> = builder
+			.build()
+			.gerber_layer_map()
+			.into_iter()
+			.map(|(layer, gerber)| (layer.name().to_owned(), gerber.extension()))
+			.collect();
+		let expected = [
+			("F.Cu", "gtl"),
+			("B.Cu", "gbl"),
+			("F.SilkS", "gto"),
+			("B.SilkS", "gbo"),
+			("F.Mask", "gts"),
+			("B.Mask", "gbs"),
+			("F.Paste", "gtp"),
+			("B.Paste", "gbp"),
+			("Edge.Cuts", "gm1"),
+			("F.Fab", "gbr")
+		];
+		let expected: Vec<_> = expected
+			.iter()
+			.map(|(name, ext)| (name.to_string(), ext.to_string()))
+			.collect();
+		assert_eq!(map, expected);
+	}
+
+	#[test]
+	fn test_statistics() {
+		let cargo_dir: PathBuf = env!("CARGO_MANIFEST_DIR").parse().unwrap();
+		let filepath = cargo_dir.join("tests").join("minnow.kicad_pcb");
+		let contents = fs::read_to_string(filepath).unwrap();
+		let stats = contents.parse::<PCB>().unwrap().statistics();
+
+		assert_eq!(stats.nets, 58);
+		assert_eq!(stats.layers, 29);
+		assert_eq!(stats.tracks, 599 + 106);
+		assert_eq!(stats.zones, 1);
+		assert!(stats.track_length > 0.0.mm());
+	}
+
+	#[test]
+	fn test_upgrade_to() {
+		let input = r#"(kicad_pcb (version 20211014) (generator pcbnew)
+  (paper "A4")
+  (gr_circle (center 1 1) (end 2 2) (layer "F.SilkS") (width 0.12) (fill none))
+)"#;
+		let mut pcb = PCB::from_str(input).unwrap();
+		let report = pcb.upgrade_to(Version(20221018));
+		assert_eq!(report, UpgradeReport {
+			from: Version(20211014),
+			to: Version(20221018),
+			converted_strokes: 1,
+			not_migrated: vec![(
+				Version(20220225),
+				"footprints no longer have a `(tedit ..)`"
+			)]
+		});
+		assert_eq!(pcb.version, Version(20221018));
+
+		let output = serde_sexpr::to_string(&pcb).unwrap();
+		assert!(output.contains(
+			r#"(gr_circle (center 1 1) (end 2 2) (stroke (width 0.12) (type solid)) (fill none) (layer "F.SilkS")"#
+		));
+
+		let report = pcb.upgrade_to(Version(20211014));
+		assert_eq!(report.converted_strokes, 0);
+		assert_eq!(pcb.version, Version(20221018));
+	}
+
+	#[test]
+	fn test_deserialize_with_whitespace_and_comments() {
+		let input = r#"
+
+(kicad_pcb (version 20211014) (generator pcbnew)
+  ; a comment line, which may contain ( and "
+  (paper "A4")
+  (net 0 "") ; the unconnected net
+  (net 1 "a ; b")
+)
+
+"#;
+		let pcb = PCB::from_str(input).unwrap();
+		assert_eq!(pcb.version, Version(20211014));
+		assert_eq!(pcb.pcb_content, vec![net(0, ""), net(1, "a ; b")]);
+	}
+
+	fn circle(tstamp: Uuid) -> PCBContent {
+		PCBContent::GrCircle(Circle {
+			locked: false,
+			center: Point::new(1.0.mm(), 1.0.mm()),
+			end: Point::new(2.0.mm(), 2.0.mm()),
+			layer: None,
+			width: 0.12.mm(),
+			stroke_type: None,
+			fill: None,
+			tstamp
+		})
+	}
+
+	fn pcb(pcb_content: Vec<PCBContent>) -> PCB {
+		PCB {
+			version: Version::new(),
+			generator: "pcbnew".to_string(),
+			generator_version: None,
+			general: General::default(),
+			page: PaperSize::A4.landscape(),
+			title_block: TitleBlock::new(),
+			layers: vec![],
+			pcb_content,
+			unknown: vec![]
+		}
+	}
+
+	#[test]
+	fn test_builder() {
+		let pcb = PCB::builder()
+			.version(20221018)
+			.generator("pcbnew")
+			.thickness(1.6.mm())
+			.add_layer(Layer::new(0, "F.Cu", LayerType::Signal))
+			.add_net(0, "")
+			.add_net(1, "GND")
+			.add_graphic(GraphicItem::Circle(Circle {
+				locked: false,
+				center: Point::new(1.0.mm(), 1.0.mm()),
+				end: Point::new(2.0.mm(), 2.0.mm()),
+				layer: None,
+				width: 0.12.mm(),
+				stroke_type: None,
+				fill: None,
+				tstamp: Uuid::nil()
+			}))
+			.build();
+		assert_eq!(pcb.layers.len(), 1);
+
+		let written = serde_sexpr::to_string(&pcb).unwrap();
+		let expected = concat!(
+			r#"(kicad_pcb (version 20221018) (generator pcbnew) "#,
+			r#"(general (thickness 1.6)) (paper A4) (title_block) "#,
+			r#"(layers (0 "F.Cu" signal)) "#,
+			r#"(net 0) (net 1 GND) "#,
+			r#"(gr_circle (center 1 1) (end 2 2) (width 0.12) "#,
+			r#"(tstamp "00000000-0000-0000-0000-000000000000")))"#
+		);
+		#[cfg(feature = "skip_nil_uuids")]
+		let expected = expected
+			.replace(r#" (tstamp "00000000-0000-0000-0000-000000000000")"#, "");
+		assert_eq!(written, expected);
+
+		let parsed: PCB = written.parse().unwrap();
+		assert_eq!(parsed, pcb);
+	}
+
+	fn segment(start: (f32, f32), end: (f32, f32), net: u32) -> PCBContent {
+		PCBContent::Segment(Segment {
+			locked: false,
+			start: Point::new(start.0.mm(), start.1.mm()),
+			end: Point::new(end.0.mm(), end.1.mm()),
+			width: 0.2.mm(),
+			layer: crate::board::Layer::new("F.Cu"),
+			net,
+			tstamp: Uuid::nil()
+		})
+	}
+
+	#[test]
+	fn test_route() {
+		let mut pcb = pcb(vec![]);
+		let points = [
+			Point::new(0.0.mm(), 0.0.mm()),
+			Point::new(5.0.mm(), 0.0.mm()),
+			Point::new(5.0.mm(), 5.0.mm())
+		];
+		assert_eq!(pcb.route(&points[.. 1], 0.2.mm(), "F.Cu", 1), None);
+		assert!(pcb.pcb_content.is_empty());
+
+		let tstamps = pcb.route(&points, 0.2.mm(), "F.Cu", 1).unwrap();
+		let (first, second) = match pcb.pcb_content.as_slice() {
+			[PCBContent::Segment(first), PCBContent::Segment(second)] => {
+				(first, second)
+			},
+			content => panic!("unexpected content {content:?}")
+		};
+		assert_eq!(tstamps, vec![first.tstamp, second.tstamp]);
+		assert_ne!(first.tstamp, second.tstamp);
+		assert_eq!((first.start, first.end), (points[0], points[1]));
+		assert_eq!((second.start, second.end), (points[1], points[2]));
+		assert_eq!(first.layer, crate::board::Layer::new("F.Cu"));
+		assert_eq!(pcb.total_track_length_for_net(1), 10.0.mm());
+	}
+
+	#[test]
+	fn test_merge_collinear_segments() {
+		let mut pcb = pcb(vec![
+			segment((0.0, 0.0), (5.0, 0.0), 1),
+			segment((5.0, 0.0), (10.0, 0.0), 1),
+			segment((10.0, 0.0), (10.0, 5.0), 1),
+			segment((0.0, 2.0), (5.0, 2.0), 1),
+			segment((5.0, 2.0), (10.0, 2.0), 2),
+		]);
+		assert_eq!(pcb.merge_collinear_segments(), 1);
+		let PCBContent::Segment(merged) = &pcb.pcb_content[0] else {
+			panic!("expected segment, found {:?}", pcb.pcb_content[0]);
+		};
+		assert_eq!(merged.start, Point::new(0.0.mm(), 0.0.mm()));
+		assert_eq!(merged.end, Point::new(10.0.mm(), 0.0.mm()));
+		assert_eq!(pcb.pcb_content.len(), 4);
+		assert_eq!(pcb.total_track_length_for_net(1), 20.0.mm());
+		assert_eq!(pcb.merge_collinear_segments(), 0);
+	}
+
+	#[test]
+	fn test_merge_collinear_segments_at_junction() {
+		let mut pcb = pcb(vec![
+			segment((0.0, 0.0), (5.0, 0.0), 1),
+			segment((5.0, 0.0), (10.0, 0.0), 1),
+			segment((5.0, 0.0), (5.0, 5.0), 1),
+		]);
+		assert_eq!(pcb.merge_collinear_segments(), 0);
+		assert_eq!(pcb.pcb_content.len(), 3);
+	}
+
+	#[test]
+	fn test_snap_all() {
+		let mut pcb = pcb(vec![segment((1.03, 2.07), (3.9, -0.2), 1)]);
+		pcb.snap_all(0.5.mm());
+		let PCBContent::Segment(segment) = &pcb.pcb_content[0] else {
+			panic!("expected segment, found {:?}", pcb.pcb_content[0]);
+		};
+		assert_eq!(segment.start, Point::new(1.0.mm(), 2.0.mm()));
+		assert_eq!(segment.end, Point::new(4.0.mm(), 0.0.mm()));
+
+		let before = pcb.clone();
+		pcb.snap_all(0.0.mm());
+		assert_eq!(pcb, before);
+	}
+
+	#[test]
+	fn test_total_track_length_for_net() {
+		let pcb = pcb(vec![
+			segment((0.0, 0.0), (3.0, 4.0), 1),
+			segment((3.0, 4.0), (3.0, 6.0), 1),
+			segment((0.0, 0.0), (10.0, 0.0), 2),
+		]);
+		assert_eq!(pcb.total_track_length_for_net(1), 7.0.mm());
+		assert_eq!(pcb.total_track_length_for_net(2), 10.0.mm());
+		assert_eq!(pcb.total_track_length_for_net(3), 0.0.mm());
+	}
+
+	#[test]
+	fn test_min_track_clearance() {
+		let tstamp = |content: &PCBContent| content.tstamp().unwrap();
+		let mut contents = vec![
+			segment((0.0, 0.0), (10.0, 0.0), 1),
+			segment((0.0, 0.5), (10.0, 0.5), 1),
+			segment((0.0, 1.5), (10.0, 1.5), 2),
+			segment((0.0, 5.0), (10.0, 5.0), 3),
+		];
+		for content in &mut contents {
+			if let PCBContent::Segment(segment) = content {
+				segment.tstamp = Uuid::new_v4();
+			}
+		}
+		let (a, b) = (tstamp(&contents[1]), tstamp(&contents[2]));
+		let pcb = pcb(contents);
+		assert_eq!(pcb.min_track_clearance(), Some((0.8.mm(), a, b)));
+	}
+
+	#[test]
+	fn test_min_track_clearance_single_net() {
+		let pcb = pcb(vec![
+			segment((0.0, 0.0), (10.0, 0.0), 1),
+			segment((0.0, 0.5), (10.0, 0.5), 1),
+		]);
+		assert_eq!(pcb.min_track_clearance(), None);
+	}
+
+	#[test]
+	fn test_unconnected_net() {
+		let net = |number: u32, name: &str| {
+			PCBContent::Net(Net {
+				number,
+				name: name.to_owned()
+			})
+		};
+		let pcb = pcb(vec![
+			net(0, ""),
+			net(1, "GND"),
+			net(2, "VCC"),
+			segment((0.0, 0.0), (3.0, 4.0), 0),
+		]);
+		let numbers = |nets: Vec<&Net>| -> Vec<u32> {
+			nets.into_iter().map(|net| net.number).collect()
+		};
+		assert!(pcb.nets().next().unwrap().is_unconnected());
+		assert_eq!(numbers(pcb.nets().collect()), vec![0, 1, 2]);
+		assert_eq!(numbers(pcb.connected_nets().collect()), vec![1, 2]);
+		assert_eq!(pcb.total_track_length_for_net(0), 0.0.mm());
+		assert_eq!(pcb.total_unconnected_track_length(), 5.0.mm());
+	}
+
+	#[test]
+	fn test_remap_layer() {
+		let line = |layer: &str| {
+			PCBContent::GrLine(Line {
+				locked: false,
+				start: Point::new(0.0.mm(), 0.0.mm()),
+				end: Point::new(1.0.mm(), 0.0.mm()),
+				angle: None,
+				layer: Some(crate::board::Layer::new(layer)),
+				width: 0.1.mm(),
+				stroke_type: None,
+				tstamp: Uuid::nil()
+			})
+		};
+		let mut pcb =
+			pcb(vec![line("Dwgs.User"), line("F.SilkS"), line("Dwgs.User")]);
+		pcb.layers = vec![
+			Layer::new(40, "Dwgs.User", LayerType::User),
+			Layer::new(41, "Cmts.User", LayerType::User),
+		];
+
+		assert_eq!(pcb.remap_layer("Dwgs.User", "Cmts.User"), 2);
+		assert_eq!(pcb.remap_layer("Dwgs.User", "Cmts.User"), 0);
+		assert!(pcb.pcb_content.iter().all(|content| match content {
+			PCBContent::GrLine(line) =>
+				line.layer.as_ref().unwrap().0 != "Dwgs.User",
+			_ => true
+		}));
+		// Cmts.User already existed, so the layers were merged
+		assert_eq!(pcb.layers[0].name(), "Dwgs.User");
+
+		// User.1 does not exist yet, so the layer is renamed
+		assert_eq!(pcb.remap_layer("Cmts.User", "User.1"), 2);
+		assert_eq!(pcb.layers[1].name(), "User.1");
+	}
+
+	#[test]
+	fn test_remove_by_tstamp() {
+		let id = Uuid::from_u128(1);
+		let group = PCBContent::Group(Group {
+			name: "group".to_owned(),
+			id: Uuid::from_u128(2),
+			members: vec![id, Uuid::from_u128(3)]
+		});
+		let mut pcb = pcb(vec![circle(Uuid::from_u128(3)), circle(id), group]);
+
+		assert!(pcb.remove_by_tstamp(id));
+		assert!(!pcb.remove_by_tstamp(id));
+		assert!(!pcb.remove_by_tstamp(Uuid::nil()));
+		match pcb.pcb_content.as_slice() {
+			[PCBContent::GrCircle(circle), PCBContent::Group(group)] => {
+				assert_eq!(circle.tstamp, Uuid::from_u128(3));
+				assert_eq!(group.members, vec![Uuid::from_u128(3)]);
+			},
+			content => panic!("unexpected content {content:?}")
+		}
+
+		pcb.retain_content(|content| !matches!(content, PCBContent::Group(_)));
+		assert_eq!(pcb.pcb_content.len(), 1);
+	}
+
+	#[test]
+	fn test_dedup_graphics() {
+		let group = PCBContent::Group(Group {
+			name: "group".to_owned(),
+			id: Uuid::from_u128(3),
+			members: vec![Uuid::from_u128(1), Uuid::from_u128(2)]
+		});
+		let mut pcb = pcb(vec![
+			circle(Uuid::from_u128(1)),
+			segment((0.0, 0.0), (1.0, 0.0), 1),
+			segment((0.0, 0.0), (1.0, 0.0), 1),
+			circle(Uuid::from_u128(2)),
+			group,
+		]);
+
+		assert_eq!(pcb.dedup_graphics(), 1);
+		assert_eq!(pcb.dedup_graphics(), 0);
+		match pcb.pcb_content.as_slice() {
+			[
+				PCBContent::GrCircle(circle),
+				PCBContent::Segment(_),
+				PCBContent::Segment(_),
+				PCBContent::Group(group)
+			] => {
+				assert_eq!(circle.tstamp, Uuid::from_u128(1));
+				assert_eq!(group.members, vec![Uuid::from_u128(1)]);
+			},
+			content => panic!("unexpected content {content:?}")
+		}
+	}
+
+	#[test]
+	fn test_clear_layer() {
+		let line = |layer: &str| {
+			let mut line = Line::new(
+				Point::new(0.0.mm(), 0.0.mm()),
+				Point::new(1.0.mm(), 0.0.mm()),
+				0.1.mm()
+			);
+			line.layer = Some(crate::board::Layer::new(layer));
+			PCBContent::GrLine(line)
+		};
+		let mut pcb = pcb(vec![
+			net(1, "GND"),
+			line("F.SilkS"),
+			line("Dwgs.User"),
+			segment((0.0, 0.0), (1.0, 0.0), 1),
+			line("F.Cu"),
+		]);
+
+		assert_eq!(pcb.clear_layer("F.Cu"), 2);
+		assert_eq!(pcb.clear_layer("F.Cu"), 0);
+		assert_eq!(pcb.pcb_content.len(), 3);
+		assert_eq!(pcb.clear_layer("Dwgs.User"), 1);
+		match pcb.pcb_content.as_slice() {
+			[PCBContent::Net(_), PCBContent::GrLine(line)] => {
+				assert_eq!(line.layer.as_ref().unwrap().0, "F.SilkS")
+			},
+			content => panic!("unexpected content {content:?}")
+		}
+	}
+
+	#[test]
+	fn test_remove_net() {
+		let mut board = pcb(vec![
+			net(0, ""),
+			net(1, "GND"),
+			segment((0.0, 0.0), (1.0, 0.0), 1),
+			footprint("C1", &[("1", 1, "GND")]),
+		]);
+		assert!(board.remove_net(1, true));
+		assert!(!board.remove_net(1, true));
+		assert!(board.validate_nets().is_empty());
+		assert!(board.pcb_content.iter().all(|content| match content {
+			PCBContent::Segment(segment) => segment.net == 0,
+			_ => true
+		}));
+		assert!(board.netlist().get(1).is_none());
+
+		let mut board =
+			pcb(vec![net(1, "GND"), segment((0.0, 0.0), (1.0, 0.0), 1)]);
+		assert!(board.remove_net(1, false));
+		assert_eq!(board.validate_nets(), vec![NetError::UndefinedNet(1)]);
+	}
+
+	#[test]
+	fn test_regenerate_nil_tstamps() {
+		let existing = Uuid::from_u128(42);
+		let mut pcb = pcb(vec![circle(Uuid::nil()), circle(existing)]);
+		pcb.regenerate_tstamps(Regenerate::Nil);
+
+		match pcb.pcb_content.as_slice() {
+			[PCBContent::GrCircle(a), PCBContent::GrCircle(b)] => {
+				assert!(!a.tstamp.is_nil());
+				assert_eq!(b.tstamp, existing);
+			},
+			content => panic!("unexpected content {content:?}")
+		}
+	}
+
+	#[test]
+	fn test_regenerate_all_tstamps_remaps_groups() {
+		let member = Uuid::from_u128(1);
+		let mut pcb = pcb(vec![
+			circle(member),
+			circle(Uuid::nil()),
+			PCBContent::Group(Group {
+				name: String::new(),
+				id: Uuid::from_u128(2),
+				members: vec![member]
+			}),
+		]);
+		pcb.regenerate_tstamps(Regenerate::All);
+
+		match pcb.pcb_content.as_slice() {
+			[PCBContent::GrCircle(a), PCBContent::GrCircle(b), PCBContent::Group(group)] =>
+			{
+				assert_ne!(a.tstamp, member);
+				assert!(!b.tstamp.is_nil());
+				assert_ne!(a.tstamp, b.tstamp);
+				assert_ne!(group.id, Uuid::from_u128(2));
+				assert_eq!(group.members, vec![a.tstamp]);
+			},
+			content => panic!("unexpected content {content:?}")
+		}
+	}
+
+	#[cfg(feature = "json")]
+	#[test]
+	fn test_json_round_trip() {
+		let mut board = pcb(vec![
+			PCBContent::Net(Net {
+				number: 1,
+				name: "GND".to_owned()
+			}),
+			segment((0.0, 0.0), (3.0, 4.0), 1),
+			circle(Uuid::nil()),
+		]);
+		board.layers = vec![Layer::new(0, "F.Cu", LayerType::Signal), Layer {
+			user: Some("F.Silkscreen".to_owned()),
+			..Layer::new(37, "F.SilkS", LayerType::User)
+		}];
+		board.pcb_content.insert(
+			0,
+			PCBContent::Setup(Setup {
+				unknown: vec!["(pad_to_mask_clearance 0)".to_owned()],
+				..Default::default()
+			})
+		);
+		board.unknown = vec![UnknownNode {
+			index: 2,
+			text: "(bogus 1 2)".to_owned()
+		}];
+
+		let json = board.to_json().unwrap();
+		assert!(json.contains(r#""version":20211123"#), "{json}");
+		assert!(json.contains(r#""number":37"#), "{json}");
+		assert_eq!(PCB::from_json(&json).unwrap(), board);
+	}
+
+	fn net(number: u32, name: &str) -> PCBContent {
+		PCBContent::Net(Net {
+			number,
+			name: name.to_owned()
+		})
+	}
+
+	fn footprint(reference: &str, pads: &[(&str, u32, &str)]) -> PCBContent {
+		let mut builder = Footprint::builder("R_0603").reference(reference);
+		for (number, net, name) in pads {
+			let pad = Pad::new(
+				*number,
+				PadType::Smd,
+				PadShape::Rect,
+				Position::new(0.0.mm(), 0.0.mm()),
+				PadSize::new(0.5.mm(), 0.5.mm()),
+				vec!["F.Cu".to_owned()],
+				Uuid::nil()
+			);
+			builder = builder.add_pad(Pad {
+				net: Some((*net, name.to_string())),
+				..pad
+			});
+		}
+		PCBContent::Footprint(builder.build())
+	}
+
+	#[test]
+	fn test_netlist() {
+		let board = pcb(vec![
+			net(0, ""),
+			net(1, "GND"),
+			net(2, "VCC"),
+			net(3, "NC"),
+			footprint("R1", &[("1", 2, "VCC"), ("2", 1, "GND")]),
+			footprint("C1", &[("1", 0, ""), ("2", 1, "GND")]),
+		]);
+		let netlist = board.netlist();
+
+		let gnd = netlist.get_by_name("GND").unwrap();
+		assert_eq!(
+			gnd.pads,
+			BTreeSet::from([PadRef::new("C1", "2"), PadRef::new("R1", "2")])
+		);
+		assert_eq!(netlist.get(2).unwrap().pads.len(), 1);
+		assert!(netlist.get(3).unwrap().pads.is_empty());
+		assert!(netlist.get(0).unwrap().pads.is_empty());
+	}
+
+	#[test]
+	fn test_netlist_minnow() {
+		let cargo_dir: PathBuf = env!("CARGO_MANIFEST_DIR").parse().unwrap();
+		let filepath = cargo_dir.join("tests").join("minnow.kicad_pcb");
+		let pcb: PCB = fs::read_to_string(filepath).unwrap().parse().unwrap();
+		let netlist = pcb.netlist();
+
+		assert_eq!(netlist.nets.len(), 58);
+		assert!(netlist.get(0).unwrap().pads.is_empty());
+		assert!(netlist.nets.range(1..).all(|(_, net)| !net.pads.is_empty()));
+		assert_eq!(netlist.get_by_name("GND").unwrap().pads.len(), 41);
+		assert_eq!(
+			netlist.get(1).unwrap().pads,
+			BTreeSet::from([
+				PadRef::new("C1", "1"),
+				PadRef::new("D1", "2"),
+				PadRef::new("D2", "2"),
+				PadRef::new("JP6", "1"),
+				PadRef::new("U1", "8"),
+				PadRef::new("U1", "9")
+			])
+		);
+	}
+
+	#[test]
+	fn test_rename_net() {
+		let zone: Zone = serde_sexpr::from_str(
+			r#"(zone (net 1) (net_name GND) (layer "F.Cu") (tstamp "00000000-0000-0000-0000-000000000000") (hatch edge 0.5) (min_thickness 0.25) (fill (thermal_gap 0.5) (thermal_bridge_width 0.5)) (polygon (pts (xy 0 0) (xy 1 0) (xy 1 1))))"#
+		)
+		.unwrap();
+		let mut board = pcb(vec![
+			net(0, ""),
+			net(1, "GND"),
+			net(2, "VCC"),
+			PCBContent::Zone(zone),
+			footprint("C1", &[("1", 2, "VCC"), ("2", 1, "GND")]),
+		]);
+
+		assert_eq!(
+			board.rename_net(1, "VCC"),
+			Err(NetError::NameInUse {
+				name: "VCC".to_owned(),
+				number: 2
+			})
+		);
+		assert_eq!(board.rename_net(3, "GNDA"), Err(NetError::UndefinedNet(3)));
+
+		board.rename_net(1, "GNDA").unwrap();
+		assert!(board.validate_nets().is_empty());
+		let netlist = board.netlist();
+		assert_eq!(netlist.get(1).unwrap().name, "GNDA");
+		assert_eq!(
+			netlist.get_by_name("GNDA").unwrap().pads,
+			BTreeSet::from([PadRef::new("C1", "2")])
+		);
+		let zone = board.pcb_content.iter().find_map(|content| match content {
+			PCBContent::Zone(zone) => Some(zone),
+			_ => None
+		});
+		assert_eq!(zone.unwrap().net_name, "GNDA");
+	}
+
+	#[test]
+	fn test_diff_added_net() {
+		let old = pcb(vec![net(0, ""), net(1, "GND")]);
+		let new = pcb(vec![net(0, ""), net(1, "GND"), net(2, "VCC")]);
+		let diff = old.diff(&new);
+		assert_eq!(diff.nets, vec![DiffEntry {
+			key: "VCC".to_owned(),
+			change: Change::Added
+		}]);
+		assert!(diff.items.is_empty());
+		assert!(old.diff(&old).is_empty());
+	}
+
+	#[test]
+	fn test_diff_moved_segment() {
+		let tstamp = Uuid::from_u128(1);
+		let moved = |start, end| match segment(start, end, 1) {
+			PCBContent::Segment(segment) => {
+				PCBContent::Segment(Segment { tstamp, ..segment })
+			},
+			_ => unreachable!()
+		};
+		let old = pcb(vec![moved((0.0, 0.0), (1.0, 0.0))]);
+		let new = pcb(vec![moved((0.0, 0.0), (2.0, 0.0))]);
+		assert_eq!(old.diff(&new).items, vec![DiffEntry {
+			key: tstamp.to_string(),
+			change: Change::Modified(vec![FieldChange {
+				field: "end".to_owned(),
+				old: Some("(end 1 0)".to_owned()),
+				new: Some("(end 2 0)".to_owned())
+			}])
+		}]);
+	}
+
+	#[test]
+	fn test_validate_nets() {
+		let board = pcb(vec![
+			net(0, ""),
+			net(1, "GND"),
+			segment((0.0, 0.0), (1.0, 0.0), 1),
+		]);
+		assert_eq!(board.validate_nets(), vec![]);
+	}
+
+	#[test]
+	fn test_validate_nets_duplicate_number() {
+		let board = pcb(vec![net(0, ""), net(1, "A"), net(1, "B"), net(3, "C")]);
+		assert_eq!(board.validate_nets(), vec![
+			NetError::DuplicateNumber {
+				number: 1,
+				count: 2
+			},
+			NetError::MissingNumber(2)
+		]);
+	}
+
+	#[test]
+	fn test_validate_nets_undefined_segment_net() {
+		let board = pcb(vec![
+			net(0, ""),
+			net(1, "GND"),
+			segment((0.0, 0.0), (1.0, 0.0), 1),
+			segment((1.0, 0.0), (2.0, 0.0), 5),
+		]);
+		assert_eq!(board.validate_nets(), vec![NetError::UndefinedNet(5)]);
+	}
+
+	/// Boards made of arbitrary nets and segments must survive being written and
+	/// parsed again. This runs the `roundtrip` fuzz target on a few fixed inputs.
+	#[cfg(feature = "arbitrary")]
+	#[test]
+	fn test_arbitrary_round_trip() {
+		use arbitrary::{Arbitrary, Unstructured};
+
+		for seed in 0..64u32 {
+			let bytes: Vec<u8> = (0..512u32)
+				.map(|i| {
+					(i.wrapping_mul(2654435761).wrapping_add(seed * 97) >> 7) as u8
+				})
+				.collect();
+			let mut u = Unstructured::new(&bytes);
+			let mut content: Vec<PCBContent> = Vec::<Net>::arbitrary(&mut u)
+				.unwrap()
+				.into_iter()
+				.map(PCBContent::Net)
+				.collect();
+			content.extend(
+				Vec::<Segment>::arbitrary(&mut u)
+					.unwrap()
+					.into_iter()
+					.map(PCBContent::Segment)
+			);
+			let board = pcb(content);
+
+			let written = serde_sexpr::to_string(&board).unwrap();
+			let parsed: PCB = written.parse().unwrap();
+			assert_eq!(parsed, board, "{written}");
+		}
+	}
+
+	proptest::proptest! {
+		#[test]
+		fn net_round_trip(net in crate::internal::proptest::net()) {
+			let written = serde_sexpr::to_string(&net).unwrap();
+			let parsed: Net = serde_sexpr::from_str(&written).unwrap();
+			proptest::prop_assert_eq!(parsed, net, "{}", written);
+		}
+	}
 }
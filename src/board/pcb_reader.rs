@@ -0,0 +1,257 @@
+use super::pcb::{
+	self, General, Generator, GeneratorVersion, Layer, LayersList, PCBContent,
+	UnknownNode, Version
+};
+use crate::{
+	common::{Paper, TitleBlock},
+	error::{from_node, missing_field},
+	internal::{raw_node, scanner}
+};
+use serde::de::Error as _;
+use std::{borrow::Cow, str::FromStr};
+
+/// A reader for board files that parses the header of the board up front, but
+/// the remaining content only one item at a time while iterating. This avoids
+/// holding the whole board in memory when processing large boards.
+#[derive(Clone, Debug)]
+pub struct PcbReader<'a> {
+	input: Cow<'a, str>,
+	/// The byte offset of the next child node of `kicad_pcb` in `input`.
+	offset: usize,
+	/// The number of [`PcbItem::Content`] items returned so far.
+	index: usize,
+	done: bool,
+
+	version: Version,
+	generator: String,
+	generator_version: Option<String>,
+	general: General,
+	page: Paper,
+	title_block: TitleBlock,
+	layers: Vec<Layer>
+}
+
+const NAME: &str = "kicad_pcb";
+
+impl<'a> PcbReader<'a> {
+	/// Parse the header of the board, i.e. all sections up to and including the
+	/// layers. The remaining content is parsed when iterating over the reader.
+	pub fn new(input: &'a str) -> Result<Self, crate::Error> {
+		let input = scanner::strip_comments(input);
+		let (name, mut rest) = scanner::open(&input)?;
+		if name != NAME {
+			return Err(serde_sexpr::de::Error::custom(format!(
+				"expected s-expr {NAME}, found {name}"
+			))
+			.into());
+		}
+
+		let mut version = None;
+		let mut generator = None;
+		let mut generator_version = None;
+		let mut general = None;
+		let mut page = None;
+		let mut title_block = None;
+		let mut layers = Vec::new();
+		while let Some((node, next)) = scanner::next_child(name, rest, false)? {
+			let within = |err: crate::Error| err.within(node.name).within(name);
+			match node.name {
+				"version" => version = Some(from_node(node.text).map_err(within)?),
+				"generator" => {
+					let Generator(g) = from_node(node.text).map_err(within)?;
+					generator = Some(g);
+				},
+				"generator_version" => {
+					let GeneratorVersion(v) =
+						from_node(node.text).map_err(within)?;
+					generator_version = Some(v);
+				},
+				"general" => general = Some(from_node(node.text).map_err(within)?),
+				"paper" => page = Some(from_node(node.text).map_err(within)?),
+				"title_block" => {
+					title_block = Some(from_node(node.text).map_err(within)?)
+				},
+				"layers" => {
+					layers = LayersList::from_str(node.text)
+						.map_err(|err| within(err.into()))?
+						.layers
+				},
+				_ => break
+			}
+			rest = next;
+		}
+
+		Ok(Self {
+			offset: input.len() - rest.len(),
+			index: 0,
+			done: false,
+			version: version.ok_or_else(|| missing_field("version", name))?,
+			generator: generator.ok_or_else(|| missing_field("generator", name))?,
+			generator_version,
+			general: general.unwrap_or_default(),
+			page: page.ok_or_else(|| missing_field("paper", name))?,
+			title_block: title_block.unwrap_or_default(),
+			layers,
+			input
+		})
+	}
+
+	pub fn version(&self) -> Version {
+		self.version
+	}
+
+	pub fn generator(&self) -> &str {
+		&self.generator
+	}
+
+	pub fn generator_version(&self) -> Option<&str> {
+		self.generator_version.as_deref()
+	}
+
+	pub fn general(&self) -> &General {
+		&self.general
+	}
+
+	pub fn page(&self) -> &Paper {
+		&self.page
+	}
+
+	pub fn title_block(&self) -> &TitleBlock {
+		&self.title_block
+	}
+
+	pub fn layers(&self) -> &[Layer] {
+		&self.layers
+	}
+}
+
+/// An item of the board returned by [`PcbReader`].
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PcbItem {
+	Content(PCBContent),
+	/// A node that is not modelled (yet), like the nodes recorded in
+	/// [`PCB::unknown`](pcb::PCB::unknown).
+	Unknown(UnknownNode)
+}
+
+impl Iterator for PcbReader<'_> {
+	type Item = Result<PcbItem, crate::Error>;
+
+	/// Parse the next item of the board. Like [`PCB::from_str`](pcb::PCB), items
+	/// that are not modelled (yet) are returned as their s-expr text. Once an
+	/// error was returned, the iterator ends.
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			return None;
+		}
+		let rest = &self.input[self.offset..];
+		let item = scanner::next_child(NAME, rest, false)
+			.map_err(crate::Error::from)
+			.and_then(|child| {
+				let Some((node, next)) = child else {
+					return Ok(None);
+				};
+				self.offset = self.input.len() - next.len();
+				match pcb::content_from_node(NAME, node)? {
+					Some(content) => {
+						self.index += 1;
+						Ok(Some(PcbItem::Content(content)))
+					},
+					None => {
+						let text = raw_node::canonical(node.text).map_err(|err| {
+							crate::Error::from(err).within(node.name).within(NAME)
+						})?;
+						let index = self.index;
+						Ok(Some(PcbItem::Unknown(UnknownNode { index, text })))
+					}
+				}
+			});
+		match item {
+			Ok(Some(item)) => Some(Ok(item)),
+			Ok(None) => {
+				self.done = true;
+				None
+			},
+			Err(err) => {
+				self.done = true;
+				Some(Err(err))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::board::pcb::PCB;
+	use std::fs;
+
+	#[test]
+	fn minnow() {
+		let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/minnow.kicad_pcb");
+		let contents = fs::read_to_string(path).unwrap();
+		let mut reader = PcbReader::new(&contents).unwrap();
+		assert_eq!(reader.generator(), "pcbnew");
+		assert_eq!(reader.layers().len(), 29);
+
+		let (mut nets, mut segments, mut footprints, mut items) = (0, 0, 0, 0);
+		let mut unknown = Vec::new();
+		for item in &mut reader {
+			match item.unwrap() {
+				PcbItem::Content(PCBContent::Net(_)) => nets += 1,
+				PcbItem::Content(PCBContent::Segment(_)) => segments += 1,
+				PcbItem::Content(PCBContent::Footprint(_)) => footprints += 1,
+				PcbItem::Content(_) => {},
+				PcbItem::Unknown(node) => {
+					unknown.push(node);
+					continue;
+				}
+			}
+			items += 1;
+		}
+		assert_eq!((nets, segments, footprints, items), (58, 599, 69, 897));
+		assert!(reader.next().is_none());
+
+		let pcb: PCB = contents.parse().unwrap();
+		assert_eq!(reader.version(), pcb.version);
+		assert_eq!(items, pcb.pcb_content.len());
+		assert_eq!(unknown, pcb.unknown);
+	}
+
+	#[test]
+	fn truncated() {
+		let input = r#"(kicad_pcb (version 20221018) (generator pcbnew) (paper "A4") (net 0 "")"#;
+		let mut reader = PcbReader::new(input).unwrap();
+		assert!(matches!(
+			reader.next(),
+			Some(Ok(PcbItem::Content(PCBContent::Net(_))))
+		));
+		assert!(reader.next().unwrap().is_err());
+		assert!(reader.next().is_none());
+	}
+
+	#[test]
+	fn unknown_node() {
+		let input = r#"(kicad_pcb (version 20221018) (generator pcbnew) (paper "A4") (net 0 "") (bogus 1 2) (net 1 "GND"))"#;
+		let items: Vec<_> = PcbReader::new(input)
+			.unwrap()
+			.collect::<Result<_, _>>()
+			.unwrap();
+		assert_eq!(items.len(), 3);
+		assert_eq!(
+			items[1],
+			PcbItem::Unknown(UnknownNode {
+				index: 1,
+				text: "(bogus 1 2)".to_owned()
+			})
+		);
+	}
+
+	#[test]
+	fn missing_header() {
+		let err = PcbReader::new("(kicad_pcb (version 20221018) (net 0 \"\"))")
+			.unwrap_err();
+		assert_eq!(err.path(), ["kicad_pcb"]);
+	}
+}
@@ -0,0 +1,29 @@
+//! A typed view of the contents of a board.
+
+use crate::board::{
+	graphic::{GraphicItem, Segment, Via},
+	pcb::{Group, Layer, Net, Property, Setup},
+	Footprint, Image, Zone
+};
+
+/// The contents of a board sorted by their type, returned by
+/// [`PCB::sections`](crate::pcb::PCB::sections). All items keep the order in
+/// which they appear in the file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PcbSections<'a> {
+	pub layers: &'a [Layer],
+	pub setup: Option<&'a Setup>,
+	pub properties: Vec<&'a Property>,
+	pub nets: Vec<&'a Net>,
+	pub footprints: Vec<&'a Footprint>,
+	/// Copies of the graphic items and dimensions on the board itself, i.e. not
+	/// including the graphics of footprints. The board stores these as separate
+	/// [`PCBContent`](crate::pcb::PCBContent) variants, so they cannot be
+	/// borrowed as a [`GraphicItem`].
+	pub graphics: Vec<GraphicItem>,
+	pub segments: Vec<&'a Segment>,
+	pub vias: Vec<&'a Via>,
+	pub zones: Vec<&'a Zone>,
+	pub groups: Vec<&'a Group>,
+	pub images: Vec<&'a Image>
+}
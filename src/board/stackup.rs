@@ -0,0 +1,213 @@
+//! The physical stackup of a board, as used for fabrication.
+
+use crate::{
+	internal::{option_tuple, tuple, yes_no::YesNo},
+	mm
+};
+use serde::{Deserialize, Serialize};
+use serde_sexpr::untagged;
+
+/// The physical stackup of the board, defined inside the board setup.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "StackUpDef", into = "StackUpDef")]
+pub struct StackUp {
+	/// The layers from top to bottom.
+	pub layers: Vec<StackupLayer>,
+
+	/// The surface finish of the copper, e.g. `ENIG`.
+	pub copper_finish: Option<String>,
+
+	/// Whether the board thickness constraints apply to the dielectric layers.
+	pub dielectric_constraints: Option<bool>,
+
+	/// The type of edge connector, either `yes` or `bevelled`.
+	pub edge_connector: Option<String>,
+
+	pub castellated_pads: bool,
+
+	pub edge_plating: bool
+}
+
+/// A layer of the [`StackUp`]. This is either a board layer like `F.Cu`, or a
+/// dielectric layer that is only part of the stackup.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename = "layer")]
+pub struct StackupLayer {
+	pub name: String,
+
+	/// The type of the layer, e.g. `copper`, `core` or `Top Solder Mask`.
+	#[serde(with = "tuple", rename = "type")]
+	pub layer_type: String,
+
+	#[serde(with = "option_tuple")]
+	pub color: Option<String>,
+
+	#[serde(with = "option_tuple")]
+	pub thickness: Option<mm>,
+
+	#[serde(with = "option_tuple")]
+	pub material: Option<String>,
+
+	/// The dielectric constant of the material.
+	#[serde(with = "option_tuple")]
+	pub epsilon_r: Option<f32>,
+
+	#[serde(with = "option_tuple")]
+	pub loss_tangent: Option<f32>
+}
+
+impl StackupLayer {
+	pub fn new<N, T>(name: N, layer_type: T) -> Self
+	where
+		N: Into<String>,
+		T: Into<String>
+	{
+		Self {
+			name: name.into(),
+			layer_type: layer_type.into(),
+			..Default::default()
+		}
+	}
+
+	pub fn is_dielectric(&self) -> bool {
+		matches!(self.layer_type.as_str(), "core" | "prepreg")
+	}
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename = "copper_finish")]
+struct CopperFinish(String);
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename = "dielectric_constraints")]
+struct DielectricConstraints(YesNo);
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename = "edge_connector")]
+struct EdgeConnector(String);
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename = "castellated_pads")]
+struct CastellatedPads(YesNo);
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename = "edge_plating")]
+struct EdgePlating(YesNo);
+
+untagged! {
+	enum StackUpContent {
+		Layer(StackupLayer),
+		CopperFinish(CopperFinish),
+		DielectricConstraints(DielectricConstraints),
+		EdgeConnector(EdgeConnector),
+		CastellatedPads(CastellatedPads),
+		EdgePlating(EdgePlating)
+	}
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename = "stackup")]
+struct StackUpDef {
+	#[serde(default, rename = "")]
+	content: Vec<StackUpContent>
+}
+
+impl From<StackUpDef> for StackUp {
+	fn from(def: StackUpDef) -> Self {
+		let mut stackup = Self::default();
+		for content in def.content {
+			match content {
+				StackUpContent::Layer(layer) => stackup.layers.push(layer),
+				StackUpContent::CopperFinish(CopperFinish(finish)) => {
+					stackup.copper_finish = Some(finish)
+				},
+				StackUpContent::DielectricConstraints(DielectricConstraints(yn)) => {
+					stackup.dielectric_constraints = Some(yn.into())
+				},
+				StackUpContent::EdgeConnector(EdgeConnector(connector)) => {
+					stackup.edge_connector = Some(connector)
+				},
+				StackUpContent::CastellatedPads(CastellatedPads(yn)) => {
+					stackup.castellated_pads = yn.into()
+				},
+				StackUpContent::EdgePlating(EdgePlating(yn)) => {
+					stackup.edge_plating = yn.into()
+				},
+			}
+		}
+		stackup
+	}
+}
+
+impl From<StackUp> for StackUpDef {
+	fn from(stackup: StackUp) -> Self {
+		let mut content: Vec<_> = stackup
+			.layers
+			.into_iter()
+			.map(StackUpContent::Layer)
+			.collect();
+		if let Some(finish) = stackup.copper_finish {
+			content.push(StackUpContent::CopperFinish(CopperFinish(finish)));
+		}
+		if let Some(constraints) = stackup.dielectric_constraints {
+			content.push(StackUpContent::DielectricConstraints(
+				DielectricConstraints(constraints.into())
+			));
+		}
+		if let Some(connector) = stackup.edge_connector {
+			content.push(StackUpContent::EdgeConnector(EdgeConnector(connector)));
+		}
+		if stackup.castellated_pads {
+			content
+				.push(StackUpContent::CastellatedPads(CastellatedPads(YesNo::Yes)));
+		}
+		if stackup.edge_plating {
+			content.push(StackUpContent::EdgePlating(EdgePlating(YesNo::Yes)));
+		}
+		Self { content }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{sexpr_test_case, Unit};
+
+	sexpr_test_case! {
+		name: stackup,
+		input: r#"(stackup (layer "F.Cu" (type copper) (thickness 0.035)) (layer "dielectric 1" (type core) (thickness 1.51) (material "FR4") (epsilon_r 4.5) (loss_tangent 0.02)) (layer "B.Cu" (type copper) (thickness 0.035)) (layer "B.Mask" (type "Bottom Solder Mask") (color Green) (thickness 0.01)) (copper_finish ENIG) (dielectric_constraints no))"#,
+		value: StackUp {
+			layers: vec![
+				StackupLayer {
+					thickness: Some(0.035.mm()),
+					..StackupLayer::new("F.Cu", "copper")
+				},
+				StackupLayer {
+					thickness: Some(1.51.mm()),
+					material: Some("FR4".into()),
+					epsilon_r: Some(4.5),
+					loss_tangent: Some(0.02),
+					..StackupLayer::new("dielectric 1", "core")
+				},
+				StackupLayer {
+					thickness: Some(0.035.mm()),
+					..StackupLayer::new("B.Cu", "copper")
+				},
+				StackupLayer {
+					color: Some("Green".into()),
+					thickness: Some(0.01.mm()),
+					..StackupLayer::new("B.Mask", "Bottom Solder Mask")
+				},
+			],
+			copper_finish: Some("ENIG".into()),
+			dielectric_constraints: Some(false),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn dielectric_layers() {
+		assert!(StackupLayer::new("dielectric 1", "core").is_dielectric());
+		assert!(!StackupLayer::new("F.Cu", "copper").is_dielectric());
+	}
+}
@@ -0,0 +1,22 @@
+//! Summary statistics of a board.
+
+use crate::mm;
+use serde::Serialize;
+
+/// The number of items of a board, returned by
+/// [`PCB::statistics`](crate::pcb::PCB::statistics).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct BoardStats {
+	/// The number of nets, including the unconnected net `0`.
+	pub nets: usize,
+	pub layers: usize,
+	/// The number of track segments and vias.
+	pub tracks: usize,
+	pub zones: usize,
+	/// The number of graphic items on the board itself, i.e. not counting the
+	/// graphics of footprints.
+	pub graphics: usize,
+	/// The summed up length of all track segments. Vias do not contribute to
+	/// the length.
+	pub track_length: mm
+}
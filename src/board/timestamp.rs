@@ -4,7 +4,11 @@ use serde::{
 	de::{self, Deserializer, Unexpected, Visitor},
 	Deserialize, Serialize, Serializer
 };
-use std::fmt::{self, Formatter};
+use std::{
+	collections::HashMap,
+	fmt::{self, Formatter}
+};
+use uuid::Uuid;
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
 #[serde(deny_unknown_fields, rename = "tedit")]
@@ -85,6 +89,58 @@ impl Serialize for Timestamp {
 	}
 }
 
+/// Which UUIDs should be replaced when regenerating timestamps.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Regenerate {
+	/// Only replace nil UUIDs.
+	#[default]
+	Nil,
+	/// Replace every UUID.
+	All
+}
+
+/// Helper to hand out fresh v4 UUIDs while remembering which old UUID has been
+/// replaced by which new one, so that references (e.g. group members) can be
+/// updated afterwards.
+#[derive(Clone, Debug, Default)]
+pub struct UuidRemap {
+	mode: Regenerate,
+	map: HashMap<Uuid, Uuid>
+}
+
+impl UuidRemap {
+	pub fn new(mode: Regenerate) -> Self {
+		Self {
+			mode,
+			map: HashMap::new()
+		}
+	}
+
+	/// Replace `uuid` with a freshly generated one if required by the mode.
+	pub fn regenerate(&mut self, uuid: &mut Uuid) {
+		if !uuid.is_nil() && self.mode == Regenerate::Nil {
+			return;
+		}
+		let new = Uuid::new_v4();
+		if !uuid.is_nil() {
+			self.map.insert(*uuid, new);
+		}
+		*uuid = new;
+	}
+
+	/// Update a reference to a UUID that has been regenerated.
+	pub fn remap(&self, uuid: &mut Uuid) {
+		if let Some(new) = self.map.get(uuid) {
+			*uuid = *new;
+		}
+	}
+
+	/// Return the new UUID for `uuid`, if it has been regenerated.
+	pub fn get(&self, uuid: &Uuid) -> Option<Uuid> {
+		self.map.get(uuid).copied()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -119,4 +175,24 @@ mod tests {
 		input: "(tedit DEADBEEF)",
 		value: Timestamp(0xDEADBEEF)
 	}
+
+	#[test]
+	fn test_uuid_remap() {
+		let old = Uuid::new_v4();
+		let mut nil = Uuid::nil();
+		let mut existing = old;
+
+		let mut remap = UuidRemap::new(Regenerate::Nil);
+		remap.regenerate(&mut nil);
+		remap.regenerate(&mut existing);
+		assert!(!nil.is_nil());
+		assert_eq!(existing, old);
+
+		let mut remap = UuidRemap::new(Regenerate::All);
+		remap.regenerate(&mut existing);
+		assert_ne!(existing, old);
+		let mut reference = old;
+		remap.remap(&mut reference);
+		assert_eq!(reference, existing);
+	}
 }
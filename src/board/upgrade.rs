@@ -0,0 +1,28 @@
+//! Migrations between the file format versions of a board.
+
+use super::pcb::Version;
+
+/// The version that replaced the `(width ..)` of graphics with
+/// `(stroke (width ..) (type ..))`.
+pub(crate) const STROKE_VERSION: u32 = 20211229;
+
+/// Format changes that [`PCB::upgrade_to`](crate::pcb::PCB::upgrade_to) does
+/// not migrate, together with the version that introduced them.
+pub(crate) const NOT_MIGRATED: &[(u32, &str)] = &[
+	(20220225, "footprints no longer have a `(tedit ..)`"),
+	(20231231, "items use `(uuid ..)` instead of `(tstamp ..)`")
+];
+
+/// The migrations performed by
+/// [`PCB::upgrade_to`](crate::pcb::PCB::upgrade_to).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeReport {
+	pub from: Version,
+	pub to: Version,
+	/// The number of board and footprint graphics whose legacy `(width ..)` was
+	/// converted into a solid `(stroke ..)`.
+	pub converted_strokes: usize,
+	/// The known format changes between the two versions that were left as
+	/// they are, described by the version that introduced them.
+	pub not_migrated: Vec<(Version, &'static str)>
+}
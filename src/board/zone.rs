@@ -0,0 +1,506 @@
+//! Copper zones of a printed circuit board.
+
+use crate::{
+	board::Layer,
+	common::PointList,
+	internal::{option_tuple, option_unit, option_yes_no, tstamp, tuple},
+	mm
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A copper zone, like a ground pour.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "zone")]
+pub struct Zone {
+	#[serde(with = "tuple")]
+	pub net: u32,
+
+	#[serde(with = "tuple")]
+	pub net_name: String,
+
+	/// The layer of a single-layer zone.
+	#[serde(with = "serde_sexpr::Option")]
+	pub layer: Option<Layer>,
+
+	/// The layers of a multi-layer zone.
+	#[serde(with = "serde_sexpr::Option")]
+	pub layers: Option<Vec<String>>,
+
+	#[serde(with = "tstamp", skip_serializing_if = "crate::skip_uuid")]
+	pub tstamp: Uuid,
+
+	#[serde(with = "option_tuple")]
+	pub name: Option<String>,
+
+	pub hatch: Hatch,
+
+	#[serde(with = "option_tuple")]
+	pub priority: Option<u32>,
+
+	#[serde(with = "serde_sexpr::Option")]
+	pub connect_pads: Option<ZoneConnectPads>,
+
+	#[serde(with = "tuple")]
+	pub min_thickness: mm,
+
+	#[serde(with = "option_yes_no")]
+	pub filled_areas_thickness: Option<bool>,
+
+	/// Present if this zone is a rule area that restricts other board items
+	/// rather than a copper pour.
+	#[serde(with = "serde_sexpr::Option")]
+	pub keepout: Option<Keepout>,
+
+	pub fill: ZoneFill,
+
+	pub polygon: ZonePolygon,
+
+	#[serde(default, rename = "")]
+	pub filled_polygons: Vec<FilledPolygon>
+}
+
+impl Zone {
+	pub fn is_keepout(&self) -> bool {
+		self.keepout.is_some()
+	}
+
+	/// Return the copper fill settings of this zone, or `None` for keepout
+	/// areas which are never filled with copper.
+	pub fn copper_fill(&self) -> Option<&ZoneFill> {
+		match self.keepout {
+			Some(_) => None,
+			None => Some(&self.fill)
+		}
+	}
+
+	/// Return `true` if this zone is placed on the given layer.
+	pub fn is_on_layer(&self, layer: &str) -> bool {
+		self.layer.as_ref().is_some_and(|l| l.0 == layer)
+			|| self.layers.iter().flatten().any(|l| l == layer)
+	}
+
+	/// Return the area of copper this zone covers on the given layer in square
+	/// millimeters. This is the area of the filled polygons once the zone is
+	/// filled, otherwise the area of its outline. Keepout areas have no copper.
+	pub fn copper_area_on_layer(&self, layer: &str) -> mm {
+		if self.is_keepout() || !self.is_on_layer(layer) {
+			return mm::default();
+		}
+		let mut filled = self
+			.filled_polygons
+			.iter()
+			.filter(|polygon| polygon.layer.0 == layer)
+			.peekable();
+		if filled.peek().is_none() {
+			return self.polygon.pts.area();
+		}
+		filled.fold(mm::default(), |sum, polygon| sum + polygon.pts.area())
+	}
+
+	/// Move this zone and its filled polygons from layer `from` to layer `to`,
+	/// returning the number of items changed.
+	pub fn remap_layer(&mut self, from: &str, to: &str) -> usize {
+		let mut changed = 0;
+		if let Some(layer) = self.layer.as_mut().filter(|layer| layer.0 == from) {
+			layer.0 = to.to_owned();
+			changed += 1;
+		}
+		if let Some(layers) = self.layers.as_mut() {
+			if layers.iter().any(|l| l == from) {
+				let has_to = layers.iter().any(|l| l == to);
+				layers.retain(|l| l != from || !has_to);
+				layers.iter_mut().filter(|l| *l == from).for_each(|l| {
+					*l = to.to_owned();
+				});
+				changed += 1;
+			}
+		}
+		for polygon in &mut self.filled_polygons {
+			if polygon.layer.0 == from {
+				polygon.layer.0 = to.to_owned();
+				changed += 1;
+			}
+		}
+		changed
+	}
+}
+
+/// The hatching used to display the outline of a zone.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "hatch")]
+pub struct Hatch {
+	pub style: HatchStyle,
+	pub pitch: mm
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HatchStyle {
+	None,
+	Edge,
+	Full
+}
+
+/// The restrictions of a keepout area.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "keepout")]
+pub struct Keepout {
+	#[serde(with = "tuple")]
+	pub tracks: KeepoutPermission,
+
+	#[serde(with = "tuple")]
+	pub vias: KeepoutPermission,
+
+	#[serde(with = "tuple")]
+	pub pads: KeepoutPermission,
+
+	#[serde(with = "tuple")]
+	pub copperpour: KeepoutPermission,
+
+	#[serde(with = "tuple")]
+	pub footprints: KeepoutPermission
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeepoutPermission {
+	Allowed,
+	NotAllowed
+}
+
+/// How a zone connects to the pads of its own net.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ConnectMode {
+	/// Pads are connected using thermal reliefs. This mode has no keyword.
+	#[default]
+	ThermalReliefs,
+	/// Pads are connected using solid copper, written as `yes`.
+	Full,
+	/// Only through hole pads are connected, using thermal reliefs.
+	ThruHoleOnly,
+	/// Pads are not connected, written as `no`.
+	No
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename = "connect_pads")]
+struct ZoneConnectPadsDef {
+	yes: bool,
+
+	no: bool,
+
+	thru_hole_only: bool,
+
+	#[serde(with = "tuple")]
+	clearance: mm
+}
+
+impl From<ZoneConnectPadsDef> for ZoneConnectPads {
+	fn from(def: ZoneConnectPadsDef) -> Self {
+		let mode = match (def.yes, def.no, def.thru_hole_only) {
+			(true, ..) => ConnectMode::Full,
+			(_, true, _) => ConnectMode::No,
+			(_, _, true) => ConnectMode::ThruHoleOnly,
+			_ => ConnectMode::ThermalReliefs
+		};
+		Self {
+			mode,
+			clearance: def.clearance
+		}
+	}
+}
+
+impl From<ZoneConnectPads> for ZoneConnectPadsDef {
+	fn from(connect: ZoneConnectPads) -> Self {
+		Self {
+			yes: connect.mode == ConnectMode::Full,
+			no: connect.mode == ConnectMode::No,
+			thru_hole_only: connect.mode == ConnectMode::ThruHoleOnly,
+			clearance: connect.clearance
+		}
+	}
+}
+
+/// How the zone connects to pads of its net, and the clearance between the
+/// zone and pads of other nets.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(from = "ZoneConnectPadsDef", into = "ZoneConnectPadsDef")]
+pub struct ZoneConnectPads {
+	pub mode: ConnectMode,
+
+	pub clearance: mm
+}
+
+impl ZoneConnectPads {
+	/// Connect pads using thermal reliefs, with the given clearance.
+	pub fn new(clearance: mm) -> Self {
+		Self {
+			mode: ConnectMode::ThermalReliefs,
+			clearance
+		}
+	}
+}
+
+/// The fill settings of a zone, including the thermal relief parameters.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "fill")]
+pub struct ZoneFill {
+	/// Whether the zone has been filled.
+	#[serde(rename = "yes")]
+	pub filled: bool,
+
+	#[serde(with = "option_tuple")]
+	pub mode: Option<ZoneFillMode>,
+
+	#[serde(with = "tuple")]
+	pub thermal_gap: mm,
+
+	#[serde(with = "tuple")]
+	pub thermal_bridge_width: mm,
+
+	#[serde(with = "option_tuple")]
+	pub smoothing: Option<ZoneSmoothing>,
+
+	#[serde(with = "option_tuple")]
+	pub radius: Option<mm>,
+
+	#[serde(with = "option_tuple")]
+	pub island_removal_mode: Option<u8>,
+
+	#[serde(with = "option_tuple")]
+	pub island_area_min: Option<f32>,
+
+	#[serde(with = "option_tuple")]
+	pub hatch_thickness: Option<mm>,
+
+	#[serde(with = "option_tuple")]
+	pub hatch_gap: Option<mm>,
+
+	#[serde(with = "option_tuple")]
+	pub hatch_orientation: Option<f32>,
+
+	#[serde(with = "option_tuple")]
+	pub hatch_smoothing_level: Option<u8>,
+
+	#[serde(with = "option_tuple")]
+	pub hatch_smoothing_value: Option<f32>,
+
+	#[serde(with = "option_tuple")]
+	pub hatch_border_algorithm: Option<ZoneHatchBorder>,
+
+	#[serde(with = "option_tuple")]
+	pub hatch_min_hole_area: Option<f32>
+}
+
+impl ZoneFill {
+	/// Create the fill settings of an unfilled zone with solid fill mode.
+	pub fn new(thermal_gap: mm, thermal_bridge_width: mm) -> Self {
+		Self {
+			filled: false,
+			mode: None,
+			thermal_gap,
+			thermal_bridge_width,
+			smoothing: None,
+			radius: None,
+			island_removal_mode: None,
+			island_area_min: None,
+			hatch_thickness: None,
+			hatch_gap: None,
+			hatch_orientation: None,
+			hatch_smoothing_level: None,
+			hatch_smoothing_value: None,
+			hatch_border_algorithm: None,
+			hatch_min_hole_area: None
+		}
+	}
+}
+
+/// The fill mode of a zone. If not present, the zone is filled solid.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneFillMode {
+	Hatch
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneSmoothing {
+	Chamfer,
+	Fillet,
+	None
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneHatchBorder {
+	HatchThickness,
+	MinThickness
+}
+
+/// The outline of a zone.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "polygon")]
+pub struct ZonePolygon {
+	pub pts: PointList
+}
+
+/// A polygon of copper that results from filling the zone.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "filled_polygon")]
+pub struct FilledPolygon {
+	pub layer: Layer,
+
+	#[serde(with = "option_unit")]
+	pub island: bool,
+
+	pub pts: PointList
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{common::Point, sexpr_test_case, Unit};
+
+	fn square() -> PointList {
+		PointList::new(vec![
+			Point::new(0.0.mm(), 0.0.mm()),
+			Point::new(10.0.mm(), 0.0.mm()),
+			Point::new(10.0.mm(), 10.0.mm()),
+			Point::new(0.0.mm(), 10.0.mm()),
+		])
+	}
+
+	sexpr_test_case! {
+		name: hatched_zone,
+		input: r#"(zone (net 2) (net_name GND) (layers "F.Cu" "B.Cu") (tstamp "00000000-0000-0000-0000-000000000000") (hatch edge 0.508) (connect_pads (clearance 0.25)) (min_thickness 0.25) (filled_areas_thickness no) (fill yes (thermal_gap 0.5) (thermal_bridge_width 0.5)) (polygon (pts (xy 0 0) (xy 10 0) (xy 10 10) (xy 0 10))))"#,
+		value: Zone {
+			net: 2,
+			net_name: "GND".to_owned(),
+			layer: None,
+			layers: Some(vec!["F.Cu".to_owned(), "B.Cu".to_owned()]),
+			tstamp: Uuid::nil(),
+			name: None,
+			hatch: Hatch {
+				style: HatchStyle::Edge,
+				pitch: 0.508.mm()
+			},
+			priority: None,
+			connect_pads: Some(ZoneConnectPads::new(0.25.mm())),
+			min_thickness: 0.25.mm(),
+			filled_areas_thickness: Some(false),
+			keepout: None,
+			fill: ZoneFill {
+				filled: true,
+				..ZoneFill::new(0.5.mm(), 0.5.mm())
+			},
+			polygon: ZonePolygon { pts: square() },
+			filled_polygons: Vec::new()
+		}
+	}
+
+	sexpr_test_case! {
+		name: hatch_filled_zone,
+		input: r#"(zone (net 1) (net_name VCC) (layer "B.Cu") (tstamp "00000000-0000-0000-0000-000000000000") (hatch full 0.5) (priority 1) (min_thickness 0.2) (fill (mode hatch) (thermal_gap 0.3) (thermal_bridge_width 0.4) (hatch_thickness 1) (hatch_gap 1.5)) (polygon (pts (xy 0 0) (xy 10 0) (xy 10 10) (xy 0 10))) (filled_polygon (layer "B.Cu") (island) (pts (xy 0 0) (xy 10 0) (xy 10 10) (xy 0 10))))"#,
+		value: Zone {
+			net: 1,
+			net_name: "VCC".to_owned(),
+			layer: Some(Layer("B.Cu".to_owned())),
+			layers: None,
+			tstamp: Uuid::nil(),
+			name: None,
+			hatch: Hatch {
+				style: HatchStyle::Full,
+				pitch: 0.5.mm()
+			},
+			priority: Some(1),
+			connect_pads: None,
+			min_thickness: 0.2.mm(),
+			filled_areas_thickness: None,
+			keepout: None,
+			fill: ZoneFill {
+				mode: Some(ZoneFillMode::Hatch),
+				hatch_thickness: Some(1.0.mm()),
+				hatch_gap: Some(1.5.mm()),
+				..ZoneFill::new(0.3.mm(), 0.4.mm())
+			},
+			polygon: ZonePolygon { pts: square() },
+			filled_polygons: vec![FilledPolygon {
+				layer: Layer("B.Cu".to_owned()),
+				island: true,
+				pts: square()
+			}]
+		}
+	}
+
+	sexpr_test_case! {
+		name: keepout_zone,
+		input: r#"(zone (net 0) (net_name "") (layer "F.Cu") (tstamp "00000000-0000-0000-0000-000000000000") (hatch full 0.508) (connect_pads (clearance 0)) (min_thickness 0.254) (filled_areas_thickness no) (keepout (tracks not_allowed) (vias not_allowed) (pads allowed) (copperpour not_allowed) (footprints allowed)) (fill (thermal_gap 0.508) (thermal_bridge_width 0.508)) (polygon (pts (xy 0 0) (xy 10 0) (xy 10 10) (xy 0 10))))"#,
+		value: Zone {
+			net: 0,
+			net_name: "".to_owned(),
+			layer: Some(Layer("F.Cu".to_owned())),
+			layers: None,
+			tstamp: Uuid::nil(),
+			name: None,
+			hatch: Hatch {
+				style: HatchStyle::Full,
+				pitch: 0.508.mm()
+			},
+			priority: None,
+			connect_pads: Some(ZoneConnectPads::new(0.0.mm())),
+			min_thickness: 0.254.mm(),
+			filled_areas_thickness: Some(false),
+			keepout: Some(Keepout {
+				tracks: KeepoutPermission::NotAllowed,
+				vias: KeepoutPermission::NotAllowed,
+				pads: KeepoutPermission::Allowed,
+				copperpour: KeepoutPermission::NotAllowed,
+				footprints: KeepoutPermission::Allowed
+			}),
+			fill: ZoneFill::new(0.508.mm(), 0.508.mm()),
+			polygon: ZonePolygon { pts: square() },
+			filled_polygons: Vec::new()
+		}
+	}
+
+	#[test]
+	fn keepout_has_no_copper_fill() {
+		let zone: Zone = serde_sexpr::from_str(r#"(zone (net 0) (net_name "") (layer "F.Cu") (hatch edge 0.5) (min_thickness 0.25) (keepout (tracks not_allowed) (vias not_allowed) (pads allowed) (copperpour not_allowed) (footprints allowed)) (fill (thermal_gap 0.5) (thermal_bridge_width 0.5)) (polygon (pts (xy 0 0) (xy 1 0) (xy 1 1))))"#).unwrap();
+		assert!(zone.is_keepout());
+		assert_eq!(zone.copper_fill(), None);
+	}
+
+	sexpr_test_case! {
+		name: connect_pads_thermal_reliefs,
+		input: "(connect_pads (clearance 0.5))",
+		value: ZoneConnectPads::new(0.5.mm())
+	}
+
+	sexpr_test_case! {
+		name: connect_pads_full,
+		input: "(connect_pads yes (clearance 0.5))",
+		value: ZoneConnectPads {
+			mode: ConnectMode::Full,
+			clearance: 0.5.mm()
+		}
+	}
+
+	sexpr_test_case! {
+		name: connect_pads_thru_hole_only,
+		input: "(connect_pads thru_hole_only (clearance 0.3))",
+		value: ZoneConnectPads {
+			mode: ConnectMode::ThruHoleOnly,
+			clearance: 0.3.mm()
+		}
+	}
+
+	sexpr_test_case! {
+		name: connect_pads_no,
+		input: "(connect_pads no (clearance 0))",
+		value: ZoneConnectPads {
+			mode: ConnectMode::No,
+			clearance: 0.0.mm()
+		}
+	}
+}
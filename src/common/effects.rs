@@ -12,12 +12,13 @@ fn justify_is_default(justify: &Justify) -> bool {
 	*justify == Justify::default()
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "effects")]
 pub struct Effects {
 	pub font: Font,
 
 	#[serde(
+		default,
 		deserialize_with = "justify_deserialize",
 		skip_serializing_if = "justify_is_default"
 	)]
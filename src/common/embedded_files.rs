@@ -0,0 +1,144 @@
+use crate::internal::{base64, option_tuple, tuple};
+use serde::{Deserialize, Serialize};
+
+/// What an [`EmbeddedFile`] is used for.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddedFileType {
+	Datasheet,
+	Font,
+	Model,
+	Worksheet,
+	Other
+}
+
+/// A file bundled with a board or footprint, like a 3D model or datasheet.
+/// Only written by KiCad 8 and later.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "file")]
+pub struct EmbeddedFile {
+	#[serde(with = "tuple")]
+	pub name: String,
+
+	#[serde(rename = "type", with = "tuple")]
+	pub ty: EmbeddedFileType,
+
+	/// The base64 encoded, zstd compressed content of the file.
+	#[serde(with = "data")]
+	pub data: String,
+
+	/// The checksum KiCad uses to detect changes of the file content.
+	#[serde(with = "option_tuple")]
+	pub checksum: Option<String>
+}
+
+impl EmbeddedFile {
+	/// Return the compressed content of the file.
+	pub fn decode(&self) -> Vec<u8> {
+		base64::decode(&self.data)
+	}
+
+	/// Return the content of the file.
+	#[cfg(feature = "zstd")]
+	pub fn decompress(&self) -> std::io::Result<Vec<u8>> {
+		use std::io::{Error, ErrorKind, Read};
+
+		let compressed = self.decode();
+		let mut decoder = ruzstd::decoding::StreamingDecoder::new(&compressed[..])
+			.map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+		let mut content = Vec::new();
+		decoder.read_to_end(&mut content)?;
+		Ok(content)
+	}
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "embedded_files")]
+pub struct EmbeddedFiles {
+	#[serde(default, rename = "")]
+	pub files: Vec<EmbeddedFile>
+}
+
+/// The (de)serializer for the data of an embedded file. KiCad encloses the
+/// data in bars and splits it into multiple chunks.
+mod data {
+	use crate::internal::UnitVariant;
+	use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serializer};
+
+	/// The number of characters per chunk, taken from KiCad.
+	const CHUNK_LEN: usize = 76;
+
+	const BAR: UnitVariant = UnitVariant("data", 0, "|");
+
+	#[derive(Deserialize)]
+	#[serde(deny_unknown_fields, rename = "data")]
+	struct Data {
+		#[serde(default, rename = "")]
+		chunks: Vec<String>
+	}
+
+	pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+	where
+		D: Deserializer<'de>
+	{
+		let data = Data::deserialize(deserializer)?.chunks.concat();
+		Ok(data.trim_matches('|').to_owned())
+	}
+
+	/// The chunks cannot be written unquoted, so the bars are written as
+	/// separate tokens around the quoted chunks.
+	pub(super) fn serialize<S>(this: &str, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		let chunks: Vec<_> = this
+			.as_bytes()
+			.chunks(CHUNK_LEN)
+			.map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+			.collect();
+		let mut st = serializer.serialize_struct("data", 3)?;
+		st.serialize_field("", &BAR)?;
+		st.serialize_field("", &chunks)?;
+		st.serialize_field("", &BAR)?;
+		st.end()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sexpr_test_case;
+
+	sexpr_test_case! {
+		name: embedded_files,
+		input: r#"(embedded_files (file (name "logo.txt") (type other) (data | "KLUv/QBYKQAAaGVsbG8=" |) (checksum "5D41402ABC4B2A76B9719D911017C592")))"#,
+		value: EmbeddedFiles {
+			files: vec![EmbeddedFile {
+				name: "logo.txt".to_owned(),
+				ty: EmbeddedFileType::Other,
+				data: "KLUv/QBYKQAAaGVsbG8=".to_owned(),
+				checksum: Some("5D41402ABC4B2A76B9719D911017C592".to_owned())
+			}]
+		}
+	}
+
+	#[test]
+	fn kicad_data() {
+		let input = "(file (name \"logo.txt\") (type other) (data |KLUv/QBYKQAA\n\t\taGVsbG8=|))";
+		let file: EmbeddedFile = serde_sexpr::from_str(input).unwrap();
+		assert_eq!(file.data, "KLUv/QBYKQAAaGVsbG8=");
+		assert_eq!(file.checksum, None);
+	}
+
+	#[cfg(feature = "zstd")]
+	#[test]
+	fn decompress() {
+		let file = EmbeddedFile {
+			name: "logo.txt".to_owned(),
+			ty: EmbeddedFileType::Other,
+			data: "KLUv/QBYKQAAaGVsbG8=".to_owned(),
+			checksum: None
+		};
+		assert_eq!(file.decompress().unwrap(), b"hello");
+	}
+}
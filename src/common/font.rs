@@ -2,9 +2,14 @@ use super::Size;
 use crate::{internal::option_tuple, mm};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "font")]
 pub struct Font {
+	/// The name of the font, if it is not the KiCad font. Only written by KiCad
+	/// 7 and later.
+	#[serde(with = "option_tuple")]
+	pub face: Option<String>,
+
 	pub size: Size,
 
 	#[serde(with = "option_tuple")]
@@ -18,6 +23,7 @@ pub struct Font {
 impl Font {
 	pub fn new(size: mm) -> Self {
 		Self {
+			face: None,
 			size: Size::new(size, size),
 			thickness: None,
 			bold: false,
@@ -27,6 +33,7 @@ impl Font {
 
 	pub fn new_bold(size: mm) -> Self {
 		Self {
+			face: None,
 			size: Size::new(size, size),
 			thickness: None,
 			bold: true,
@@ -36,6 +43,7 @@ impl Font {
 
 	pub fn new_italic(size: mm) -> Self {
 		Self {
+			face: None,
 			size: Size::new(size, size),
 			thickness: None,
 			bold: false,
@@ -59,10 +67,23 @@ mod tests {
 		name: font_with_thickness,
 		input: "(font (size 1.27 1.27) (thickness 0.508))",
 		value: Font {
+			face: None,
 			size: Size::new(1.27.mm(), 1.27.mm()),
 			thickness: Some(0.508.mm()),
 			bold: false,
 			italic: false
 		}
 	}
+
+	sexpr_test_case! {
+		name: font_with_face,
+		input: "(font (face \"DejaVu Sans\") (size 0.6 0.6) (thickness 0.1))",
+		value: Font {
+			face: Some("DejaVu Sans".to_owned()),
+			size: Size::new(0.6.mm(), 0.6.mm()),
+			thickness: Some(0.1.mm()),
+			bold: false,
+			italic: false
+		}
+	}
 }
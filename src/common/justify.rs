@@ -4,32 +4,22 @@ use serde::{
 };
 use std::fmt::{self, Formatter};
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum JustifyHoriz {
 	Left,
+	#[default]
 	Center,
 	Right
 }
 
-impl Default for JustifyHoriz {
-	fn default() -> Self {
-		Self::Center
-	}
-}
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum JustifyVert {
 	Top,
+	#[default]
 	Center,
 	Bottom
 }
 
-impl Default for JustifyVert {
-	fn default() -> Self {
-		Self::Center
-	}
-}
-
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub struct Justify {
 	pub horiz: JustifyHoriz,
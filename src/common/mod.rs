@@ -4,21 +4,25 @@
 //! footprint library, schematic, board, and work sheet file formats.
 
 mod effects;
+mod embedded_files;
 mod font;
 mod justify;
 mod paper;
 mod point;
-mod point_list;
+pub(crate) mod point_list;
 mod position;
+mod render_cache;
 mod size;
 mod title_block;
 
 pub use effects::Effects;
+pub use embedded_files::{EmbeddedFile, EmbeddedFileType, EmbeddedFiles};
 pub use font::Font;
 pub use justify::{Justify, JustifyHoriz, JustifyVert};
 pub use paper::{Paper, PaperSize};
 pub use point::Point;
 pub use point_list::PointList;
 pub use position::Position;
+pub use render_cache::{RenderCache, RenderPolygon};
 pub use size::Size;
 pub use title_block::TitleBlock;
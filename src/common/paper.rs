@@ -1,4 +1,4 @@
-use crate::{internal::UnitVariant, mm};
+use crate::{common::Point, internal::UnitVariant, mm, Unit};
 use serde::{
 	de::Error as _,
 	ser::{Serialize, SerializeStruct, Serializer},
@@ -37,6 +37,27 @@ impl PaperSize {
 			portrait: true
 		}
 	}
+
+	/// Return the width and height of this paper size in landscape orientation.
+	/// Custom sizes are returned as they are defined.
+	pub fn dimensions(self) -> (mm, mm) {
+		let (width, height) = match self {
+			Self::A0 => (1189.0, 841.0),
+			Self::A1 => (841.0, 594.0),
+			Self::A2 => (594.0, 420.0),
+			Self::A3 => (420.0, 297.0),
+			Self::A4 => (297.0, 210.0),
+			Self::A5 => (210.0, 148.0),
+			// the US sizes are defined in inches
+			Self::A => (279.4, 215.9),
+			Self::B => (431.8, 279.4),
+			Self::C => (558.8, 431.8),
+			Self::D => (863.6, 558.8),
+			Self::E => (1117.6, 863.6),
+			Self::Custom { width, height } => return (width, height)
+		};
+		(width.mm(), height.mm())
+	}
 }
 
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
@@ -70,16 +91,35 @@ pub struct Paper {
 	pub portrait: bool
 }
 
+impl Paper {
+	/// Return the width and height of the sheet. Portrait sheets swap the width
+	/// and height of their paper size, like KiCad does.
+	pub fn dimensions(&self) -> (mm, mm) {
+		let (width, height) = self.size.dimensions();
+		match self.portrait {
+			true => (height, width),
+			false => (width, height)
+		}
+	}
+
+	/// Return the center of the sheet. KiCad places the origin at the top-left
+	/// corner of the sheet.
+	pub fn center(&self) -> Point {
+		let (width, height) = self.dimensions();
+		Point::new(width / 2.0, height / 2.0)
+	}
+}
+
 #[derive(Deserialize)]
 #[serde(rename = "paper")]
 struct PaperDef {
-	#[serde(with = "serde_sexpr::Option")]
+	#[serde(default, with = "serde_sexpr::Option")]
 	width: Option<mm>,
 
-	#[serde(with = "serde_sexpr::Option")]
+	#[serde(default, with = "serde_sexpr::Option")]
 	height: Option<mm>,
 
-	#[serde(with = "serde_sexpr::Option")]
+	#[serde(default, with = "serde_sexpr::Option")]
 	size: Option<String>,
 
 	portrait: bool
@@ -184,4 +224,32 @@ mod tests {
 			height: 297.0.mm()
 		}.portrait()
 	}
+
+	#[test]
+	fn dimensions() {
+		assert_eq!(
+			PaperSize::A4.landscape().dimensions(),
+			(297.0.mm(), 210.0.mm())
+		);
+		assert_eq!(
+			PaperSize::A4.portrait().dimensions(),
+			(210.0.mm(), 297.0.mm())
+		);
+		assert_eq!(PaperSize::A.landscape().dimensions().0, 11.0.inch());
+	}
+
+	#[test]
+	fn center() {
+		let center = PaperSize::A4.portrait().center();
+		assert_eq!(center, Point::new(105.0.mm(), 148.5.mm()));
+
+		let custom = PaperSize::Custom {
+			width: 100.0.mm(),
+			height: 50.0.mm()
+		};
+		assert_eq!(
+			custom.landscape().center(),
+			Point::new(50.0.mm(), 25.0.mm())
+		);
+	}
 }
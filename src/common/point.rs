@@ -1,11 +1,17 @@
-use crate::{deg, mm, Unit};
+use crate::{deg, internal::lenient_mm, mm, Unit};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+/// A point on the board or in a footprint or symbol. Points are ordered by
+/// their x coordinate first, and then by their y coordinate.
+#[derive(
+	Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize,
+)]
 #[serde(deny_unknown_fields, rename = "xy")]
 pub struct Point {
+	#[serde(with = "lenient_mm")]
 	pub x: mm,
 
+	#[serde(with = "lenient_mm")]
 	pub y: mm
 }
 
@@ -14,6 +20,13 @@ impl Point {
 		Self { x, y }
 	}
 
+	/// Return the euclidean distance between two points.
+	pub fn distance(self, other: Point) -> mm {
+		let dx = self.x - other.x;
+		let dy = self.y - other.y;
+		(dx * dx + dy * dy).sqrt()
+	}
+
 	pub fn rotate_around(self, mid: Point, angle: deg) -> Self {
 		let angle = angle.to_radians();
 		let sin = angle.sin();
@@ -27,6 +40,22 @@ impl Point {
 		}
 	}
 
+	/// Snap the point to the nearest intersection of a grid with the given
+	/// spacing, whose origin is at `(0, 0)`. A grid without spacing leaves the
+	/// point unchanged.
+	#[must_use]
+	pub fn snap_to_grid(self, grid: mm) -> Self {
+		let grid = grid.raw_value().abs();
+		if grid == 0.0 {
+			return self;
+		}
+		let snap = |v: mm| ((v.raw_value() / grid).round() * grid).mm();
+		Self {
+			x: snap(self.x),
+			y: snap(self.y)
+		}
+	}
+
 	#[must_use]
 	pub fn round_nm_precision(self) -> Self {
 		fn d(v: mm) -> mm {
@@ -56,4 +85,46 @@ mod tests {
 		input: "(xy 1.27 -2.54)",
 		value: Point::new(1.27.mm(), -2.54.mm())
 	}
+
+	#[test]
+	fn snap_to_grid() {
+		let point = Point::new(1.03.mm(), 2.07.mm());
+		assert_eq!(point.snap_to_grid(0.5.mm()), Point::new(1.0.mm(), 2.0.mm()));
+		assert_eq!(point.snap_to_grid(0.0.mm()), point);
+
+		let point = Point::new(-1.3.mm(), 0.26.mm());
+		assert_eq!(point.snap_to_grid(0.5.mm()), Point::new(-1.5.mm(), 0.5.mm()));
+	}
+
+	#[test]
+	fn distance() {
+		let a = Point::new(1.0.mm(), 1.0.mm());
+		let b = Point::new(4.0.mm(), -3.0.mm());
+		assert_eq!(a.distance(b), 5.0.mm());
+		assert_eq!(b.distance(a), 5.0.mm());
+	}
+
+	#[test]
+	fn ordering() {
+		let mut pts = vec![
+			Point::new(1.0.mm(), 0.0.mm()),
+			Point::new(0.0.mm(), 2.0.mm()),
+			Point::new(0.0.mm(), (-1.0).mm()),
+		];
+		pts.sort();
+		assert_eq!(pts, [
+			Point::new(0.0.mm(), (-1.0).mm()),
+			Point::new(0.0.mm(), 2.0.mm()),
+			Point::new(1.0.mm(), 0.0.mm())
+		]);
+	}
+
+	proptest::proptest! {
+		#[test]
+		fn point_round_trip(point in crate::internal::proptest::point()) {
+			let written = serde_sexpr::to_string(&point).unwrap();
+			let parsed: Point = serde_sexpr::from_str(&written).unwrap();
+			proptest::prop_assert_eq!(parsed, point, "{}", written);
+		}
+	}
 }
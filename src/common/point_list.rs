@@ -1,6 +1,11 @@
 use super::Point;
+use crate::{mm, Unit};
 use serde::{Deserialize, Serialize};
-use std::ops::{Deref, DerefMut};
+use std::{
+	f64::consts::TAU,
+	ops::{Deref, DerefMut},
+	slice, vec
+};
 
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "pts")]
@@ -17,6 +22,188 @@ impl PointList {
 	pub fn empty() -> Self {
 		Self { pts: Vec::new() }
 	}
+
+	/// Approximate the cubic Bézier curve defined by the four control points in
+	/// this list with `segments` line segments. The endpoints are preserved
+	/// exactly. If `segments` is zero, only the endpoints are returned. Lists that
+	/// do not contain exactly four control points are returned unchanged.
+	pub fn cubic_bezier_polyline(&self, segments: usize) -> PointList {
+		let [p0, p1, p2, p3] = match self.pts.as_slice() {
+			[p0, p1, p2, p3] => [*p0, *p1, *p2, *p3],
+			_ => return self.clone()
+		};
+		if segments == 0 {
+			return Self::new(vec![p0, p3]);
+		}
+
+		let mut pts = Vec::with_capacity(segments + 1);
+		pts.push(p0);
+		for i in 1..segments {
+			let t = i as f32 / segments as f32;
+			let u = 1.0 - t;
+			let (a, b, c, d) =
+				(u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+			pts.push(Point::new(
+				a * p0.x + b * p1.x + c * p2.x + d * p3.x,
+				a * p0.y + b * p1.y + c * p2.y + d * p3.y
+			));
+		}
+		pts.push(p3);
+		Self::new(pts)
+	}
+
+	/// Approximate the circular arc from `start` through `mid` to `end` with
+	/// `segments` line segments. The endpoints are preserved exactly. If `start`
+	/// and `end` coincide, the arc is a full circle with `mid` opposite of the
+	/// start. If the three points are (almost) collinear, the sweep is too small
+	/// to compute a center and only the endpoints are returned, as they are if
+	/// `segments` is zero.
+	pub fn arc_polyline(
+		start: Point,
+		mid: Point,
+		end: Point,
+		segments: usize
+	) -> PointList {
+		let xy = |p: Point| (p.x.raw_value() as f64, p.y.raw_value() as f64);
+		let ((x0, y0), (xm, ym), (x1, y1)) = (xy(start), xy(mid), xy(end));
+		let full_circle = start.distance(end) < 1.0.nm();
+
+		let (cx, cy) = if full_circle {
+			((x0 + xm) / 2.0, (y0 + ym) / 2.0)
+		} else {
+			// the circumcenter of the three points
+			let d = 2.0 * (x0 * (ym - y1) + xm * (y1 - y0) + x1 * (y0 - ym));
+			let scale =
+				start.distance(mid).max(mid.distance(end)).raw_value() as f64;
+			if segments == 0 || d.abs() < 1e-9 * scale * scale {
+				return Self::new(vec![start, end]);
+			}
+			let (s0, sm, s1) =
+				(x0 * x0 + y0 * y0, xm * xm + ym * ym, x1 * x1 + y1 * y1);
+			(
+				(s0 * (ym - y1) + sm * (y1 - y0) + s1 * (y0 - ym)) / d,
+				(s0 * (x1 - xm) + sm * (x0 - x1) + s1 * (xm - x0)) / d
+			)
+		};
+		if segments == 0 {
+			return Self::new(vec![start, end]);
+		}
+
+		let radius = (x0 - cx).hypot(y0 - cy);
+		let angle = |x: f64, y: f64| (y - cy).atan2(x - cx);
+		let ccw = |from: f64, to: f64| (to - from).rem_euclid(TAU);
+		let a0 = angle(x0, y0);
+		let sweep = if full_circle {
+			TAU
+		} else {
+			let to_end = ccw(a0, angle(x1, y1));
+			match ccw(a0, angle(xm, ym)) < to_end {
+				true => to_end,
+				false => to_end - TAU
+			}
+		};
+
+		let mut pts = Vec::with_capacity(segments + 1);
+		pts.push(start);
+		for i in 1..segments {
+			let a = a0 + sweep * i as f64 / segments as f64;
+			pts.push(Point::new(
+				((cx + radius * a.cos()) as f32).mm(),
+				((cy + radius * a.sin()) as f32).mm()
+			));
+		}
+		pts.push(end);
+		Self::new(pts)
+	}
+
+	/// Return `true` if the list has at least three points and the first and
+	/// last point coincide.
+	pub fn closed(&self) -> bool {
+		self.pts.len() > 2 && self.pts.first() == self.pts.last()
+	}
+
+	/// Return the area enclosed by the polygon described by this list, computed
+	/// with the shoelace formula. The polygon may be closed or not. Note that
+	/// the result is an area in square millimeters.
+	pub fn area(&self) -> mm {
+		self.signed_area().abs()
+	}
+
+	/// Like [`Self::area`], but the area is negative if the points are ordered
+	/// clockwise, assuming the y axis points up. Lists with fewer than three
+	/// points have no area.
+	pub fn signed_area(&self) -> mm {
+		((shoelace(&self.pts) / 2.0) as f32).mm()
+	}
+
+	/// Return the centroid of the polygon described by this list, or `None` if
+	/// the polygon has no area.
+	pub fn centroid(&self) -> Option<Point> {
+		let twice_area = shoelace(&self.pts);
+		if twice_area.abs() < f64::EPSILON {
+			return None;
+		}
+		let (cx, cy) = edges(&self.pts).fold((0.0, 0.0), |(cx, cy), (p0, p1)| {
+			let cross = p0.0 * p1.1 - p1.0 * p0.1;
+			(cx + (p0.0 + p1.0) * cross, cy + (p0.1 + p1.1) * cross)
+		});
+		let scale = 3.0 * twice_area;
+		Some(Point::new(
+			((cx / scale) as f32).mm(),
+			((cy / scale) as f32).mm()
+		))
+	}
+
+	/// Remove consecutive duplicate points, e.g. the zero length edges some
+	/// tools emit.
+	pub fn dedup_consecutive(&mut self) {
+		self.pts.dedup();
+	}
+
+	/// Bring the list into a canonical form so that lists describing the same
+	/// geometry compare equal. Consecutive duplicate points are removed. If
+	/// `normalize_winding` is set, the list is treated as a polygon: its points
+	/// are ordered counter-clockwise, assuming the y axis points up, starting
+	/// with the smallest point. Whether the polygon was [closed](Self::closed) is
+	/// preserved.
+	pub fn canonicalize(&mut self, normalize_winding: bool) {
+		self.dedup_consecutive();
+		if !normalize_winding || self.pts.len() < 3 {
+			return;
+		}
+
+		let closed = self.closed();
+		if closed {
+			self.pts.pop();
+		}
+		if shoelace(&self.pts) < 0.0 {
+			self.pts.reverse();
+		}
+		if let Some(start) = (0..self.pts.len()).min_by_key(|&i| self.pts[i]) {
+			self.pts.rotate_left(start);
+		}
+		if closed {
+			self.pts.push(self.pts[0]);
+		}
+	}
+}
+
+/// Return the edges of the polygon, including the edge from the last back to
+/// the first point, as pairs of coordinates.
+fn edges(pts: &[Point]) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+	let coords = |pt: &Point| (pt.x.raw_value() as f64, pt.y.raw_value() as f64);
+	(0..pts.len()).map(move |i| (coords(&pts[i]), coords(&pts[(i + 1) % pts.len()])))
+}
+
+/// Twice the signed area of the polygon, computed with the shoelace formula.
+/// It is positive if the points are ordered counter-clockwise.
+fn shoelace(pts: &[Point]) -> f64 {
+	if pts.len() < 3 {
+		return 0.0;
+	}
+	edges(pts)
+		.map(|((x0, y0), (x1, y1))| x0 * y1 - x1 * y0)
+		.sum()
 }
 
 impl Deref for PointList {
@@ -33,6 +220,62 @@ impl DerefMut for PointList {
 	}
 }
 
+/// Join line segments into a closed ring by matching their endpoints. The
+/// first point is not repeated at the end of the ring. Returns `None` if the
+/// segments do not form a single closed ring.
+pub(crate) fn stitch(segments: &[(Point, Point)]) -> Option<PointList> {
+	let coincide = |a: Point, b: Point| a.distance(b) < 1.0.nm();
+
+	let (&(start, mut end), rest) = segments.split_first()?;
+	let mut remaining = rest.to_vec();
+	let mut pts = vec![start];
+	while !remaining.is_empty() {
+		let i = remaining
+			.iter()
+			.position(|(a, b)| coincide(*a, end) || coincide(*b, end))?;
+		let (a, b) = remaining.swap_remove(i);
+		pts.push(end);
+		end = if coincide(a, end) { b } else { a };
+	}
+	(pts.len() > 2 && coincide(start, end)).then(|| PointList::new(pts))
+}
+
+impl FromIterator<Point> for PointList {
+	fn from_iter<I>(iter: I) -> Self
+	where
+		I: IntoIterator<Item = Point>
+	{
+		Self::new(iter.into_iter().collect())
+	}
+}
+
+impl Extend<Point> for PointList {
+	fn extend<I>(&mut self, iter: I)
+	where
+		I: IntoIterator<Item = Point>
+	{
+		self.pts.extend(iter)
+	}
+}
+
+impl IntoIterator for PointList {
+	type Item = Point;
+	type IntoIter = vec::IntoIter<Point>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.pts.into_iter()
+	}
+}
+
+impl<'a> IntoIterator for &'a PointList {
+	type Item = &'a Point;
+	type IntoIter = slice::Iter<'a, Point>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.pts.iter()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -60,4 +303,205 @@ mod tests {
 			Point::new(2.54.mm(), 0.0.mm())
 		])
 	}
+
+	#[test]
+	fn cubic_bezier_polyline() {
+		let pts = PointList::new(vec![
+			Point::new(0.0.mm(), 0.0.mm()),
+			Point::new(1.0.mm(), 1.0.mm()),
+			Point::new(2.0.mm(), 2.0.mm()),
+			Point::new(3.0.mm(), 3.0.mm()),
+		]);
+		let line = pts.cubic_bezier_polyline(6);
+		assert_eq!(line.len(), 7);
+		assert_eq!(line[0], pts[0]);
+		assert_eq!(line[6], pts[3]);
+		for p in line.iter() {
+			assert!((p.x - p.y).abs() < 1.0.nm());
+		}
+
+		let line = pts.cubic_bezier_polyline(0);
+		assert_eq!(*line, vec![pts[0], pts[3]]);
+	}
+
+	fn assert_on_circle(pts: &PointList, center: Point, radius: f32) {
+		for pt in pts.iter() {
+			let distance = pt.distance(center).raw_value();
+			assert!((distance - radius).abs() < 1e-4, "{pt:?} is off the circle");
+		}
+	}
+
+	#[test]
+	fn arc_polyline() {
+		let center = Point::new(5.0.mm(), 5.0.mm());
+		let (r, diag) = (10.0, 10.0 * std::f32::consts::FRAC_1_SQRT_2);
+		let start = Point::new(15.0.mm(), 5.0.mm());
+		let mid = Point::new((5.0 + diag).mm(), (5.0 + diag).mm());
+		let end = Point::new(5.0.mm(), 15.0.mm());
+
+		let pts = PointList::arc_polyline(start, mid, end, 4);
+		assert_eq!(pts.len(), 5);
+		assert_eq!((pts[0], pts[4]), (start, end));
+		assert_on_circle(&pts, center, r);
+		assert!(pts[2].distance(mid) < 1e-3.mm());
+
+		// the other way round, the arc sweeps clockwise through the same points
+		let reversed = PointList::arc_polyline(end, mid, start, 4);
+		assert!(reversed[2].distance(mid) < 1e-3.mm());
+		assert!(reversed[1].distance(pts[3]) < 1e-3.mm());
+
+		assert_eq!(PointList::arc_polyline(start, mid, end, 0).pts, [
+			start, end
+		]);
+	}
+
+	#[test]
+	fn arc_polyline_full_circle() {
+		let start = Point::new(1.0.mm(), 0.0.mm());
+		let mid = Point::new((-1.0).mm(), 0.0.mm());
+		let pts = PointList::arc_polyline(start, mid, start, 4);
+		assert_eq!(pts.len(), 5);
+		assert_on_circle(&pts, Point::new(0.0.mm(), 0.0.mm()), 1.0);
+		assert!(pts[2].distance(mid) < 1e-3.mm());
+		assert_eq!(pts[4], start);
+	}
+
+	#[test]
+	fn arc_polyline_collinear() {
+		let start = Point::new(0.0.mm(), 0.0.mm());
+		let mid = Point::new(1.0.mm(), 0.0.mm());
+		let end = Point::new(2.0.mm(), 0.0.mm());
+		assert_eq!(PointList::arc_polyline(start, mid, end, 8).pts, [
+			start, end
+		]);
+	}
+
+	#[test]
+	fn area_of_unit_square() {
+		let mut square = PointList::new(vec![
+			Point::new(0.0.mm(), 0.0.mm()),
+			Point::new(1.0.mm(), 0.0.mm()),
+			Point::new(1.0.mm(), 1.0.mm()),
+			Point::new(0.0.mm(), 1.0.mm()),
+		]);
+		assert_eq!(square.area(), 1.0.mm());
+		assert_eq!(square.signed_area(), 1.0.mm());
+		assert_eq!(square.centroid(), Some(Point::new(0.5.mm(), 0.5.mm())));
+
+		square.reverse();
+		assert_eq!(square.area(), 1.0.mm());
+		assert_eq!(square.signed_area(), (-1.0).mm());
+		assert_eq!(square.centroid(), Some(Point::new(0.5.mm(), 0.5.mm())));
+	}
+
+	#[test]
+	fn area_of_triangle() {
+		let mut triangle = PointList::new(vec![
+			Point::new(0.0.mm(), 0.0.mm()),
+			Point::new(4.0.mm(), 0.0.mm()),
+			Point::new(0.0.mm(), 3.0.mm()),
+		]);
+		assert_eq!(triangle.area(), 6.0.mm());
+		let centroid = triangle.centroid().unwrap();
+		assert!(
+			centroid.distance(Point::new((4.0 / 3.0).mm(), 1.0.mm())) < 1.0.nm()
+		);
+
+		// closing the triangle does not change its area
+		triangle.pts.push(triangle.pts[0]);
+		assert_eq!(triangle.area(), 6.0.mm());
+	}
+
+	#[test]
+	fn area_of_degenerate_polygon() {
+		let line = PointList::new(vec![
+			Point::new(0.0.mm(), 0.0.mm()),
+			Point::new(1.0.mm(), 1.0.mm()),
+		]);
+		assert_eq!(line.area(), 0.0.mm());
+		assert_eq!(line.centroid(), None);
+		assert_eq!(PointList::empty().centroid(), None);
+	}
+
+	#[test]
+	fn dedup_consecutive() {
+		let a = Point::new(0.0.mm(), 0.0.mm());
+		let b = Point::new(1.0.mm(), 0.0.mm());
+		let mut pts = PointList::new(vec![a, b, b, a]);
+		pts.dedup_consecutive();
+		assert_eq!(pts.pts, [a, b, a]);
+	}
+
+	#[test]
+	fn canonicalize() {
+		let pts = |coords: &[(f32, f32)]| -> PointList {
+			coords
+				.iter()
+				.map(|&(x, y)| Point::new(x.mm(), y.mm()))
+				.collect()
+		};
+		let square = pts(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+
+		// clockwise, starting at a different corner, closed and with a repeated point
+		let mut reversed = pts(&[
+			(2.0, 2.0),
+			(2.0, 0.0),
+			(2.0, 0.0),
+			(0.0, 0.0),
+			(0.0, 2.0),
+			(2.0, 2.0)
+		]);
+		reversed.canonicalize(true);
+		assert!(reversed.closed());
+		assert_eq!(reversed[..4], square[..]);
+		assert_eq!(reversed.len(), 5);
+
+		// without normalizing the winding, only the duplicate is removed
+		let mut reversed = pts(&[(2.0, 2.0), (2.0, 0.0), (2.0, 0.0), (0.0, 0.0)]);
+		reversed.canonicalize(false);
+		assert_eq!(reversed, pts(&[(2.0, 2.0), (2.0, 0.0), (0.0, 0.0)]));
+	}
+
+	#[test]
+	fn build_closed_ring() {
+		let mut pts = PointList::empty();
+		assert!(pts.is_empty());
+		pts.push(Point::new(0.0.mm(), 0.0.mm()));
+		pts.push(Point::new(2.0.mm(), 2.0.mm()));
+		pts.insert(1, Point::new(2.0.mm(), 0.0.mm()));
+		assert_eq!(pts.len(), 3);
+		assert_eq!(pts[1], Point::new(2.0.mm(), 0.0.mm()));
+		assert!(!pts.closed());
+
+		let first = pts[0];
+		pts.push(first);
+		assert!(pts.closed());
+		assert_eq!(pts.remove(3), Point::new(0.0.mm(), 0.0.mm()));
+		assert!(!pts.closed());
+
+		let copy: PointList = pts.iter().copied().collect();
+		assert_eq!(copy, pts);
+		let xs: Vec<_> = pts.into_iter().map(|p| p.x).collect();
+		assert_eq!(xs, vec![0.0.mm(), 2.0.mm(), 2.0.mm()]);
+	}
+
+	#[test]
+	fn stitch_segments() {
+		let p = |x: f32, y: f32| Point::new(x.mm(), y.mm());
+		let square = [
+			(p(0.0, 0.0), p(1.0, 0.0)),
+			(p(0.0, 1.0), p(0.0, 0.0)),
+			(p(1.0, 1.0), p(0.0, 1.0)),
+			(p(1.0, 1.0), p(1.0, 0.0))
+		];
+		let ring = stitch(&square).unwrap();
+		assert_eq!(*ring, vec![
+			p(0.0, 0.0),
+			p(1.0, 0.0),
+			p(1.0, 1.0),
+			p(0.0, 1.0)
+		]);
+		assert_eq!(stitch(&square[..3]), None);
+		assert_eq!(stitch(&[]), None);
+	}
 }
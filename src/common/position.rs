@@ -1,11 +1,14 @@
-use crate::{deg, mm, Unit};
+use super::Point;
+use crate::{deg, internal::lenient_mm, mm, Unit};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "at")]
 pub struct Position {
+	#[serde(with = "lenient_mm")]
 	pub x: mm,
 
+	#[serde(with = "lenient_mm")]
 	pub y: mm,
 
 	#[serde(with = "serde_sexpr::Option")]
@@ -32,6 +35,17 @@ impl Position {
 			angle: Some(angle)
 		}
 	}
+
+	/// Return the position without its rotation.
+	pub fn point(&self) -> Point {
+		Point::new(self.x, self.y)
+	}
+}
+
+impl From<Point> for Position {
+	fn from(point: Point) -> Self {
+		Self::new(point.x, point.y)
+	}
 }
 
 #[cfg(test)]
@@ -56,4 +70,15 @@ mod tests {
 		input: "(at 1.27 -2.54 -90)",
 		value: Position::new_with_angle(1.27.mm(), -2.54.mm(), -90.0.deg())
 	}
+
+	#[test]
+	fn from_point() {
+		let point = Point::new(1.27.mm(), -2.54.mm());
+		let position = Position::from(point);
+		assert_eq!(position.point(), point);
+		assert_eq!(
+			serde_sexpr::to_string(&position).unwrap(),
+			"(at 1.27 -2.54)"
+		);
+	}
 }
@@ -0,0 +1,44 @@
+use super::PointList;
+use crate::deg;
+use serde::{Deserialize, Serialize};
+
+/// The outline of a text drawn with a font face, cached so that the text can be
+/// drawn without the font. Only written by KiCad 7 and later.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "render_cache")]
+pub struct RenderCache {
+	pub text: String,
+
+	pub angle: deg,
+
+	#[serde(default, rename = "")]
+	pub polygons: Vec<RenderPolygon>
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "polygon")]
+pub struct RenderPolygon {
+	pub pts: PointList
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{common::Point, sexpr_test_case, Deg, Unit};
+
+	sexpr_test_case! {
+		name: render_cache,
+		input: r#"(render_cache "H2" 0 (polygon (pts (xy 1 2) (xy 3 4) (xy 5 6))))"#,
+		value: RenderCache {
+			text: "H2".to_owned(),
+			angle: 0.0.deg(),
+			polygons: vec![RenderPolygon {
+				pts: PointList::new(vec![
+					Point::new(1.0.mm(), 2.0.mm()),
+					Point::new(3.0.mm(), 4.0.mm()),
+					Point::new(5.0.mm(), 6.0.mm())
+				])
+			}]
+		}
+	}
+}
@@ -1,10 +1,13 @@
-use crate::mm;
+use crate::{internal::lenient_mm, mm};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "size")]
 pub struct Size {
+	#[serde(with = "lenient_mm")]
 	pub height: mm,
+
+	#[serde(with = "lenient_mm")]
 	pub width: mm
 }
 
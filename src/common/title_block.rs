@@ -28,6 +28,12 @@ pub struct TitleBlock {
 	pub comments: Vec<TitleBlockComment>
 }
 
+impl Default for TitleBlock {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
 impl TitleBlock {
 	pub const fn new() -> Self {
 		Self {
@@ -70,6 +76,49 @@ impl TitleBlock {
 		self.company = Some(company.into());
 		self
 	}
+
+	pub fn with_comment<T>(mut self, n: u32, comment: T) -> Self
+	where
+		T: Into<String>
+	{
+		self.set_comment(n, comment);
+		self
+	}
+
+	/// Return the comment with index `n`, if any.
+	pub fn comment(&self, n: u32) -> Option<&str> {
+		self.comments
+			.iter()
+			.find(|comment| comment.n == n)
+			.map(|comment| comment.comment.as_str())
+	}
+
+	/// Set the comment with index `n`, replacing an existing one. Comments are
+	/// kept sorted by their index.
+	pub fn set_comment<T>(&mut self, n: u32, comment: T)
+	where
+		T: Into<String>
+	{
+		let comment = comment.into();
+		match self.comments.binary_search_by_key(&n, |comment| comment.n) {
+			Ok(i) => self.comments[i].comment = comment,
+			Err(i) => self.comments.insert(i, TitleBlockComment { n, comment })
+		}
+	}
+}
+
+#[cfg(feature = "chrono")]
+impl TitleBlock {
+	/// Return the date, if it is set and written as `YYYY-MM-DD` like KiCad does.
+	/// Free-form dates return `None`, but are kept as they are in [`Self::date`].
+	pub fn date_parsed(&self) -> Option<chrono::NaiveDate> {
+		self.date.as_deref()?.parse().ok()
+	}
+
+	/// Set the date, written as `YYYY-MM-DD`.
+	pub fn set_date(&mut self, date: chrono::NaiveDate) {
+		self.date = Some(date.to_string());
+	}
 }
 
 #[cfg(test)]
@@ -117,5 +166,48 @@ mod tests {
 			.with_company("Example GmbH")
 	}
 
-	// TODO add test cases with comments
+	sexpr_test_case! {
+		name: with_comments,
+		input: r#"(title_block (title "Cool Title") (comment 1 "First comment") (comment 2 "Second comment") (comment 4 "Fourth comment"))"#,
+		value: TitleBlock::new()
+			.with_title("Cool Title")
+			.with_comment(1, "First comment")
+			.with_comment(4, "Fourth comment")
+			.with_comment(2, "Second comment")
+	}
+
+	#[test]
+	fn comment_indices() {
+		let input = r#"(title_block (comment 1 "First comment") (comment 2 "Second comment") (comment 4 "Fourth comment"))"#;
+		let mut title_block: TitleBlock = serde_sexpr::from_str(input).unwrap();
+		assert_eq!(title_block.comment(2), Some("Second comment"));
+		assert_eq!(title_block.comment(3), None);
+		assert_eq!(title_block.comment(4), Some("Fourth comment"));
+
+		title_block.set_comment(2, "replaced");
+		title_block.set_comment(3, "third");
+		assert_eq!(title_block.comment(2), Some("replaced"));
+		let indices: Vec<u32> = title_block.comments.iter().map(|c| c.n).collect();
+		assert_eq!(indices, [1, 2, 3, 4]);
+	}
+
+	#[cfg(feature = "chrono")]
+	#[test]
+	fn date_parsed() {
+		let mut title_block = TitleBlock::new().with_date("2021-12-31");
+		let date = chrono::NaiveDate::from_ymd_opt(2021, 12, 31).unwrap();
+		assert_eq!(title_block.date_parsed(), Some(date));
+
+		title_block.set_date(date.succ_opt().unwrap());
+		assert_eq!(title_block.date.as_deref(), Some("2022-01-01"));
+	}
+
+	#[cfg(feature = "chrono")]
+	#[test]
+	fn free_form_date() {
+		let title_block = TitleBlock::new().with_date("Summer 2023");
+		assert_eq!(title_block.date_parsed(), None);
+		assert_eq!(title_block.date.as_deref(), Some("Summer 2023"));
+		assert_eq!(TitleBlock::new().date_parsed(), None);
+	}
 }
@@ -1,5 +1,5 @@
 #![allow(
-	clippy::derive_hash_xor_eq,
+	clippy::derived_hash_with_manual_eq,
 	clippy::derive_ord_xor_partial_ord,
 	clippy::manual_range_contains
 )]
@@ -0,0 +1,122 @@
+use serde::Deserialize;
+use serde_path_to_error::Segment;
+use std::fmt::{self, Display, Formatter};
+
+/// An error while parsing a KiCad file.
+#[derive(Debug)]
+pub enum Error {
+	/// A node of the file could not be parsed. The path names the s-expr nodes
+	/// from the root of the file down to the node or field that failed, as far
+	/// as it is known.
+	Parse {
+		path: Vec<String>,
+		source: serde_sexpr::de::Error
+	}
+}
+
+impl Error {
+	/// Return the path of s-expr nodes where parsing failed.
+	pub fn path(&self) -> &[String] {
+		match self {
+			Self::Parse { path, .. } => path
+		}
+	}
+
+	/// Prepend the name of the node that contains the failing node.
+	pub(crate) fn within(mut self, node: &str) -> Self {
+		match &mut self {
+			Self::Parse { path, .. } => path.insert(0, node.to_owned())
+		}
+		self
+	}
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Parse { path, source } => {
+				write!(f, "{source}")?;
+				for (i, node) in path.iter().enumerate() {
+					let sep = if i == 0 { " in" } else { " >" };
+					write!(f, "{sep} `{node}`")?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Parse { source, .. } => Some(source)
+		}
+	}
+}
+
+impl From<serde_sexpr::de::Error> for Error {
+	fn from(source: serde_sexpr::de::Error) -> Self {
+		Self::Parse {
+			path: Vec::new(),
+			source
+		}
+	}
+}
+
+/// Parse the s-expr `node`, recording the fields that were being parsed when an
+/// error occurs. The name of the node itself is not part of the path.
+pub(crate) fn from_node<'de, T>(node: &'de str) -> Result<T, Error>
+where
+	T: Deserialize<'de>
+{
+	let mut deserializer = serde_sexpr::de::Deserializer::from_str(node);
+	serde_path_to_error::deserialize(&mut deserializer).map_err(|err| {
+		let path = err
+			.path()
+			.iter()
+			.filter_map(|segment| match segment {
+				// content lists have no name and tuples are indexed by their
+				// position, neither helps to locate the field
+				Segment::Map { key } if !key.is_empty() => Some(key.clone()),
+				Segment::Enum { variant } => Some(variant.clone()),
+				_ => None
+			})
+			.collect();
+		Error::Parse {
+			path,
+			source: err.into_inner()
+		}
+	})
+}
+
+/// The required `field` of `node` is missing.
+pub(crate) fn missing_field(field: &'static str, node: &str) -> Error {
+	Error::from(<serde_sexpr::de::Error as serde::de::Error>::missing_field(
+		field
+	))
+	.within(node)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::board::pcb::General;
+
+	#[test]
+	fn path_to_field() {
+		let err = from_node::<General>("(general (thickness abc))")
+			.unwrap_err()
+			.within("general")
+			.within("kicad_pcb");
+		assert_eq!(err.path(), ["kicad_pcb", "general", "thickness"]);
+		assert!(err
+			.to_string()
+			.ends_with(" in `kicad_pcb` > `general` > `thickness`"));
+	}
+
+	#[test]
+	fn without_path() {
+		let source = <serde_sexpr::de::Error as serde::de::Error>::custom("oops");
+		assert_eq!(Error::from(source).to_string(), "oops");
+	}
+}
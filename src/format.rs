@@ -0,0 +1,300 @@
+//! Formatting of numbers the way KiCad writes them.
+//!
+//! KiCad writes lengths with at most [`KICAD_DECIMALS`] decimals, trims trailing
+//! zeros and never uses scientific notation. Coordinates, sizes and track and
+//! stroke widths are serialized that way by default, so computed values like
+//! `1.0 / 3.0` are written as `0.333333`, see [`format_mm`].
+//!
+//! The [`mm`] type is defined by the `millimeter` crate, so other lengths are
+//! written by its own `Serialize` implementation, which uses the shortest
+//! representation. Also note that lengths are stored as [`f32`], so values with
+//! more than 7 significant digits like `167.992063` lose their last digits.
+//!
+//! To match the exact output of KiCad, e.g. for clean diffs, whole files can be
+//! written with [`ToSexpr::to_string_with`] and [`SerializeOptions`]. This also
+//! writes the `(uuid ..)` nodes of newer files, see [`UuidName`].
+
+use crate::mm;
+use serde::Serialize;
+
+/// The number of decimals KiCad writes for lengths.
+pub const KICAD_DECIMALS: usize = 6;
+
+/// Format `value` with at most `decimals` decimals, trimming trailing zeros.
+/// Negative zero is written as `0`.
+pub fn format_float(value: f32, decimals: usize) -> String {
+	// prefer the shortest representation, rounding it would introduce the error
+	// of the binary representation, e.g. 152.89 would become 152.889999
+	let mut out = value.to_string();
+	let len = out.find('.').map(|idx| out.len() - idx - 1).unwrap_or(0);
+	if len > decimals {
+		out = format!("{value:.decimals$}");
+	}
+	if out.contains('.') {
+		let len = out.trim_end_matches('0').trim_end_matches('.').len();
+		out.truncate(len);
+	}
+	if out == "-0" {
+		out.remove(0);
+	}
+	out
+}
+
+/// Format a length exactly like KiCad does.
+pub fn format_mm(value: mm) -> String {
+	format_float(value.raw_value(), KICAD_DECIMALS)
+}
+
+/// The line ending used by [`ToSexpr::to_string_with`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LineEnding {
+	/// `\n`, which is what KiCad writes on all platforms.
+	#[default]
+	Lf,
+	/// `\r\n`.
+	CrLf
+}
+
+impl LineEnding {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Lf => "\n",
+			Self::CrLf => "\r\n"
+		}
+	}
+}
+
+/// How [`ToSexpr::to_string_with`] writes numbers with decimals. Numbers are
+/// always written with a `.` as decimal separator, independent of the locale.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum FloatFormat {
+	/// The shortest representation that is parsed back to the same value.
+	#[default]
+	Shortest,
+	/// At most the given number of decimals, like [`format_float`]. Use
+	/// [`KICAD_DECIMALS`] to round like KiCad.
+	Decimals(usize)
+}
+
+/// The name of the node holding the unique identifier of an item, written by
+/// [`ToSexpr::to_string_with`]. Both names are accepted when parsing.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum UuidName {
+	/// `(tstamp ..)`, which is what KiCad 7 and earlier write.
+	#[default]
+	Tstamp,
+	/// `(uuid ..)`, which is what KiCad 8 and later write.
+	Uuid
+}
+
+impl UuidName {
+	/// The first board file version that names the identifier `uuid`.
+	pub const UUID_VERSION: u32 = 20231007;
+
+	/// Return the name used by the given board file version.
+	pub fn for_version(version: u32) -> Self {
+		if version >= Self::UUID_VERSION {
+			Self::Uuid
+		} else {
+			Self::Tstamp
+		}
+	}
+}
+
+/// Options for [`ToSexpr::to_string_with`]. The default writes `\n` line
+/// endings, the shortest representation of numbers and `(tstamp ..)` nodes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SerializeOptions {
+	pub line_ending: LineEnding,
+	pub float_format: FloatFormat,
+	pub uuid_name: UuidName
+}
+
+/// Write values as pretty printed s-expressions, the layout used for files.
+pub trait ToSexpr {
+	fn to_string_with(
+		&self,
+		opts: &SerializeOptions
+	) -> Result<String, serde_sexpr::ser::Error>;
+}
+
+impl<T> ToSexpr for T
+where
+	T: ?Sized + Serialize
+{
+	fn to_string_with(
+		&self,
+		opts: &SerializeOptions
+	) -> Result<String, serde_sexpr::ser::Error> {
+		let output = serde_sexpr::to_string_pretty(self)?;
+		if *opts == SerializeOptions::default() {
+			return Ok(output);
+		}
+		Ok(reformat(&output, opts))
+	}
+}
+
+/// Apply the options to the output of the serializer. Quoted strings are
+/// copied unchanged, so a line break inside of a string is kept as it is.
+fn reformat(input: &str, opts: &SerializeOptions) -> String {
+	let mut out = String::with_capacity(input.len());
+	let mut atom = String::new();
+	let mut chars = input.chars();
+	while let Some(ch) = chars.next() {
+		match ch {
+			' ' | '(' | ')' | '\n' | '"' => {
+				write_atom(&mut out, &atom, opts);
+				atom.clear();
+			},
+			_ => {
+				atom.push(ch);
+				continue;
+			}
+		}
+		match ch {
+			'\n' => out += opts.line_ending.as_str(),
+			'"' => {
+				out.push('"');
+				while let Some(ch) = chars.next() {
+					out.push(ch);
+					match ch {
+						'\\' => out.extend(chars.next()),
+						'"' => break,
+						_ => {}
+					}
+				}
+			},
+			ch => out.push(ch)
+		}
+	}
+	write_atom(&mut out, &atom, opts);
+	out
+}
+
+fn write_atom(out: &mut String, atom: &str, opts: &SerializeOptions) {
+	// only the name of a node is renamed, not a value that happens to match
+	if atom == "tstamp" && opts.uuid_name == UuidName::Uuid && out.ends_with('(') {
+		*out += "uuid";
+		return;
+	}
+	let value = match opts.float_format {
+		FloatFormat::Decimals(decimals) if atom.contains('.') => {
+			atom.parse().ok().map(|value| format_float(value, decimals))
+		},
+		_ => None
+	};
+	*out += value.as_deref().unwrap_or(atom);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{board::graphic::Segment, pcb::PCB, Unit};
+	use std::fs;
+
+	#[test]
+	fn trims_trailing_zeros() {
+		assert_eq!(format_mm(0.1.mm()), "0.1");
+		assert_eq!(format_mm(1.0.mm()), "1");
+		assert_eq!(format_mm(0.127.mm()), "0.127");
+		assert_eq!(format_mm(100.0.mm()), "100");
+	}
+
+	#[test]
+	fn rounds_to_kicad_precision() {
+		assert_eq!(format_mm((1.0 / 3.0).mm()), "0.333333");
+		assert_eq!(format_mm(0.0000001.mm()), "0");
+		assert_eq!(format_mm(-0.0000001.mm()), "0");
+		assert_eq!(format_mm(1e10.mm()), "10000000000");
+		assert_eq!(format_mm(-2.54.mm()), "-2.54");
+	}
+
+	/// Every coordinate in a file written by KiCad must be formatted identically,
+	/// unless it has more significant digits than an [`f32`] can hold.
+	#[test]
+	fn matches_kicad_output() {
+		let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/minnow.kicad_pcb");
+		let input = fs::read_to_string(path).unwrap();
+		let mut checked = 0;
+		for prefix in ["(at ", "(xy ", "(start ", "(end ", "(width ", "(size "] {
+			for (idx, _) in input.match_indices(prefix) {
+				let rest = &input[idx + prefix.len()..];
+				let values = &rest[..rest.find(')').unwrap()];
+				for token in values.split_whitespace() {
+					let Ok(value) = token.parse::<f32>() else {
+						continue;
+					};
+					let digits =
+						token.trim_start_matches(['-', '0', '.']).replace('.', "");
+					if digits.len() > 7 {
+						continue;
+					}
+					assert_eq!(format_float(value, KICAD_DECIMALS), token);
+					checked += 1;
+				}
+			}
+		}
+		assert!(checked > 100);
+	}
+
+	fn board() -> PCB {
+		let mut pcb = PCB::builder()
+			.version(20221018)
+			.title("Line 1\nLine 2")
+			.add_net(0, "")
+			.build();
+		pcb.general.thickness = (1.0 / 3.0).mm();
+		pcb
+	}
+
+	#[test]
+	fn line_endings() {
+		let pcb = board();
+		let lf = pcb.to_string_with(&SerializeOptions::default()).unwrap();
+		assert_eq!(lf, serde_sexpr::to_string_pretty(&pcb).unwrap());
+		assert!(lf.contains("(general\n    (thickness 0.33333334))"));
+		assert!(!lf.contains('\r'));
+
+		let crlf = pcb
+			.to_string_with(&SerializeOptions {
+				line_ending: LineEnding::CrLf,
+				..Default::default()
+			})
+			.unwrap();
+		assert!(crlf.contains("(general\r\n    (thickness 0.33333334))"));
+		// line breaks inside of strings are not changed
+		assert!(crlf.contains("\"Line 1\nLine 2\""));
+		assert_eq!(crlf.replace("\r\n", "\n"), lf);
+		assert_eq!(crlf.parse::<PCB>().unwrap(), pcb);
+	}
+
+	#[test]
+	fn float_decimals() {
+		let output = board()
+			.to_string_with(&SerializeOptions {
+				float_format: FloatFormat::Decimals(KICAD_DECIMALS),
+				..Default::default()
+			})
+			.unwrap();
+		assert!(output.contains("(thickness 0.333333)"));
+		assert!(output.contains("(version 20221018)"));
+	}
+
+	#[test]
+	fn uuid_name() {
+		let input = r#"(segment (start 1 0) (end 2 0) (width 0.12) (layer "F.Cu") (net 1) (tstamp "00000000-0000-0000-0000-000000000001"))"#;
+		let segment: Segment = serde_sexpr::from_str(input).unwrap();
+		let opts = SerializeOptions {
+			uuid_name: UuidName::for_version(20240108),
+			..Default::default()
+		};
+		let output = segment.to_string_with(&opts).unwrap();
+		assert!(output.contains("(uuid \"00000000-0000-0000-0000-000000000001\")"));
+		assert!(!output.contains("tstamp"));
+		assert_eq!(serde_sexpr::from_str::<Segment>(&output).unwrap(), segment);
+
+		assert_eq!(UuidName::for_version(20221018), UuidName::Tstamp);
+		let output = segment.to_string_with(&SerializeOptions::default()).unwrap();
+		assert!(output.contains("(tstamp "));
+	}
+}
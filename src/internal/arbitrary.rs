@@ -0,0 +1,26 @@
+//! Helpers to generate values for [`Arbitrary`](arbitrary::Arbitrary)
+//! implementations that can be written as s-expr and parsed back unchanged.
+
+use crate::{board::Layer, common::Point, mm, Unit};
+use arbitrary::{Result, Unstructured};
+use uuid::Uuid;
+
+const COPPER_LAYERS: &[&str] = &["F.Cu", "In1.Cu", "In2.Cu", "B.Cu"];
+
+/// A length in whole micrometres, within one metre of the origin.
+pub(crate) fn mm(u: &mut Unstructured<'_>) -> Result<mm> {
+	let um: i32 = u.int_in_range(-1_000_000..=1_000_000)?;
+	Ok((um as f32 / 1000.0).mm())
+}
+
+pub(crate) fn point(u: &mut Unstructured<'_>) -> Result<Point> {
+	Ok(Point::new(mm(u)?, mm(u)?))
+}
+
+pub(crate) fn copper_layer(u: &mut Unstructured<'_>) -> Result<Layer> {
+	Ok(Layer::new(*u.choose(COPPER_LAYERS)?))
+}
+
+pub(crate) fn uuid(u: &mut Unstructured<'_>) -> Result<Uuid> {
+	Ok(Uuid::from_bytes(u.arbitrary()?))
+}
@@ -0,0 +1,69 @@
+//! Standard base64 encoding with padding, used for embedded images.
+
+const ALPHABET: &[u8; 64] =
+	b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub(crate) fn encode(bytes: &[u8]) -> String {
+	let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+	for chunk in bytes.chunks(3) {
+		let b = [
+			chunk[0],
+			chunk.get(1).copied().unwrap_or(0),
+			chunk.get(2).copied().unwrap_or(0)
+		];
+		let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+		for i in 0..4 {
+			if i <= chunk.len() {
+				out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+			} else {
+				out.push('=');
+			}
+		}
+	}
+	out
+}
+
+/// Decode base64 data. Characters outside of the alphabet, like whitespace and
+/// padding, are ignored.
+pub(crate) fn decode(data: &str) -> Vec<u8> {
+	let mut out = Vec::with_capacity(data.len() / 4 * 3);
+	let mut buf = 0_u32;
+	let mut bits = 0;
+	for ch in data.bytes() {
+		let Some(value) = ALPHABET.iter().position(|c| *c == ch) else {
+			continue;
+		};
+		buf = buf << 6 | value as u32;
+		bits += 6;
+		if bits >= 8 {
+			bits -= 8;
+			out.push((buf >> bits) as u8);
+			buf &= (1 << bits) - 1;
+		}
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn round_trip() {
+		for (bytes, encoded) in [
+			(&b""[..], ""),
+			(b"f", "Zg=="),
+			(b"fo", "Zm8="),
+			(b"foo", "Zm9v"),
+			(b"foobar", "Zm9vYmFy")
+		] {
+			assert_eq!(encode(bytes), encoded);
+			assert_eq!(decode(encoded), bytes);
+		}
+	}
+
+	#[test]
+	fn decode_ignores_whitespace() {
+		assert_eq!(decode("Zm9v\nYmFy"), b"foobar");
+	}
+}
@@ -0,0 +1,1045 @@
+//! A JSON (de)serializer for the types of this crate.
+//!
+//! The s-expression format relies on a few conventions that do not translate
+//! well to JSON: Values are wrapped in single-element tuples, untagged enums are
+//! told apart by the name of the s-expr and the last field of a struct might have
+//! an empty name. This module works on [`serde_json::Value`]s and turns these
+//! conventions into plain JSON:
+//!
+//!  - Single-element tuples are written as their only element.
+//!  - Structs and named newtypes inside of sequences carry their s-expr name in a
+//!    `$kind` key, which is used to select the variant of untagged enums.
+//!  - Struct fields with an empty name are written as `children`.
+//!  - Unit values, i.e. present valueless tokens like `(locked)`, are written as
+//!    `true`.
+
+use serde::{
+	de::{
+		self, value::BorrowedStrDeserializer, DeserializeSeed, EnumAccess,
+		IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor
+	},
+	forward_to_deserialize_any,
+	ser::{self, Impossible, Serialize},
+	Deserialize
+};
+use serde_json::{Error, Map, Number, Value};
+
+const TAG: &str = "$kind";
+const VALUE: &str = "value";
+const CHILDREN: &str = "children";
+
+pub(crate) fn to_value<T>(value: &T) -> Result<Value, Error>
+where
+	T: ?Sized + Serialize
+{
+	value.serialize(Serializer { element: false })
+}
+
+pub(crate) fn from_value<'de, T>(value: &'de Value) -> Result<T, Error>
+where
+	T: Deserialize<'de>
+{
+	T::deserialize(Deserializer { value })
+}
+
+fn field_name(name: &str) -> &str {
+	match name {
+		"" => CHILDREN,
+		name => name
+	}
+}
+
+fn float(v: f64) -> Value {
+	Number::from_f64(v)
+		.map(Value::Number)
+		.unwrap_or(Value::Null)
+}
+
+struct Serializer {
+	/// Whether the value is an element of a sequence and might therefore be the
+	/// variant of an untagged enum.
+	element: bool
+}
+
+struct SerializeVec {
+	element: bool,
+	vec: Vec<Value>,
+	/// Whether a single element should be written without the array.
+	flatten: bool,
+	/// Whether null elements should be skipped.
+	skip_null: bool
+}
+
+struct SerializeObject {
+	map: Map<String, Value>,
+	next_key: Option<String>
+}
+
+struct SerializeVariant {
+	variant: &'static str,
+	inner: SerializeVecOrObject
+}
+
+enum SerializeVecOrObject {
+	Vec(SerializeVec),
+	Object(SerializeObject)
+}
+
+impl SerializeVec {
+	fn end(self) -> Value {
+		match (self.flatten, self.vec.len()) {
+			(true, 1) => self.vec.into_iter().next().unwrap(),
+			_ => Value::Array(self.vec)
+		}
+	}
+
+	fn push<T>(&mut self, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize
+	{
+		let value = value.serialize(Serializer {
+			element: self.element
+		})?;
+		if !(self.skip_null && value.is_null()) {
+			self.vec.push(value);
+		}
+		Ok(())
+	}
+}
+
+impl SerializeObject {
+	fn new(tag: Option<&str>) -> Self {
+		let mut map = Map::new();
+		if let Some(tag) = tag {
+			map.insert(TAG.to_owned(), Value::String(tag.to_owned()));
+		}
+		Self {
+			map,
+			next_key: None
+		}
+	}
+
+	fn insert<T>(&mut self, key: &str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize
+	{
+		let value = to_value(value)?;
+		self.map.insert(field_name(key).to_owned(), value);
+		Ok(())
+	}
+}
+
+impl SerializeVariant {
+	fn end(self) -> Value {
+		let value = match self.inner {
+			SerializeVecOrObject::Vec(vec) => vec.end(),
+			SerializeVecOrObject::Object(object) => Value::Object(object.map)
+		};
+		let mut map = Map::new();
+		map.insert(self.variant.to_owned(), value);
+		Value::Object(map)
+	}
+}
+
+impl ser::Serializer for Serializer {
+	type Ok = Value;
+	type Error = Error;
+
+	type SerializeSeq = SerializeVec;
+	type SerializeTuple = SerializeVec;
+	type SerializeTupleStruct = SerializeVec;
+	type SerializeTupleVariant = SerializeVariant;
+	type SerializeMap = SerializeObject;
+	type SerializeStruct = SerializeObject;
+	type SerializeStructVariant = SerializeVariant;
+
+	fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+		Ok(Value::Bool(v))
+	}
+
+	fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+		Ok(v.into())
+	}
+
+	fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+		Ok(v.into())
+	}
+
+	fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+		Ok(v.into())
+	}
+
+	fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+		Ok(v.into())
+	}
+
+	fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+		Ok(v.into())
+	}
+
+	fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+		Ok(v.into())
+	}
+
+	fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+		Ok(v.into())
+	}
+
+	fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+		Ok(v.into())
+	}
+
+	fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+		// go through the shortest decimal representation of the f32 so that 0.1
+		// doesn't turn into 0.10000000149011612
+		Ok(float(v.to_string().parse().unwrap_or(v as f64)))
+	}
+
+	fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+		Ok(float(v))
+	}
+
+	fn serialize_char(self, v: char) -> Result<Value, Error> {
+		Ok(Value::String(v.to_string()))
+	}
+
+	fn serialize_str(self, v: &str) -> Result<Value, Error> {
+		Ok(Value::String(v.to_owned()))
+	}
+
+	fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+		Ok(Value::Array(v.iter().map(|b| (*b).into()).collect()))
+	}
+
+	fn serialize_none(self) -> Result<Value, Error> {
+		Ok(Value::Null)
+	}
+
+	fn serialize_some<T>(self, value: &T) -> Result<Value, Error>
+	where
+		T: ?Sized + Serialize
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_unit(self) -> Result<Value, Error> {
+		Ok(Value::Bool(true))
+	}
+
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+		Ok(Value::Bool(true))
+	}
+
+	fn serialize_unit_variant(
+		self,
+		_name: &'static str,
+		_idx: u32,
+		variant: &'static str
+	) -> Result<Value, Error> {
+		Ok(Value::String(variant.to_owned()))
+	}
+
+	fn serialize_newtype_struct<T>(
+		self,
+		name: &'static str,
+		value: &T
+	) -> Result<Value, Error>
+	where
+		T: ?Sized + Serialize
+	{
+		let value = to_value(value)?;
+		if !self.element || name.is_empty() {
+			return Ok(value);
+		}
+		let mut map = Map::new();
+		map.insert(TAG.to_owned(), Value::String(name.to_owned()));
+		map.insert(VALUE.to_owned(), value);
+		Ok(Value::Object(map))
+	}
+
+	fn serialize_newtype_variant<T>(
+		self,
+		_name: &'static str,
+		_idx: u32,
+		variant: &'static str,
+		value: &T
+	) -> Result<Value, Error>
+	where
+		T: ?Sized + Serialize
+	{
+		let mut map = Map::new();
+		map.insert(variant.to_owned(), to_value(value)?);
+		Ok(Value::Object(map))
+	}
+
+	fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVec, Error> {
+		Ok(SerializeVec {
+			element: true,
+			vec: Vec::with_capacity(len.unwrap_or_default()),
+			flatten: false,
+			skip_null: false
+		})
+	}
+
+	fn serialize_tuple(self, len: usize) -> Result<SerializeVec, Error> {
+		Ok(SerializeVec {
+			element: self.element && len == 1,
+			vec: Vec::with_capacity(len),
+			flatten: len == 1,
+			skip_null: false
+		})
+	}
+
+	fn serialize_tuple_struct(
+		self,
+		_name: &'static str,
+		len: usize
+	) -> Result<SerializeVec, Error> {
+		// missing values of tuple structs are skipped in s-exprs
+		Ok(SerializeVec {
+			element: false,
+			vec: Vec::with_capacity(len),
+			flatten: len == 1,
+			skip_null: true
+		})
+	}
+
+	fn serialize_tuple_variant(
+		self,
+		_name: &'static str,
+		_idx: u32,
+		variant: &'static str,
+		len: usize
+	) -> Result<SerializeVariant, Error> {
+		Ok(SerializeVariant {
+			variant,
+			inner: SerializeVecOrObject::Vec(SerializeVec {
+				element: false,
+				vec: Vec::with_capacity(len),
+				flatten: false,
+				skip_null: false
+			})
+		})
+	}
+
+	fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject, Error> {
+		Ok(SerializeObject::new(None))
+	}
+
+	fn serialize_struct(
+		self,
+		name: &'static str,
+		_len: usize
+	) -> Result<SerializeObject, Error> {
+		let tag = (self.element && !name.is_empty()).then_some(name);
+		Ok(SerializeObject::new(tag))
+	}
+
+	fn serialize_struct_variant(
+		self,
+		_name: &'static str,
+		_idx: u32,
+		variant: &'static str,
+		_len: usize
+	) -> Result<SerializeVariant, Error> {
+		Ok(SerializeVariant {
+			variant,
+			inner: SerializeVecOrObject::Object(SerializeObject::new(None))
+		})
+	}
+}
+
+impl ser::SerializeSeq for SerializeVec {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize
+	{
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(SerializeVec::end(self))
+	}
+}
+
+impl ser::SerializeTuple for SerializeVec {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize
+	{
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(SerializeVec::end(self))
+	}
+}
+
+impl ser::SerializeTupleStruct for SerializeVec {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize
+	{
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(SerializeVec::end(self))
+	}
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize
+	{
+		match &mut self.inner {
+			SerializeVecOrObject::Vec(vec) => vec.push(value),
+			SerializeVecOrObject::Object(_) => unreachable!()
+		}
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(SerializeVariant::end(self))
+	}
+}
+
+impl ser::SerializeMap for SerializeObject {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize
+	{
+		self.next_key = Some(key.serialize(KeySerializer)?);
+		Ok(())
+	}
+
+	fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize
+	{
+		let key = self.next_key.take().ok_or_else(|| {
+			ser::Error::custom("serialize_value called before key")
+		})?;
+		self.map.insert(key, to_value(value)?);
+		Ok(())
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(Value::Object(self.map))
+	}
+}
+
+impl ser::SerializeStruct for SerializeObject {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_field<T>(
+		&mut self,
+		key: &'static str,
+		value: &T
+	) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize
+	{
+		self.insert(key, value)
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(Value::Object(self.map))
+	}
+}
+
+impl ser::SerializeStructVariant for SerializeVariant {
+	type Ok = Value;
+	type Error = Error;
+
+	fn serialize_field<T>(
+		&mut self,
+		key: &'static str,
+		value: &T
+	) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize
+	{
+		match &mut self.inner {
+			SerializeVecOrObject::Object(object) => object.insert(key, value),
+			SerializeVecOrObject::Vec(_) => unreachable!()
+		}
+	}
+
+	fn end(self) -> Result<Value, Error> {
+		Ok(SerializeVariant::end(self))
+	}
+}
+
+/// Serializes map keys, which have to be strings in JSON.
+struct KeySerializer;
+
+macro_rules! serialize_key_display {
+	($($method:ident($ty:ty);)+) => {
+		$(
+			fn $method(self, v: $ty) -> Result<String, Error> {
+				Ok(v.to_string())
+			}
+		)+
+	};
+}
+
+impl ser::Serializer for KeySerializer {
+	type Ok = String;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<String, Error>;
+	type SerializeTuple = Impossible<String, Error>;
+	type SerializeTupleStruct = Impossible<String, Error>;
+	type SerializeTupleVariant = Impossible<String, Error>;
+	type SerializeMap = Impossible<String, Error>;
+	type SerializeStruct = Impossible<String, Error>;
+	type SerializeStructVariant = Impossible<String, Error>;
+
+	serialize_key_display! {
+		serialize_bool(bool);
+		serialize_i8(i8);
+		serialize_i16(i16);
+		serialize_i32(i32);
+		serialize_i64(i64);
+		serialize_u8(u8);
+		serialize_u16(u16);
+		serialize_u32(u32);
+		serialize_u64(u64);
+		serialize_f32(f32);
+		serialize_f64(f64);
+		serialize_char(char);
+		serialize_str(&str);
+	}
+
+	fn serialize_bytes(self, _v: &[u8]) -> Result<String, Error> {
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_none(self) -> Result<String, Error> {
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_some<T>(self, value: &T) -> Result<String, Error>
+	where
+		T: ?Sized + Serialize
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_unit(self) -> Result<String, Error> {
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<String, Error> {
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_unit_variant(
+		self,
+		_name: &'static str,
+		_idx: u32,
+		variant: &'static str
+	) -> Result<String, Error> {
+		Ok(variant.to_owned())
+	}
+
+	fn serialize_newtype_struct<T>(
+		self,
+		_name: &'static str,
+		value: &T
+	) -> Result<String, Error>
+	where
+		T: ?Sized + Serialize
+	{
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_variant<T>(
+		self,
+		_name: &'static str,
+		_idx: u32,
+		_variant: &'static str,
+		_value: &T
+	) -> Result<String, Error>
+	where
+		T: ?Sized + Serialize
+	{
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_seq(
+		self,
+		_len: Option<usize>
+	) -> Result<Self::SerializeSeq, Error> {
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_tuple_struct(
+		self,
+		_name: &'static str,
+		_len: usize
+	) -> Result<Self::SerializeTupleStruct, Error> {
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_tuple_variant(
+		self,
+		_name: &'static str,
+		_idx: u32,
+		_variant: &'static str,
+		_len: usize
+	) -> Result<Self::SerializeTupleVariant, Error> {
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_map(
+		self,
+		_len: Option<usize>
+	) -> Result<Self::SerializeMap, Error> {
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize
+	) -> Result<Self::SerializeStruct, Error> {
+		Err(key_must_be_a_string())
+	}
+
+	fn serialize_struct_variant(
+		self,
+		_name: &'static str,
+		_idx: u32,
+		_variant: &'static str,
+		_len: usize
+	) -> Result<Self::SerializeStructVariant, Error> {
+		Err(key_must_be_a_string())
+	}
+}
+
+fn key_must_be_a_string() -> Error {
+	ser::Error::custom("key must be a string")
+}
+
+struct Deserializer<'de> {
+	value: &'de Value
+}
+
+impl<'de> Deserializer<'de> {
+	fn invalid_type(&self, exp: &dyn de::Expected) -> Error {
+		let unexp = match self.value {
+			Value::Null => de::Unexpected::Unit,
+			Value::Bool(b) => de::Unexpected::Bool(*b),
+			Value::Number(_) => de::Unexpected::Other("number"),
+			Value::String(s) => de::Unexpected::Str(s),
+			Value::Array(_) => de::Unexpected::Seq,
+			Value::Object(_) => de::Unexpected::Map
+		};
+		de::Error::invalid_type(unexp, exp)
+	}
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+	type Error = Error;
+
+	fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		match self.value {
+			// don't call the visitor so that `serde_sexpr::Option` treats this as
+			// a missing value
+			Value::Null => Err(self.invalid_type(&visitor)),
+			Value::Bool(b) => visitor.visit_bool(*b),
+			Value::Number(n) => {
+				if let Some(n) = n.as_u64() {
+					visitor.visit_u64(n)
+				} else if let Some(n) = n.as_i64() {
+					visitor.visit_i64(n)
+				} else {
+					visitor.visit_f64(n.as_f64().unwrap_or_default())
+				}
+			},
+			Value::String(s) => visitor.visit_borrowed_str(s),
+			Value::Array(vec) => visitor.visit_seq(SeqDeserializer(vec.iter())),
+			Value::Object(map) => visitor.visit_map(MapDeserializer::new(map))
+		}
+	}
+
+	fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		match self.value {
+			Value::Null => visitor.visit_none(),
+			_ => visitor.visit_some(self)
+		}
+	}
+
+	fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		match self.value {
+			Value::Bool(true) => visitor.visit_unit(),
+			_ => Err(self.invalid_type(&visitor))
+		}
+	}
+
+	fn deserialize_unit_struct<V>(
+		self,
+		_name: &'static str,
+		visitor: V
+	) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		self.deserialize_unit(visitor)
+	}
+
+	fn deserialize_newtype_struct<V>(
+		self,
+		name: &'static str,
+		visitor: V
+	) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		if self.value.is_null() {
+			return Err(self.invalid_type(&visitor));
+		}
+		if let Value::Object(map) = self.value {
+			if map.get(TAG).and_then(Value::as_str) == Some(name) {
+				if let Some(value) = map.get(VALUE) {
+					return visitor.visit_newtype_struct(Deserializer { value });
+				}
+			}
+		}
+		visitor.visit_newtype_struct(self)
+	}
+
+	fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		match self.value {
+			Value::Array(vec) => visitor.visit_seq(SeqDeserializer(vec.iter())),
+			_ => Err(self.invalid_type(&visitor))
+		}
+	}
+
+	fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		if self.value.is_null() {
+			return Err(self.invalid_type(&visitor));
+		}
+		match len {
+			1 => visitor
+				.visit_seq(SeqDeserializer(std::slice::from_ref(self.value).iter())),
+			_ => self.deserialize_seq(visitor)
+		}
+	}
+
+	fn deserialize_tuple_struct<V>(
+		self,
+		_name: &'static str,
+		len: usize,
+		visitor: V
+	) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		self.deserialize_tuple(len, visitor)
+	}
+
+	fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		match self.value {
+			Value::Object(map) => visitor.visit_map(MapDeserializer::new(map)),
+			_ => Err(self.invalid_type(&visitor))
+		}
+	}
+
+	fn deserialize_struct<V>(
+		self,
+		_name: &'static str,
+		_fields: &'static [&'static str],
+		visitor: V
+	) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		self.deserialize_map(visitor)
+	}
+
+	fn deserialize_enum<V>(
+		self,
+		_name: &'static str,
+		_variants: &'static [&'static str],
+		visitor: V
+	) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		match self.value {
+			Value::String(variant) => visitor.visit_enum(EnumDeserializer {
+				variant,
+				value: None
+			}),
+			Value::Object(map) => {
+				if let Some(Value::String(variant)) = map.get(TAG) {
+					return visitor.visit_enum(EnumDeserializer {
+						variant,
+						value: Some(self.value)
+					});
+				}
+				let mut iter = map.iter();
+				match (iter.next(), iter.next()) {
+					(Some((variant, value)), None) => {
+						visitor.visit_enum(EnumDeserializer {
+							variant,
+							value: Some(value)
+						})
+					},
+					_ => Err(self.invalid_type(&visitor))
+				}
+			},
+			_ => Err(self.invalid_type(&visitor))
+		}
+	}
+
+	fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		visitor.visit_unit()
+	}
+
+	forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+		bytes byte_buf identifier
+	}
+}
+
+struct SeqDeserializer<'de>(std::slice::Iter<'de, Value>);
+
+impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
+	type Error = Error;
+
+	fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+	where
+		T: DeserializeSeed<'de>
+	{
+		self.0
+			.next()
+			.map(|value| seed.deserialize(Deserializer { value }))
+			.transpose()
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.0.len())
+	}
+}
+
+struct MapDeserializer<'de> {
+	iter: serde_json::map::Iter<'de>,
+	value: Option<&'de Value>
+}
+
+impl<'de> MapDeserializer<'de> {
+	fn new(map: &'de Map<String, Value>) -> Self {
+		Self {
+			iter: map.iter(),
+			value: None
+		}
+	}
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer<'de> {
+	type Error = Error;
+
+	fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+	where
+		K: DeserializeSeed<'de>
+	{
+		for (key, value) in self.iter.by_ref() {
+			let key = match key.as_str() {
+				TAG => continue,
+				CHILDREN => "",
+				key => key
+			};
+			self.value = Some(value);
+			return seed
+				.deserialize(BorrowedStrDeserializer::<Error>::new(key))
+				.map(Some);
+		}
+		Ok(None)
+	}
+
+	fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+	where
+		V: DeserializeSeed<'de>
+	{
+		let value = self
+			.value
+			.take()
+			.ok_or_else(|| de::Error::custom("next_value called before next_key"))?;
+		seed.deserialize(Deserializer { value })
+	}
+}
+
+struct EnumDeserializer<'de> {
+	variant: &'de str,
+	value: Option<&'de Value>
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+	type Error = Error;
+	type Variant = VariantDeserializer<'de>;
+
+	fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Error>
+	where
+		V: DeserializeSeed<'de>
+	{
+		let variant = seed.deserialize(self.variant.into_deserializer())?;
+		Ok((variant, VariantDeserializer { value: self.value }))
+	}
+}
+
+struct VariantDeserializer<'de> {
+	value: Option<&'de Value>
+}
+
+impl<'de> VariantDeserializer<'de> {
+	fn value(self) -> Result<Deserializer<'de>, Error> {
+		self.value
+			.map(|value| Deserializer { value })
+			.ok_or_else(|| {
+				de::Error::invalid_type(de::Unexpected::UnitVariant, &"a value")
+			})
+	}
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
+	type Error = Error;
+
+	fn unit_variant(self) -> Result<(), Error> {
+		match self.value {
+			None => Ok(()),
+			Some(_) => Err(de::Error::invalid_type(
+				de::Unexpected::NewtypeVariant,
+				&"unit variant"
+			))
+		}
+	}
+
+	fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+	where
+		T: DeserializeSeed<'de>
+	{
+		seed.deserialize(self.value()?)
+	}
+
+	fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		de::Deserializer::deserialize_tuple(self.value()?, len, visitor)
+	}
+
+	fn struct_variant<V>(
+		self,
+		_fields: &'static [&'static str],
+		visitor: V
+	) -> Result<V::Value, Error>
+	where
+		V: Visitor<'de>
+	{
+		de::Deserializer::deserialize_map(self.value()?, visitor)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		board::{
+			graphic::{GraphicItem, Segment, Text},
+			Layer
+		},
+		common::{Effects, Font, Point, Position},
+		mm, Unit
+	};
+	use serde_json::json;
+	use uuid::Uuid;
+
+	#[test]
+	fn single_element_tuples_are_flattened() {
+		let value = to_value(&(1.5.mm(),)).unwrap();
+		assert_eq!(value, json!(1.5));
+		let parsed: (mm,) = from_value(&value).unwrap();
+		assert_eq!(parsed, (1.5.mm(),));
+	}
+
+	#[test]
+	fn untagged_enum_variants_are_tagged() {
+		let items = vec![GraphicItem::Text(Text {
+			text: "hi".to_owned(),
+			locked: false,
+			position: Position::new(0.0.mm(), 0.0.mm()),
+			layer: None,
+			knockout: false,
+			effects: Effects::new(Font::new(1.0.mm())),
+			tstamp: Uuid::nil(),
+			render_cache: None
+		})];
+		let value = to_value(&items).unwrap();
+		assert_eq!(value[0][TAG], json!("gr_text"));
+		let parsed: Vec<GraphicItem> = from_value(&value).unwrap();
+		assert_eq!(parsed, items);
+	}
+
+	#[test]
+	fn unit_values_are_true() {
+		let segment = Segment {
+			locked: true,
+			start: Point::new(0.0.mm(), 0.0.mm()),
+			end: Point::new(1.0.mm(), 0.0.mm()),
+			width: 0.2.mm(),
+			layer: Layer::new("F.Cu"),
+			net: 1,
+			tstamp: Uuid::nil()
+		};
+		let value = to_value(&segment).unwrap();
+		assert_eq!(value["locked"], json!(true));
+		assert_eq!(value["net"], json!(1));
+		let parsed: Segment = from_value(&value).unwrap();
+		assert_eq!(parsed, segment);
+	}
+}
@@ -0,0 +1,197 @@
+//! A custom (de)serializer for [`mm`] that accepts bare numbers as well as
+//! numbers with a `mm`, `in` or `mil` suffix, which some third-party tools
+//! write. Values are always serialized as bare millimeters, rounded like KiCad
+//! does by [`format_mm`].
+
+use crate::{format::format_mm, length::parse_mm, mm};
+use serde::{
+	de::{self, Visitor},
+	Deserialize, Deserializer, Serialize, Serializer
+};
+use std::fmt::{self, Formatter};
+
+struct LengthVisitor;
+
+impl<'de> Visitor<'de> for LengthVisitor {
+	type Value = mm;
+
+	fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("a length in mm, in or mil")
+	}
+
+	fn visit_f64<E>(self, value: f64) -> Result<mm, E>
+	where
+		E: de::Error
+	{
+		mm::try_new(value as f32).map_err(E::custom)
+	}
+
+	fn visit_i64<E>(self, value: i64) -> Result<mm, E>
+	where
+		E: de::Error
+	{
+		self.visit_f64(value as f64)
+	}
+
+	fn visit_u64<E>(self, value: u64) -> Result<mm, E>
+	where
+		E: de::Error
+	{
+		self.visit_f64(value as f64)
+	}
+
+	fn visit_str<E>(self, value: &str) -> Result<mm, E>
+	where
+		E: de::Error
+	{
+		parse_mm(value)
+			.map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+	}
+}
+
+struct Length(mm);
+
+impl<'de> Deserialize<'de> for Length {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>
+	{
+		deserializer.deserialize_any(LengthVisitor).map(Self)
+	}
+}
+
+impl Serialize for Length {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		// the rounded value is parsed back so that it is still written as a
+		// number, the shortest representation of it has the same digits
+		let raw = self.0.raw_value();
+		serializer.serialize_f32(format_mm(self.0).parse().unwrap_or(raw))
+	}
+}
+
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<mm, D::Error>
+where
+	D: Deserializer<'de>
+{
+	Length::deserialize(deserializer).map(|length| length.0)
+}
+
+pub(crate) fn serialize<S>(this: &mm, serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: Serializer
+{
+	Length(*this).serialize(serializer)
+}
+
+/// The same as [`super::tuple`], but for lengths with an optional unit suffix.
+pub(crate) mod tuple {
+	use super::Length;
+	use crate::mm;
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<mm, D::Error>
+	where
+		D: Deserializer<'de>
+	{
+		Deserialize::deserialize(deserializer).map(|t: (Length,)| t.0 .0)
+	}
+
+	pub(crate) fn serialize<S>(this: &mm, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		crate::internal::tuple::serialize(&Length(*this), serializer)
+	}
+}
+
+/// The same as [`super::option_tuple`], but for lengths with an optional unit
+/// suffix.
+pub(crate) mod option_tuple {
+	use super::Length;
+	use crate::mm;
+	use serde::{Deserializer, Serializer};
+
+	pub(crate) fn deserialize<'de, D>(
+		deserializer: D
+	) -> Result<Option<mm>, D::Error>
+	where
+		D: Deserializer<'de>
+	{
+		serde_sexpr::Option::deserialize(deserializer)
+			.map(|t: Option<(Length,)>| t.map(|t| t.0 .0))
+	}
+
+	pub(crate) fn serialize<S>(
+		this: &Option<mm>,
+		serializer: S
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		crate::internal::option_tuple::serialize(&this.map(Length), serializer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{board::graphic::Segment, board::Layer, common::Point, Unit};
+
+	fn parse_point(input: &str) -> Point {
+		serde_sexpr::from_str(input).unwrap()
+	}
+
+	#[test]
+	fn bare_number() {
+		assert_eq!(parse_point("(xy 0.89 2)").x, 0.89.mm());
+		assert_eq!(parse_point("(xy 0.89 2)").y, 2.0.mm());
+	}
+
+	#[test]
+	fn unit_suffix() {
+		let point = parse_point("(xy 0.89mm 35mil)");
+		assert_eq!(point.x, 0.89.mm());
+		assert_eq!(point.y, 0.889.mm());
+		assert_eq!(parse_point("(xy 1in -2)").x, 25.4.mm());
+	}
+
+	#[test]
+	fn serialize_bare() {
+		let point = parse_point("(xy 35mil 1mm)");
+		assert_eq!(serde_sexpr::to_string(&point).unwrap(), "(xy 0.889 1)");
+	}
+
+	#[test]
+	fn invalid() {
+		assert!(serde_sexpr::from_str::<Point>("(xy 1ft 0)").is_err());
+		assert!(serde_sexpr::from_str::<Point>("(xy mm 0)").is_err());
+	}
+
+	#[test]
+	fn serialize_rounded() {
+		let point = Point::new((1.0 / 3.0).mm(), (0.1 + 0.2).mm());
+		assert_eq!(
+			serde_sexpr::to_string(&point).unwrap(),
+			"(xy 0.333333 0.3)"
+		);
+		let point = Point::new(152.89.mm(), (-0.0000001).mm());
+		assert_eq!(serde_sexpr::to_string(&point).unwrap(), "(xy 152.89 0)");
+		let point = Point::new(100.0.mm(), 1e10.mm());
+		assert_eq!(
+			serde_sexpr::to_string(&point).unwrap(),
+			"(xy 100 10000000000)"
+		);
+
+		let segment = Segment::new(
+			Point::new(0.0.mm(), 0.0.mm()),
+			Point::new(1.0.mm(), 0.0.mm()),
+			(0.25 / 3.0).mm(),
+			Layer::new("F.Cu"),
+			1
+		);
+		let written = serde_sexpr::to_string(&segment).unwrap();
+		assert!(written.contains("(width 0.083333)"), "{written}");
+	}
+}
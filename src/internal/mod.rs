@@ -1,16 +1,29 @@
+#[cfg(feature = "arbitrary")]
+pub(crate) mod arbitrary;
+pub(crate) mod base64;
+#[cfg(feature = "json")]
+pub(crate) mod json;
+pub(crate) mod lenient_mm;
 pub(crate) mod option_tuple;
 pub(crate) mod option_unit;
 pub(crate) mod option_yes_no;
+#[cfg(test)]
+pub(crate) mod proptest;
+pub(crate) mod raw_node;
 pub(crate) mod rename;
+pub(crate) mod scanner;
+pub(crate) mod tstamp;
 pub(crate) mod tuple;
 pub(crate) mod tuple_or_default;
 pub(crate) mod yes_no;
 
+mod u32_dec;
 mod u32_hex;
 mod unit_variant;
 
 use crate::Color;
 use serde::{Deserialize, Serialize};
+pub(crate) use u32_dec::u32_dec;
 pub(crate) use u32_hex::u32_hex;
 pub(crate) use unit_variant::UnitVariant;
 
@@ -22,3 +35,64 @@ pub(crate) struct ColorDef {
 	b: u8,
 	a: f32
 }
+
+/// The color KiCad writes as `(color 0 0 0 0)` to mean the default color.
+pub(crate) const fn transparent() -> Color {
+	Color::new_alpha(0, 0, 0, 0.0)
+}
+
+/// Serialize an optional [`Color`] as an optional `(color ..)` node.
+pub(crate) mod option_color {
+	use super::ColorDef;
+	use crate::Color;
+	use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+	#[derive(Deserialize, Serialize)]
+	#[serde(transparent)]
+	struct ColorNode(#[serde(with = "ColorDef")] Color);
+
+	pub(crate) fn deserialize<'de, D>(
+		deserializer: D
+	) -> Result<Option<Color>, D::Error>
+	where
+		D: Deserializer<'de>
+	{
+		let color: Option<ColorNode> =
+			serde_sexpr::Option::deserialize(deserializer)?;
+		Ok(color.map(|ColorNode(color)| color))
+	}
+
+	pub(crate) fn serialize<S>(
+		this: &Option<Color>,
+		serializer: S
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		serde_sexpr::Option::serialize(&this.map(ColorNode), serializer)
+	}
+}
+
+/// Serialize a [`Color`] that falls back to [`transparent`] if it is missing.
+pub(crate) mod color_or_transparent {
+	use super::{option_color, transparent, ColorDef};
+	use crate::Color;
+	use serde::{Deserializer, Serializer};
+
+	pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Color, D::Error>
+	where
+		D: Deserializer<'de>
+	{
+		Ok(option_color::deserialize(deserializer)?.unwrap_or_else(transparent))
+	}
+
+	pub(crate) fn serialize<S>(
+		this: &Color,
+		serializer: S
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		ColorDef::serialize(this, serializer)
+	}
+}
@@ -0,0 +1,100 @@
+//! [`proptest`] strategies for property tests asserting that values can be
+//! written as s-expr and parsed back unchanged.
+
+use crate::{
+	board::{graphic::Segment, pcb::Net, Layer},
+	common::Point,
+	format::format_mm,
+	mm, Unit
+};
+use proptest::{num::f32, prelude::*};
+use uuid::Uuid;
+
+const LAYERS: &[&str] = &[
+	"F.Cu",
+	"In1.Cu",
+	"In30.Cu",
+	"B.Cu",
+	"F.SilkS",
+	"B.Mask",
+	"Edge.Cuts",
+	"User.9"
+];
+
+/// The largest length that can be parsed back. Larger values are written
+/// without a fractional part, which the s-expr parser reads as an integer that
+/// overflows.
+const MAX_LENGTH: f32 = 1e18;
+
+/// Lengths that stress the number formatter: whole micrometres like KiCad
+/// writes them, arbitrary finite values including subnormal and very large
+/// ones, and a few hand-picked edge cases. Lengths are written rounded to
+/// [`KICAD_DECIMALS`](crate::format::KICAD_DECIMALS), so all values are rounded
+/// the same way to be written back unchanged.
+pub(crate) fn mm() -> impl Strategy<Value = mm> {
+	let round = |value: mm| format_mm(value).parse::<f32>().unwrap().mm();
+	prop_oneof![
+		(-1_000_000..=1_000_000i32).prop_map(|um| (um as f32 / 1000.0).mm()),
+		(f32::NORMAL | f32::SUBNORMAL | f32::ZERO | f32::POSITIVE | f32::NEGATIVE)
+			.prop_filter("too large to parse", |value| value.abs() < MAX_LENGTH)
+			.prop_map(Unit::mm),
+		prop::sample::select(vec![
+			0.0,
+			-0.0,
+			1e-7,
+			0.0000005,
+			0.1 + 0.2,
+			1.0 / 3.0,
+			-1e10,
+			-9.99e17,
+			f32::MIN_POSITIVE
+		])
+		.prop_map(Unit::mm)
+	]
+	.prop_map(round)
+}
+
+pub(crate) fn point() -> impl Strategy<Value = Point> {
+	(mm(), mm()).prop_map(|(x, y)| Point::new(x, y))
+}
+
+/// Canonical layer names as well as arbitrary user layer names.
+pub(crate) fn layer() -> impl Strategy<Value = Layer> {
+	prop_oneof![
+		prop::sample::select(LAYERS).prop_map(Layer::new),
+		"[A-Za-z][A-Za-z0-9_.&* -]{0,15}".prop_map(Layer::new)
+	]
+}
+
+/// Nets with any number and names with quotes, backslashes and parentheses,
+/// or with non-ASCII characters. The s-expr parser cannot unescape a quote
+/// that follows a non-ASCII character, so both are never combined.
+pub(crate) fn net() -> impl Strategy<Value = Net> {
+	let name = prop_oneof!["[ -~]{0,16}", "[^\"\\\\[:cntrl:]]{0,16}"];
+	(any::<u32>(), name).prop_map(|(number, name)| Net { number, name })
+}
+
+pub(crate) fn uuid() -> impl Strategy<Value = Uuid> {
+	any::<[u8; 16]>().prop_map(Uuid::from_bytes)
+}
+
+pub(crate) fn segment() -> impl Strategy<Value = Segment> {
+	(
+		any::<bool>(),
+		point(),
+		point(),
+		mm(),
+		layer(),
+		any::<u32>(),
+		uuid()
+	)
+		.prop_map(|(locked, start, end, width, layer, net, tstamp)| Segment {
+			locked,
+			start,
+			end,
+			width,
+			layer,
+			net,
+			tstamp
+		})
+}
@@ -0,0 +1,128 @@
+//! A custom serializer that writes s-expr nodes recorded as their text, e.g.
+//! the nodes that are not modelled (yet). The nodes are written as an unnamed
+//! sequence and therefore need a field with an empty name.
+//!
+//! Unquoted atoms like numbers and keywords are written as they are, quoted
+//! strings are re-quoted by the s-expr serializer. This might change which
+//! strings are quoted, but not the meaning of the node.
+
+use super::scanner;
+use once_cell::sync::Lazy as SyncLazy;
+use serde::{
+	ser::{Error as _, SerializeSeq, SerializeTupleStruct},
+	Serialize, Serializer
+};
+use std::{collections::HashSet, sync::Mutex};
+
+static SYMBOLS: SyncLazy<Mutex<HashSet<&'static str>>> =
+	SyncLazy::new(|| Mutex::new(HashSet::new()));
+
+/// The serializer only accepts static names, so node names and symbols are
+/// leaked once and re-used afterwards.
+fn symbol(v: &str) -> &'static str {
+	let mut symbols = SYMBOLS.lock().expect("I got poisoned");
+	match symbols.get(v) {
+		Some(symbol) => symbol,
+		None => {
+			let symbol: &'static str = Box::leak(v.to_owned().into_boxed_str());
+			symbols.insert(symbol);
+			symbol
+		}
+	}
+}
+
+/// Return the node as it is written by [`serialize`], so that a recorded node
+/// compares equal to itself after being written and parsed again.
+pub(crate) fn canonical(node: &str) -> Result<String, serde_sexpr::de::Error> {
+	serde_sexpr::to_string(&RawNode(node)).map_err(serde::de::Error::custom)
+}
+
+pub(crate) fn serialize<S>(
+	nodes: &[String],
+	serializer: S
+) -> Result<S::Ok, S::Error>
+where
+	S: Serializer
+{
+	let mut seq = serializer.serialize_seq(Some(nodes.len()))?;
+	for node in nodes {
+		seq.serialize_element(&RawNode(node))?;
+	}
+	seq.end()
+}
+
+/// A node recorded as its text, written like [`serialize`] writes each node.
+pub(crate) struct RawNode<'a>(pub(crate) &'a str);
+
+impl Serialize for RawNode<'_> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		let (name, elements) =
+			scanner::elements(self.0).map_err(S::Error::custom)?;
+		let mut st =
+			serializer.serialize_tuple_struct(symbol(name), elements.len())?;
+		for element in elements {
+			if element.text.starts_with('(') {
+				st.serialize_field(&RawNode(element.text))?;
+			} else {
+				st.serialize_field(&Atom(element.text))?;
+			}
+		}
+		st.end()
+	}
+}
+
+struct Atom<'a>(&'a str);
+
+impl Serialize for Atom<'_> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		let atom = self.0;
+		if atom.starts_with('"') {
+			return serializer.serialize_str(&scanner::unquote(atom));
+		}
+		// avoid leaking the many different numbers if they are written unchanged
+		if let Ok(v) = atom.parse::<i64>() {
+			if v.to_string() == atom {
+				return serializer.serialize_i64(v);
+			}
+		}
+		if let Ok(v) = atom.parse::<f64>() {
+			if v.to_string() == atom {
+				return serializer.serialize_f64(v);
+			}
+		}
+		serializer.serialize_unit_variant("", 0, symbol(atom))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde::Serialize;
+
+	#[derive(Serialize)]
+	#[serde(rename = "node")]
+	struct Nodes {
+		#[serde(rename = "", serialize_with = "super::serialize")]
+		unknown: Vec<String>
+	}
+
+	#[test]
+	fn serialize_raw_nodes() {
+		let nodes = Nodes {
+			unknown: vec![
+				"(bogus 1 -2.5 yes)".to_owned(),
+				r#"(layers F.Cu "B.Cu" (0 "a \"b\""))"#.to_owned(),
+				"(empty)".to_owned()
+			]
+		};
+		assert_eq!(
+			serde_sexpr::to_string(&nodes).unwrap(),
+			r#"(node (bogus 1 -2.5 yes) (layers F.Cu "B.Cu" (0 "a \"b\"")) (empty))"#
+		);
+	}
+}
@@ -135,7 +135,6 @@ where
 		fn serialize_str(self, v: &str) -> Self::Ok;
 		fn serialize_bytes(self, v: &[u8]) -> Self::Ok;
 		fn serialize_none(self) -> Self::Ok;
-		fn serialize_some<T>(self, v: &T) -> Self::Ok;
 		fn serialize_unit(self) -> Self::Ok;
 		fn serialize_unit_struct(self, name: &'static str) -> Self::Ok;
 		fn serialize_unit_variant(self, name: &'static str, idx: u32, var: &'static str) -> Self::Ok;
@@ -149,6 +148,14 @@ where
 		fn serialize_struct(self, name: &'static str, len: usize) -> Self::SerializeStruct;
 		fn serialize_struct_variant(self, name: &'static str, idx: u32, var: &'static str, len: usize) -> Self::SerializeStructVariant;
 	}
+
+	// keep renaming the value inside of the option
+	fn serialize_some<T>(self, v: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize
+	{
+		v.serialize(self)
+	}
 }
 
 macro_rules! rename {
@@ -228,5 +235,5 @@ macro_rules! rename {
 }
 
 rename! {
-	start end center mid offset
+	at start end center mid offset
 }
@@ -0,0 +1,285 @@
+//! A tiny scanner that splits an s-expression into its direct child nodes
+//! without interpreting them. This is used for the top-level of large files
+//! where some of the children cannot (yet) be deserialized.
+
+use serde::de::Error as _;
+use serde_sexpr::de::Error;
+use std::borrow::Cow;
+
+/// A child node of an s-expression, borrowed from the scanned input.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Node<'a> {
+	/// The identifier directly following the opening parenthesis. This might be
+	/// empty for nodes like `(0 "F.Cu" signal)`.
+	pub(crate) name: &'a str,
+	/// The full text of the node, including its parentheses.
+	pub(crate) text: &'a str
+}
+
+/// Split `(name (child ..) (child ..))` into its name and its child nodes.
+/// Atoms in between the child nodes are not allowed.
+pub(crate) fn children(input: &str) -> Result<(&str, Vec<Node<'_>>), Error> {
+	scan(input, false)
+}
+
+/// Split `(name atom (child ..))` into its name and all of its children. Atoms
+/// are returned as nodes with an empty name.
+pub(crate) fn elements(input: &str) -> Result<(&str, Vec<Node<'_>>), Error> {
+	scan(input, true)
+}
+
+fn scan(input: &str, allow_atoms: bool) -> Result<(&str, Vec<Node<'_>>), Error> {
+	let (name, mut rest) = open(input)?;
+	let mut nodes = Vec::new();
+	while let Some((node, next)) = next_child(name, rest, allow_atoms)? {
+		nodes.push(node);
+		rest = next;
+	}
+	Ok((name, nodes))
+}
+
+/// Split `(name ..)` into its name and the remaining input after the name,
+/// which can be scanned one child at a time using [`next_child`].
+pub(crate) fn open(input: &str) -> Result<(&str, &str), Error> {
+	let input = input.trim();
+	let inner = input
+		.strip_prefix('(')
+		.ok_or_else(|| Error::custom("expected s-expr"))?;
+	let name_len = identifier_len(inner);
+	Ok((&inner[..name_len], &inner[name_len..]))
+}
+
+/// Scan the next child of the s-expr `name` from `rest` and return it together
+/// with the input following it. Returns `None` once the closing parenthesis of
+/// the s-expr is reached.
+pub(crate) fn next_child<'a>(
+	name: &str,
+	rest: &'a str,
+	allow_atoms: bool
+) -> Result<Option<(Node<'a>, &'a str)>, Error> {
+	let rest = rest.trim_start();
+	match rest.chars().next() {
+		Some(')') => {
+			if !rest[1..].trim().is_empty() {
+				return Err(Error::custom("trailing tokens after s-expr"));
+			}
+			Ok(None)
+		},
+		Some('(') => {
+			let len = node_len(rest)?;
+			let name_len = identifier_len(&rest[1..]);
+			let node = Node {
+				name: &rest[1..1 + name_len],
+				text: &rest[..len]
+			};
+			Ok(Some((node, &rest[len..])))
+		},
+		Some(_) if allow_atoms => {
+			let len = atom_len(rest)?;
+			let node = Node {
+				name: "",
+				text: &rest[..len]
+			};
+			Ok(Some((node, &rest[len..])))
+		},
+		Some(_) => Err(Error::custom(format!("unexpected atom in s-expr {name}"))),
+		None => Err(Error::custom("unexpected end of input"))
+	}
+}
+
+/// Remove `;` line comments outside of strings and surrounding whitespace from
+/// the input. KiCad does not write comments, but hand-edited files might
+/// contain them.
+pub(crate) fn strip_comments(input: &str) -> Cow<'_, str> {
+	let input = input.trim();
+	if !input.contains(';') {
+		return Cow::Borrowed(input);
+	}
+
+	let mut out = String::with_capacity(input.len());
+	let mut in_string = false;
+	let mut escaped = false;
+	let mut in_comment = false;
+	for ch in input.chars() {
+		if in_comment {
+			if ch == '\n' {
+				in_comment = false;
+				out.push(ch);
+			}
+			continue;
+		}
+		if in_string {
+			match ch {
+				_ if escaped => escaped = false,
+				'\\' => escaped = true,
+				'"' => in_string = false,
+				_ => {}
+			}
+		} else if ch == ';' {
+			in_comment = true;
+			continue;
+		} else if ch == '"' {
+			in_string = true;
+		}
+		out.push(ch);
+	}
+	let len = out.trim_end().len();
+	out.truncate(len);
+	Cow::Owned(out)
+}
+
+/// Write `value` as a quoted s-expr string, escaping quotes and backslashes.
+pub(crate) fn quote(value: &str) -> String {
+	let mut out = String::with_capacity(value.len() + 2);
+	out.push('"');
+	for ch in value.chars() {
+		if ch == '"' || ch == '\\' {
+			out.push('\\');
+		}
+		out.push(ch);
+	}
+	out.push('"');
+	out
+}
+
+/// Return the value of an atom, removing the quotes and escapes of a quoted
+/// string. This is the inverse of [`quote`].
+pub(crate) fn unquote(atom: &str) -> Cow<'_, str> {
+	let Some(inner) = atom
+		.strip_prefix('"')
+		.and_then(|atom| atom.strip_suffix('"'))
+	else {
+		return Cow::Borrowed(atom);
+	};
+	if !inner.contains('\\') {
+		return Cow::Borrowed(inner);
+	}
+
+	let mut out = String::with_capacity(inner.len());
+	let mut chars = inner.chars();
+	while let Some(ch) = chars.next() {
+		match ch {
+			'\\' => out.extend(chars.next()),
+			ch => out.push(ch)
+		}
+	}
+	Cow::Owned(out)
+}
+
+fn identifier_len(input: &str) -> usize {
+	input
+		.chars()
+		.take_while(|ch| ch.is_ascii_alphanumeric() || *ch == '_')
+		.map(|ch| ch.len_utf8())
+		.sum()
+}
+
+/// Return the length in bytes of the atom at the start of `input`.
+fn atom_len(input: &str) -> Result<usize, Error> {
+	if let Some(string) = input.strip_prefix('"') {
+		let mut escaped = false;
+		for (idx, ch) in string.char_indices() {
+			match ch {
+				_ if escaped => escaped = false,
+				'\\' => escaped = true,
+				'"' => return Ok(idx + 2),
+				_ => {}
+			}
+		}
+		return Err(Error::custom("unexpected end of input"));
+	}
+	Ok(input
+		.find(|ch: char| ch.is_whitespace() || ch == '(' || ch == ')')
+		.unwrap_or(input.len()))
+}
+
+/// Return the length in bytes of the s-expr at the start of `input`.
+fn node_len(input: &str) -> Result<usize, Error> {
+	let mut depth = 0_usize;
+	let mut in_string = false;
+	let mut escaped = false;
+	for (idx, ch) in input.char_indices() {
+		if in_string {
+			match ch {
+				_ if escaped => escaped = false,
+				'\\' => escaped = true,
+				'"' => in_string = false,
+				_ => {}
+			}
+			continue;
+		}
+		match ch {
+			'"' => in_string = true,
+			'(' => depth += 1,
+			')' => {
+				depth -= 1;
+				if depth == 0 {
+					return Ok(idx + 1);
+				}
+			},
+			_ => {}
+		}
+	}
+	Err(Error::custom("unexpected end of input"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_children() {
+		let input =
+			r#"(kicad_pcb (version 1) (title "a ) \" b") (0 "F.Cu" signal))"#;
+		let (name, nodes) = children(input).unwrap();
+		assert_eq!(name, "kicad_pcb");
+		assert_eq!(nodes, vec![
+			Node {
+				name: "version",
+				text: "(version 1)"
+			},
+			Node {
+				name: "title",
+				text: r#"(title "a ) \" b")"#
+			},
+			Node {
+				name: "0",
+				text: r#"(0 "F.Cu" signal)"#
+			}
+		]);
+	}
+
+	#[test]
+	fn test_elements() {
+		let input = r#"(gr_text "a \" b" (at 0 0) foo)"#;
+		let (name, nodes) = elements(input).unwrap();
+		assert_eq!(name, "gr_text");
+		let texts: Vec<_> = nodes.iter().map(|node| node.text).collect();
+		assert_eq!(texts, vec![r#""a \" b""#, "(at 0 0)", "foo"]);
+		assert_eq!(nodes[0].name, "");
+		assert_eq!(nodes[1].name, "at");
+		assert!(children(input).is_err());
+	}
+
+	#[test]
+	fn test_strip_comments() {
+		let input = "\n\n; a comment\n(title \"a ; b\") ; trailing\n\n";
+		assert_eq!(strip_comments(input), "\n(title \"a ; b\")");
+		assert!(matches!(strip_comments(" (a) "), Cow::Borrowed("(a)")));
+	}
+
+	#[test]
+	fn test_children_unterminated() {
+		assert!(children("(kicad_pcb (version 1)").is_err());
+	}
+
+	#[test]
+	fn test_quote() {
+		assert_eq!(quote("F.Cu"), r#""F.Cu""#);
+		assert_eq!(quote(r#"a "b" \ c"#), r#""a \"b\" \\ c""#);
+		for value in ["F.Cu", "", r#"a "b" \ c"#] {
+			assert_eq!(unquote(&quote(value)), value);
+		}
+		assert_eq!(unquote("signal"), "signal");
+	}
+}
@@ -0,0 +1,92 @@
+//! A custom (de)serializer for the unique identifier of an item. KiCad used to
+//! write it as `(tstamp ..)` and renamed the node to `(uuid ..)` in later
+//! versions, both names are accepted when parsing. The identifier is always
+//! written as `(tstamp ..)`, see [`UuidName`](crate::format::UuidName) to write
+//! the newer name instead.
+
+use serde::{
+	de::{self, EnumAccess, VariantAccess, Visitor},
+	Deserialize, Deserializer, Serialize, Serializer
+};
+use std::fmt::{self, Formatter};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+#[serde(rename = "tstamp")]
+struct Tstamp(Uuid);
+
+#[derive(Deserialize)]
+#[serde(rename = "uuid")]
+struct UuidNode(Uuid);
+
+struct TstampVisitor;
+
+impl<'de> Visitor<'de> for TstampVisitor {
+	type Value = Option<Uuid>;
+
+	fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("a (tstamp ..) or (uuid ..) node")
+	}
+
+	fn visit_none<E>(self) -> Result<Self::Value, E>
+	where
+		E: de::Error
+	{
+		Ok(None)
+	}
+
+	fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+	where
+		A: EnumAccess<'de>
+	{
+		let (name, variant): (String, _) = data.variant()?;
+		match name.as_str() {
+			"tstamp" => variant.newtype_variant().map(|Tstamp(t)| Some(t)),
+			"uuid" => variant.newtype_variant().map(|UuidNode(t)| Some(t)),
+			// the s-expr deserializer only peeked at the name of the next node,
+			// which belongs to the next field. Formats without named nodes like
+			// JSON hand us the plain identifier instead.
+			name => Ok(Uuid::parse_str(name).ok())
+		}
+	}
+}
+
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Uuid, D::Error>
+where
+	D: Deserializer<'de>
+{
+	option::deserialize(deserializer).map(Option::unwrap_or_default)
+}
+
+pub(crate) fn serialize<S>(this: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: Serializer
+{
+	(this,).serialize(serializer)
+}
+
+/// Like the parent module, but the identifier is optional.
+pub(crate) mod option {
+	use super::TstampVisitor;
+	use serde::{Deserializer, Serializer};
+	use uuid::Uuid;
+
+	pub(crate) fn deserialize<'de, D>(
+		deserializer: D
+	) -> Result<Option<Uuid>, D::Error>
+	where
+		D: Deserializer<'de>
+	{
+		deserializer.deserialize_enum("tstamp", &["tstamp", "uuid"], TstampVisitor)
+	}
+
+	pub(crate) fn serialize<S>(
+		this: &Option<Uuid>,
+		serializer: S
+	) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		crate::internal::option_tuple::serialize(this, serializer)
+	}
+}
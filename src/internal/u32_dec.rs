@@ -0,0 +1,13 @@
+use once_cell::sync::Lazy as SyncLazy;
+use std::{collections::HashMap, sync::Mutex};
+
+static DECMAP: SyncLazy<Mutex<HashMap<u32, &'static str>>> =
+	SyncLazy::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) fn u32_dec(v: u32) -> &'static str {
+	DECMAP
+		.lock()
+		.expect("I got poisoned")
+		.entry(v)
+		.or_insert_with(|| Box::leak(Box::new(v.to_string())))
+}
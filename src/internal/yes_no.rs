@@ -1,9 +1,11 @@
+//! Custom (de)serializer that deserializes a bool from (name yes) or (name no).
+
 use super::tuple;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Clone, Copy, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
-pub(super) enum YesNo {
+pub(crate) enum YesNo {
 	Yes,
 	No
 }
@@ -41,3 +43,55 @@ where
 {
 	tuple::serialize(&YesNo::from(*this), serializer)
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		internal::{option_yes_no, yes_no},
+		sexpr_test_case
+	};
+	use serde::{Deserialize, Serialize};
+
+	#[derive(Debug, Deserialize, PartialEq, Serialize)]
+	#[serde(deny_unknown_fields, rename = "flags")]
+	struct Flags {
+		#[serde(with = "yes_no")]
+		in_bom: bool,
+
+		#[serde(with = "option_yes_no")]
+		dnp: Option<bool>
+	}
+
+	sexpr_test_case! {
+		name: yes,
+		input: "(flags (in_bom yes) (dnp yes))",
+		value: Flags {
+			in_bom: true,
+			dnp: Some(true)
+		}
+	}
+
+	sexpr_test_case! {
+		name: no,
+		input: "(flags (in_bom no) (dnp no))",
+		value: Flags {
+			in_bom: false,
+			dnp: Some(false)
+		}
+	}
+
+	sexpr_test_case! {
+		name: missing,
+		input: "(flags (in_bom yes))",
+		value: Flags {
+			in_bom: true,
+			dnp: None
+		}
+	}
+
+	#[test]
+	fn invalid() {
+		assert!(serde_sexpr::from_str::<Flags>("(flags (in_bom maybe))").is_err());
+		assert!(serde_sexpr::from_str::<Flags>("(flags (dnp yes))").is_err());
+	}
+}
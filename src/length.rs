@@ -0,0 +1,72 @@
+//! Parsing and formatting of lengths. [`mm`] itself is defined by the
+//! `millimeter` crate, whose `FromStr` and `Display` implementations only
+//! handle bare numbers, so the KiCad specific variants are provided here.
+//! Lengths are formatted by [`format_mm`] from [`crate::format`].
+
+pub use crate::format::format_mm;
+use crate::{mm, Unit};
+use thiserror::Error;
+
+const MM_PER_INCH: f32 = 25.4;
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+#[error(
+	"invalid length {0:?}, expected a number with an optional mm, in or mil suffix"
+)]
+pub struct ParseLengthError(String);
+
+/// Parse a length with an optional unit suffix, e.g. `0.89`, `0.89mm`, `0.1in`
+/// or `35mil`. This accepts the same input as the deserializer of coordinates
+/// and widths.
+pub fn parse_mm(value: &str) -> Result<mm, ParseLengthError> {
+	let err = || ParseLengthError(value.to_owned());
+	let trimmed = value.trim();
+	let (number, factor) = if let Some(number) = trimmed.strip_suffix("mm") {
+		(number, 1.0)
+	} else if let Some(number) = trimmed.strip_suffix("mil") {
+		(number, MM_PER_INCH / 1000.0)
+	} else if let Some(number) = trimmed.strip_suffix("in") {
+		(number, MM_PER_INCH)
+	} else {
+		(trimmed, 1.0)
+	};
+	let number: f32 = number.trim_end().parse().map_err(|_| err())?;
+	let length = number * factor;
+	length.is_finite().then(|| length.mm()).ok_or_else(err)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse() {
+		assert_eq!(parse_mm("0"), Ok(0.0.mm()));
+		assert_eq!(parse_mm("1.27"), Ok(1.27.mm()));
+		assert_eq!(parse_mm("0.89mm"), Ok(0.89.mm()));
+		assert_eq!(parse_mm("35mil"), Ok(0.889.mm()));
+		assert_eq!(parse_mm("-0.1in"), Ok((-2.54).mm()));
+		assert_eq!(parse_mm(" 2 mm "), Ok(2.0.mm()));
+		assert!(parse_mm("1ft").is_err());
+		assert!(parse_mm("inf").is_err());
+		assert!(parse_mm("").is_err());
+
+		// the millimeter crate parses bare numbers on its own
+		assert_eq!("0.89".parse::<mm>(), Ok(0.89.mm()));
+	}
+
+	#[test]
+	fn format() {
+		assert_eq!(format_mm(0.0.mm()), "0");
+		assert_eq!(format_mm((-0.0).mm()), "0");
+		assert_eq!(format_mm(1.27.mm()), "1.27");
+		assert_eq!(format_mm(100.0.mm()), "100");
+		assert_eq!(format_mm((-2.403861).mm()), "-2.403861");
+		assert_eq!(format_mm(0.1234567.mm()), "0.123457");
+		assert_eq!(format_mm((0.1 + 0.2).mm()), "0.3");
+		// rounding the binary representation would write 152.889999
+		assert_eq!(format_mm(152.89.mm()), "152.89");
+		assert_eq!(format_mm(1234.56.mm()), "1234.56");
+		assert_eq!(format_mm(parse_mm("152.89mm").unwrap()), "152.89");
+	}
+}
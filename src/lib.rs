@@ -20,20 +20,24 @@ use rgb::RGBA;
 use uuid::Uuid;
 
 #[cfg(test)]
-#[macro_use]
 mod macros;
 
 mod degree;
+mod error;
 mod internal;
+mod length;
 
 pub mod board;
 pub mod common;
+pub mod format;
 pub mod schematic;
 pub mod symbol;
 pub mod symbol_lib;
 pub use board::pcb;
 
 pub use degree::{deg, Deg};
+pub use error::Error;
+pub use length::{format_mm, parse_mm, ParseLengthError};
 
 pub type Color = RGBA<u8, f32>;
 
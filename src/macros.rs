@@ -31,7 +31,9 @@ macro_rules! sexpr_test_case {
 				let value = $value;
 				let expected = $input;
 				#[cfg(feature = "skip_nil_uuids")]
-				let expected = expected.replace(r#" (tstamp "00000000-0000-0000-0000-000000000000")"#, "");
+				let expected = expected
+					.replace(r#" (tstamp "00000000-0000-0000-0000-000000000000")"#, "")
+					.replace(r#" (uuid "00000000-0000-0000-0000-000000000000")"#, "");
 				assert_eq_ugly(&value, &expected);
 			}
 		}
@@ -1,6 +1,6 @@
 use crate::{
 	common::Position,
-	internal::{tuple, tuple_or_default, ColorDef},
+	internal::{color_or_transparent, tuple, tuple_or_default},
 	mm, Color
 };
 use serde::{Deserialize, Serialize};
@@ -14,7 +14,9 @@ pub struct Junction {
 	#[serde(with = "tuple")]
 	pub diameter: mm,
 
-	#[serde(with = "ColorDef")]
+	/// The color of the junction. A transparent color, which is the default if
+	/// the color is missing, means the color of the schematic theme is used.
+	#[serde(with = "color_or_transparent")]
 	pub color: Color,
 
 	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
@@ -36,4 +38,25 @@ mod tests {
 			uuid: Uuid::nil()
 		}
 	}
+
+	sexpr_test_case! {
+		name: red_junction,
+		input: r#"(junction (at 25 31) (diameter 0) (color 255 0 0 1) (uuid "00000000-0000-0000-0000-000000000000"))"#,
+		value: Junction {
+			pos: Position::new(25.0.mm(), 31.0.mm()),
+			diameter: 0.0.mm(),
+			color: Color::new_alpha(255, 0, 0, 1.0),
+			uuid: Uuid::nil()
+		}
+	}
+
+	#[test]
+	fn default_color() {
+		let input = r#"(junction (at 25 31) (diameter 0) (uuid "00000000-0000-0000-0000-000000000000"))"#;
+		let junction: Junction = serde_sexpr::from_str(input).unwrap();
+		assert_eq!(junction.color, crate::internal::transparent());
+		assert!(serde_sexpr::to_string(&junction)
+			.unwrap()
+			.contains("(color 0 0 0 0)"));
+	}
 }
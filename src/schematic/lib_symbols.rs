@@ -1,7 +1,7 @@
 use crate::symbol::Symbol;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "lib_symbols")]
 pub struct LibSymbols {
 	#[serde(default, rename = "")]
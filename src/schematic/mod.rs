@@ -3,9 +3,14 @@
 //! This module defines all the syntax used in the schematic file format that is not
 //! shared with symbol libraries.
 
-use crate::internal::{tuple, tuple_or_default};
-use serde::{Deserialize, Serialize};
+use crate::{
+	common::{Paper, TitleBlock},
+	error::{from_node, missing_field},
+	internal::{scanner, tuple, tuple_or_default}
+};
+use serde::{de::Error as _, Deserialize, Serialize};
 use serde_sexpr::untagged;
+use std::str::FromStr;
 use uuid::Uuid;
 
 mod bus;
@@ -15,6 +20,7 @@ mod label;
 mod lib_symbols;
 mod no_connect;
 mod polyline;
+mod symbol_instance;
 mod text;
 mod wire;
 
@@ -25,6 +31,7 @@ pub use label::Label;
 pub use lib_symbols::LibSymbols;
 pub use no_connect::NoConnect;
 pub use polyline::Polyline;
+pub use symbol_instance::{PinInstance, SymbolInstance, SymbolInstanceContent};
 pub use text::Text;
 pub use wire::Wire;
 
@@ -53,12 +60,13 @@ untagged! {
 		Label(Label),
 		NoConnect(NoConnect),
 		Polyline(Polyline),
+		Symbol(SymbolInstance),
 		Text(Text),
 		Wire(Wire)
 	}
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "kicad_sch")]
 pub struct Schematic {
 	pub version: Version,
@@ -69,5 +77,175 @@ pub struct Schematic {
 	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
 	pub uuid: Uuid,
 
-	pub lib_symbols: LibSymbols
+	pub paper: Paper,
+
+	#[serde(with = "serde_sexpr::Option")]
+	pub title_block: Option<TitleBlock>,
+
+	pub lib_symbols: LibSymbols,
+
+	#[serde(default, rename = "")]
+	pub content: Vec<SchematicContent>
+}
+
+impl Schematic {
+	/// Return all symbols placed on this schematic.
+	pub fn instances(&self) -> impl Iterator<Item = &SymbolInstance> {
+		self.content.iter().filter_map(|c| match c {
+			SchematicContent::Symbol(symbol) => Some(symbol),
+			_ => None
+		})
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename = "generator")]
+struct Generator(String);
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename = "uuid")]
+struct SchematicUuid(Uuid);
+
+impl FromStr for Schematic {
+	type Err = crate::Error;
+
+	/// Parse a schematic file. The version, generator and paper are required,
+	/// all other sections that cannot be parsed (yet) are skipped.
+	fn from_str(s: &str) -> Result<Self, crate::Error> {
+		let s = scanner::strip_comments(s);
+		let (name, nodes) = scanner::children(&s)?;
+		if name != "kicad_sch" {
+			return Err(serde_sexpr::de::Error::custom(format!(
+				"expected s-expr kicad_sch, found {name}"
+			))
+			.into());
+		}
+
+		let mut version = None;
+		let mut generator = None;
+		let mut uuid = Uuid::nil();
+		let mut paper = None;
+		let mut title_block = None;
+		let mut lib_symbols = LibSymbols::default();
+		let mut content = Vec::new();
+		for node in nodes {
+			let within = |err: crate::Error| err.within(node.name).within(name);
+			match node.name {
+				"version" => version = Some(from_node(node.text).map_err(within)?),
+				"generator" => {
+					let Generator(g) = from_node(node.text).map_err(within)?;
+					generator = Some(g);
+				},
+				"uuid" => {
+					let SchematicUuid(u) = from_node(node.text).map_err(within)?;
+					uuid = u;
+				},
+				"paper" => paper = Some(from_node(node.text).map_err(within)?),
+				"title_block" => {
+					title_block = Some(from_node(node.text).map_err(within)?)
+				},
+				"lib_symbols" => {
+					lib_symbols = from_node(node.text).map_err(within)?
+				},
+				_ => {
+					if let Ok(item) = serde_sexpr::from_str(node.text) {
+						content.push(item);
+					}
+				},
+			}
+		}
+
+		Ok(Self {
+			version: version.ok_or_else(|| missing_field("version", name))?,
+			generator: generator.ok_or_else(|| missing_field("generator", name))?,
+			uuid,
+			paper: paper.ok_or_else(|| missing_field("paper", name))?,
+			title_block,
+			lib_symbols,
+			content
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{common::PaperSize, sexpr_test_case};
+
+	sexpr_test_case! {
+		name: schematic,
+		input: r#"(kicad_sch (version 20211123) (generator eeschema) (uuid "00000000-0000-0000-0000-000000000000") (paper A4) (lib_symbols))"#,
+		value: Schematic {
+			version: Version::new(),
+			generator: "eeschema".to_owned(),
+			uuid: Uuid::nil(),
+			paper: PaperSize::A4.landscape(),
+			title_block: None,
+			lib_symbols: LibSymbols::default(),
+			content: Vec::new()
+		}
+	}
+
+	#[test]
+	fn test_from_str_skips_unknown_sections() {
+		let input = r#"(kicad_sch (version 20211123) (generator eeschema)
+  (uuid 6c2d3a4e-4e36-4b83-9c54-5b5bd6f2d4a8)
+  (paper "A4")
+  (title_block (title "Minimal") (rev "1"))
+  (lib_symbols)
+  (junction (at 25 31) (diameter 0) (color 0 0 0 0) (uuid 00000000-0000-0000-0000-000000000000))
+  (sheet_instances (path "/" (page "1")))
+)"#;
+		let schematic: Schematic = input.parse().unwrap();
+		assert_eq!(schematic.version, Version(20211123));
+		assert_eq!(schematic.generator, "eeschema");
+		assert_eq!(
+			schematic.uuid,
+			Uuid::parse_str("6c2d3a4e-4e36-4b83-9c54-5b5bd6f2d4a8").unwrap()
+		);
+		assert_eq!(schematic.paper, PaperSize::A4.landscape());
+		assert_eq!(
+			schematic.title_block.unwrap().title.as_deref(),
+			Some("Minimal")
+		);
+		assert!(schematic.lib_symbols.symbols.is_empty());
+		assert!(matches!(schematic.content.as_slice(), [
+			SchematicContent::Junction(_)
+		]));
+	}
+
+	#[test]
+	fn test_instances() {
+		let input = r#"(kicad_sch (version 20211123) (generator eeschema) (paper "A4")
+  (lib_symbols
+    (symbol "Device:R" (in_bom yes) (on_board yes)
+      (property "Reference" "R" (id 0) (at 2.032 0 90) (effects (font (size 1.27 1.27))))
+      (property "Value" "R" (id 1) (at 0 0 90) (effects (font (size 1.27 1.27))))))
+  (symbol (lib_id "Device:R") (at 100 50 0) (unit 1) (in_bom yes) (on_board yes)
+    (uuid 2f4d5a6b-1c1e-4a2b-8d3e-9f8a7b6c5d4e)
+    (property "Reference" "R1" (id 0) (at 102 49 0) (effects (font (size 1.27 1.27))))
+    (property "Value" "10k" (id 1) (at 102 51 0) (effects (font (size 1.27 1.27))))
+    (pin "1" (uuid 3a1b2c3d-4e5f-4a6b-8c7d-9e0f1a2b3c4d))
+    (pin "2" (uuid 4b2c3d4e-5f6a-4b7c-9d8e-0f1a2b3c4d5e)))
+)"#;
+		let schematic: Schematic = input.parse().unwrap();
+		let symbol = &schematic.lib_symbols.symbols[0];
+		assert_eq!(symbol.get_property("Value"), Some("R"));
+
+		let instances: Vec<_> = schematic.instances().collect();
+		assert_eq!(instances.len(), 1);
+		let resistor = instances[0];
+		assert_eq!(resistor.lib_id, symbol.id);
+		assert_eq!(resistor.unit, 1);
+		assert_eq!(resistor.get_property("Reference"), Some("R1"));
+		assert_eq!(resistor.get_property("Value"), Some("10k"));
+		let pins: Vec<_> = resistor.pins().map(|pin| pin.number.as_str()).collect();
+		assert_eq!(pins, ["1", "2"]);
+	}
+
+	#[test]
+	fn test_from_str_requires_paper() {
+		let input = r#"(kicad_sch (version 20211123) (generator eeschema))"#;
+		assert!(input.parse::<Schematic>().is_err());
+	}
 }
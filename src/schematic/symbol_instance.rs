@@ -0,0 +1,166 @@
+use crate::{
+	common::Position,
+	internal::{
+		option_tuple, option_unit, option_yes_no, tuple, tuple_or_default, yes_no
+	},
+	symbol::Property
+};
+use serde::{Deserialize, Serialize};
+use serde_sexpr::untagged;
+use uuid::Uuid;
+
+/// The unique identifier of a pin of a placed symbol.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "pin")]
+pub struct PinInstance {
+	pub number: String,
+
+	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
+	pub uuid: Uuid
+}
+
+untagged! {
+	#[derive(Clone, Debug, PartialEq)]
+	pub enum SymbolInstanceContent {
+		Property(Property),
+		Pin(PinInstance)
+	}
+}
+
+/// A library symbol placed on the schematic. The symbol itself is referenced
+/// by its `lib_id` and defined in the `lib_symbols` of the schematic.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename = "symbol")]
+pub struct SymbolInstance {
+	/// The name of the symbol in `lib_symbols`, if it differs from `lib_id`.
+	#[serde(with = "option_tuple")]
+	pub lib_name: Option<String>,
+
+	#[serde(with = "tuple")]
+	pub lib_id: String,
+
+	pub position: Position,
+
+	#[serde(with = "option_tuple")]
+	pub mirror: Option<String>,
+
+	#[serde(with = "tuple")]
+	pub unit: u32,
+
+	#[serde(with = "option_tuple")]
+	pub convert: Option<u32>,
+
+	/// Exclude the symbol from simulation. Only written by KiCad 7 and later.
+	#[serde(with = "option_yes_no")]
+	pub exclude_from_sim: Option<bool>,
+
+	#[serde(with = "yes_no")]
+	pub in_bom: bool,
+
+	#[serde(with = "yes_no")]
+	pub on_board: bool,
+
+	/// Do not populate the symbol. Only written by KiCad 7 and later.
+	#[serde(with = "option_yes_no")]
+	pub dnp: Option<bool>,
+
+	#[serde(with = "option_unit")]
+	pub fields_autoplaced: bool,
+
+	#[serde(with = "tuple_or_default", skip_serializing_if = "crate::skip_uuid")]
+	pub uuid: Uuid,
+
+	#[serde(default, rename = "")]
+	pub content: Vec<SymbolInstanceContent>
+}
+
+impl SymbolInstance {
+	pub fn new<T>(lib_id: T, position: Position, unit: u32) -> Self
+	where
+		T: Into<String>
+	{
+		Self {
+			lib_name: None,
+			lib_id: lib_id.into(),
+			position,
+			mirror: None,
+			unit,
+			convert: None,
+			exclude_from_sim: None,
+			in_bom: true,
+			on_board: true,
+			dnp: None,
+			fields_autoplaced: false,
+			uuid: Uuid::new_v4(),
+			content: Vec::new()
+		}
+	}
+
+	/// Return the properties of this placement, like its reference and value.
+	pub fn properties(&self) -> impl Iterator<Item = &Property> {
+		self.content.iter().filter_map(|c| match c {
+			SymbolInstanceContent::Property(prop) => Some(prop),
+			_ => None
+		})
+	}
+
+	/// Return the value of the property with the given key.
+	pub fn get_property(&self, key: &str) -> Option<&str> {
+		self.properties()
+			.find(|prop| prop.key == key)
+			.map(|prop| prop.value.as_str())
+	}
+
+	/// Return the pins of this placement together with their unique identifiers.
+	pub fn pins(&self) -> impl Iterator<Item = &PinInstance> {
+		self.content.iter().filter_map(|c| match c {
+			SymbolInstanceContent::Pin(pin) => Some(pin),
+			_ => None
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{sexpr_test_case, symbol::PropertyPosition, Deg, Unit};
+
+	sexpr_test_case! {
+		name: resistor,
+		input: r#"(symbol (lib_id "Device:R") (at 100 50 90) (unit 1) (in_bom yes) (on_board yes) (uuid "00000000-0000-0000-0000-000000000000") (property Reference "R1" (id 0) (at 100 45 90) (effects (font (size 1.27 1.27)))) (property Value "10k" (id 1) (at 100 55 90) (effects (font (size 1.27 1.27)))) (pin "1" (uuid "00000000-0000-0000-0000-000000000000")) (pin "2" (uuid "00000000-0000-0000-0000-000000000000")))"#,
+		value: SymbolInstance {
+			uuid: Uuid::nil(),
+			content: vec![
+				SymbolInstanceContent::Property(Property::new(
+					Property::REFERENCE_KEY,
+					"R1",
+					Property::REFERENCE_ID,
+					PropertyPosition::new(100.0.mm(), 45.0.mm(), 90.0.deg()),
+					1.27.mm(),
+					false
+				)),
+				SymbolInstanceContent::Property(Property::new(
+					Property::VALUE_KEY,
+					"10k",
+					Property::VALUE_ID,
+					PropertyPosition::new(100.0.mm(), 55.0.mm(), 90.0.deg()),
+					1.27.mm(),
+					false
+				)),
+				SymbolInstanceContent::Pin(PinInstance {
+					number: "1".to_owned(),
+					uuid: Uuid::nil()
+				}),
+				SymbolInstanceContent::Pin(PinInstance {
+					number: "2".to_owned(),
+					uuid: Uuid::nil()
+				}),
+			],
+			..SymbolInstance::new(
+				"Device:R",
+				Position::new_with_angle(100.0.mm(), 50.0.mm(), 90.0.deg()),
+				1
+			)
+		}
+	}
+}
@@ -12,6 +12,13 @@ pub struct Curve {
 	pub fill: Fill
 }
 
+impl Curve {
+	/// Approximate the curve with a polyline of `segments` line segments.
+	pub fn to_polyline(&self, segments: usize) -> PointList {
+		self.pts.cubic_bezier_polyline(segments)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1,30 +1,41 @@
-use crate::internal::tuple;
+use crate::{
+	internal::{option_color, tuple},
+	Color
+};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum FillType {
+	#[default]
 	None,
 	Outline,
-	Background
+	Background,
+	/// Fill with the color of the [`Fill`]. Only written by KiCad 7 and later.
+	Color
 }
 
-impl Default for FillType {
-	fn default() -> Self {
-		Self::None
-	}
-}
-
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "fill")]
 pub struct Fill {
 	#[serde(rename = "type", with = "tuple")]
-	pub ty: FillType
+	pub ty: FillType,
+
+	#[serde(with = "option_color")]
+	pub color: Option<Color>
 }
 
 impl Fill {
 	pub const fn new(ty: FillType) -> Self {
-		Self { ty }
+		Self { ty, color: None }
+	}
+
+	/// Fill with the given color.
+	pub const fn color(color: Color) -> Self {
+		Self {
+			ty: FillType::Color,
+			color: Some(color)
+		}
 	}
 }
 
@@ -38,4 +49,22 @@ mod tests {
 		input: r#"(fill (type none))"#,
 		value: Fill::new(FillType::None)
 	}
+
+	sexpr_test_case! {
+		name: fill_outline,
+		input: r#"(fill (type outline))"#,
+		value: Fill::new(FillType::Outline)
+	}
+
+	sexpr_test_case! {
+		name: fill_background,
+		input: r#"(fill (type background))"#,
+		value: Fill::new(FillType::Background)
+	}
+
+	sexpr_test_case! {
+		name: fill_color,
+		input: r#"(fill (type color) (color 255 0 0 1))"#,
+		value: Fill::color(Color::new_alpha(255, 0, 0, 1.0))
+	}
 }
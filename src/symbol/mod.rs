@@ -3,7 +3,10 @@
 //! This module defines all syntax that is shared across the symbol library and
 //! schematic file formats.
 
-use crate::internal::{option_tuple, option_unit, option_yes_no};
+use crate::{
+	internal::{option_tuple, option_unit, option_yes_no},
+	Unit
+};
 use serde::{Deserialize, Serialize};
 use serde_sexpr::untagged;
 
@@ -19,6 +22,7 @@ mod property;
 mod rectangle;
 mod stroke;
 mod text;
+mod validate;
 
 pub use arc::Arc;
 pub use circle::Circle;
@@ -33,12 +37,13 @@ pub use property::{Property, PropertyPosition};
 pub use rectangle::Rectangle;
 pub use stroke::{Stroke, StrokeType};
 pub use text::Text;
+pub use validate::SymbolWarning;
 
 untagged! {
 	#[derive(Clone, Debug, PartialEq)]
 	pub enum SymbolContent {
 		Property(Property),
-		Symbol(InnerSymbol),
+		Symbol(SymbolUnit),
 		Pin(Pin),
 
 		Arc(Arc),
@@ -67,6 +72,10 @@ pub struct Symbol {
 	#[serde(with = "serde_sexpr::Option")]
 	pub pin_names: Option<PinNames>,
 
+	/// Exclude the symbol from simulation. Only written by KiCad 7 and later.
+	#[serde(with = "option_yes_no")]
+	pub exclude_from_sim: Option<bool>,
+
 	// TODO this is only optional if extends is being used
 	#[serde(with = "option_yes_no")]
 	pub in_bom: Option<bool>,
@@ -79,15 +88,216 @@ pub struct Symbol {
 	pub content: Vec<SymbolContent>
 }
 
+impl Symbol {
+	fn properties(&self) -> impl Iterator<Item = &Property> {
+		self.content.iter().filter_map(|c| match c {
+			SymbolContent::Property(prop) => Some(prop),
+			_ => None
+		})
+	}
+
+	fn property_mut(&mut self, key: &str) -> Option<&mut Property> {
+		self.content.iter_mut().find_map(|c| match c {
+			SymbolContent::Property(prop) if prop.key == key => Some(prop),
+			_ => None
+		})
+	}
+
+	/// Return the value of the property with the given key.
+	pub fn get_property(&self, key: &str) -> Option<&str> {
+		self.properties()
+			.find(|prop| prop.key == key)
+			.map(|prop| prop.value.as_str())
+	}
+
+	/// Return the default footprint of this symbol, e.g.
+	/// `Resistor_SMD:R_0402`. Symbols without a footprint have an empty
+	/// `Footprint` property, which is returned as [`None`].
+	pub fn default_footprint(&self) -> Option<&str> {
+		self.get_property("Footprint")
+			.filter(|footprint| !footprint.is_empty())
+	}
+
+	/// Return the datasheet link of this symbol. KiCad writes `~` if there is
+	/// no datasheet, which is returned as [`None`] like an empty value.
+	pub fn datasheet(&self) -> Option<&str> {
+		self.get_property("Datasheet")
+			.filter(|datasheet| !datasheet.is_empty() && *datasheet != "~")
+	}
+
+	/// Update the value of the property with the given key, or add a new hidden
+	/// property after the existing ones if there is none.
+	pub fn set_property<K, V>(&mut self, key: K, value: V)
+	where
+		K: Into<String>,
+		V: Into<String>
+	{
+		let key = key.into();
+		let value = value.into();
+		if let Some(prop) = self.property_mut(&key) {
+			prop.value = value;
+			return;
+		}
+
+		let ids: Vec<i32> = self.properties().map(|prop| prop.id).collect();
+		let id = match key.as_str() {
+			"Reference" => Some(Property::REFERENCE_ID),
+			"Value" => Some(Property::VALUE_ID),
+			"Footprint" => Some(Property::FOOTPRINT_ID),
+			"Datasheet" => Some(Property::DATASHEET_ID),
+			_ => None
+		};
+		// user defined properties are numbered after the mandatory ones
+		let id = id.filter(|id| !ids.contains(id)).unwrap_or_else(|| {
+			ids.into_iter()
+				.chain([Property::DATASHEET_ID])
+				.max()
+				.unwrap() + 1
+		});
+		let hide = key != "Reference" && key != "Value";
+		let prop = Property::new(
+			key,
+			value,
+			id,
+			PropertyPosition::origin(),
+			1.27.mm(),
+			hide
+		);
+
+		let idx = self
+			.content
+			.iter()
+			.rposition(|c| matches!(c, SymbolContent::Property(_)))
+			.map(|idx| idx + 1)
+			.unwrap_or(0);
+		self.content.insert(idx, SymbolContent::Property(prop));
+	}
+
+	/// Return all units of this symbol.
+	pub fn units(&self) -> impl Iterator<Item = &SymbolUnit> {
+		self.content.iter().filter_map(|c| match c {
+			SymbolContent::Symbol(unit) => Some(unit),
+			_ => None
+		})
+	}
+
+	/// Return the first unit with the given unit number.
+	pub fn unit(&self, n: u32) -> Option<&SymbolUnit> {
+		self.units().find(|unit| unit.unit_number() == Some(n))
+	}
+
+	/// Return the number of units of this symbol. The shared unit `0` is not
+	/// counted, so symbols consisting only of shared graphics have one unit.
+	pub fn unit_count(&self) -> u32 {
+		self.units()
+			.filter_map(SymbolUnit::unit_number)
+			.max()
+			.unwrap_or(0)
+			.max(1)
+	}
+
+	/// Return all pins of this symbol, including the pins of all units. Pins of
+	/// units with alternate body styles are returned as well.
+	pub fn pins(&self) -> impl Iterator<Item = &Pin> {
+		self.content
+			.iter()
+			.filter_map(|c| match c {
+				SymbolContent::Pin(pin) => Some(pin),
+				_ => None
+			})
+			.chain(self.units().flat_map(SymbolUnit::pins))
+	}
+
+	/// Return the first pin with the given number.
+	pub fn pin_by_number(&self, number: &str) -> Option<&Pin> {
+		self.pins().find(|pin| pin.number.number == number)
+	}
+
+	/// Return the power input and output pins of this symbol.
+	pub fn power_pins(&self) -> impl Iterator<Item = &Pin> {
+		self.pins().filter(|pin| {
+			matches!(
+				pin.electrical_type,
+				PinElectricalType::PowerIn | PinElectricalType::PowerOut
+			)
+		})
+	}
+
+	/// Remove the property with the given key, returning it if it existed.
+	pub fn remove_property(&mut self, key: &str) -> Option<Property> {
+		let idx = self.content.iter().position(
+			|c| matches!(c, SymbolContent::Property(prop) if prop.key == key)
+		)?;
+		match self.content.remove(idx) {
+			SymbolContent::Property(prop) => Some(prop),
+			_ => unreachable!()
+		}
+	}
+
+	/// Sort the properties into the order KiCad writes them in: Reference, Value,
+	/// Footprint and Datasheet, followed by all other properties in their
+	/// current order. The properties stay in place relative to other content.
+	pub fn sort_properties(&mut self) {
+		fn rank(prop: &Property) -> usize {
+			["Reference", "Value", "Footprint", "Datasheet"]
+				.iter()
+				.position(|key| prop.key == *key)
+				.unwrap_or(4)
+		}
+
+		let mut props: Vec<Property> = self.properties().cloned().collect();
+		props.sort_by_key(rank);
+		let slots = self
+			.content
+			.iter_mut()
+			.filter(|c| matches!(c, SymbolContent::Property(_)));
+		for (slot, prop) in slots.zip(props) {
+			*slot = SymbolContent::Property(prop);
+		}
+	}
+}
+
+/// A unit of a [`Symbol`]. Its id is the name of the parent symbol followed by
+/// the unit number and the body style, e.g. `LM358_2_1`. Unit `0` contains the
+/// graphics that are shared by all units.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "symbol")]
-pub struct InnerSymbol {
+pub struct SymbolUnit {
 	pub id: String,
 
 	#[serde(default, rename = "")]
 	pub content: Vec<SymbolContent>
 }
 
+impl SymbolUnit {
+	fn suffix(&self) -> Option<(u32, u32)> {
+		let mut parts = self.id.rsplitn(3, '_');
+		let body_style = parts.next()?.parse().ok()?;
+		let unit_number = parts.next()?.parse().ok()?;
+		parts.next()?;
+		Some((unit_number, body_style))
+	}
+
+	/// Return the pins of this unit.
+	pub fn pins(&self) -> impl Iterator<Item = &Pin> {
+		self.content.iter().filter_map(|c| match c {
+			SymbolContent::Pin(pin) => Some(pin),
+			_ => None
+		})
+	}
+
+	/// The unit number encoded in the id, or `None` if the id has no valid suffix.
+	pub fn unit_number(&self) -> Option<u32> {
+		self.suffix().map(|(unit_number, _)| unit_number)
+	}
+
+	/// The body style encoded in the id, or `None` if the id has no valid
+	/// suffix. Body style `1` is the normal style, `2` the De Morgan style.
+	pub fn body_style(&self) -> Option<u32> {
+		self.suffix().map(|(_, body_style)| body_style)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -102,6 +312,7 @@ mod tests {
 			power: false,
 			hide_pin_numbers: false,
 			pin_names: None,
+			exclude_from_sim: None,
 			in_bom: Some(true),
 			on_board: Some(true),
 			content: vec![]
@@ -117,6 +328,7 @@ mod tests {
 			power: false,
 			hide_pin_numbers: false,
 			pin_names: None,
+			exclude_from_sim: None,
 			in_bom: Some(true),
 			on_board: Some(true),
 			content: vec![SymbolContent::Property(Property::new(
@@ -129,4 +341,184 @@ mod tests {
 			))]
 		}
 	}
+
+	sexpr_test_case! {
+		name: symbol_hide_pin_numbers,
+		input: r#"(symbol R (pin_numbers hide) (pin_names (offset 0) hide) (in_bom yes) (on_board yes))"#,
+		value: Symbol {
+			id: "R".into(),
+			extends: None,
+			power: false,
+			hide_pin_numbers: true,
+			pin_names: Some(PinNames {
+				offset: Some(0.0.mm()),
+				hide: true
+			}),
+			exclude_from_sim: None,
+			in_bom: Some(true),
+			on_board: Some(true),
+			content: vec![]
+		}
+	}
+
+	sexpr_test_case! {
+		name: symbol_pin_names_offset,
+		input: r#"(symbol U (pin_names (offset 0.254)) (in_bom yes) (on_board yes))"#,
+		value: Symbol {
+			id: "U".into(),
+			extends: None,
+			power: false,
+			hide_pin_numbers: false,
+			pin_names: Some(PinNames::new_with_offset(0.254.mm())),
+			exclude_from_sim: None,
+			in_bom: Some(true),
+			on_board: Some(true),
+			content: vec![]
+		}
+	}
+
+	sexpr_test_case! {
+		name: symbol_bom_flags,
+		input: r#"(symbol TP (exclude_from_sim no) (in_bom yes) (on_board no))"#,
+		value: Symbol {
+			id: "TP".into(),
+			extends: None,
+			power: false,
+			hide_pin_numbers: false,
+			pin_names: None,
+			exclude_from_sim: Some(false),
+			in_bom: Some(true),
+			on_board: Some(false),
+			content: vec![]
+		}
+	}
+
+	#[test]
+	fn properties() {
+		let prop = |key, value, id| {
+			SymbolContent::Property(Property::new(
+				key,
+				value,
+				id,
+				PropertyPosition::origin(),
+				1.27.mm(),
+				false
+			))
+		};
+		let mut symbol = Symbol {
+			id: "R".into(),
+			extends: None,
+			power: false,
+			hide_pin_numbers: false,
+			pin_names: None,
+			exclude_from_sim: None,
+			in_bom: Some(true),
+			on_board: Some(true),
+			content: vec![
+				prop("Reference", "R", 0),
+				prop("Value", "R", 1),
+				SymbolContent::Symbol(SymbolUnit {
+					id: "R_0_1".into(),
+					content: vec![]
+				}),
+			]
+		};
+
+		symbol.set_property("Value", "10k");
+		symbol.set_property("Datasheet", "~");
+		let keys: Vec<_> = symbol.properties().map(|p| p.key.as_str()).collect();
+		assert_eq!(keys, vec!["Reference", "Value", "Datasheet"]);
+		assert_eq!(symbol.get_property("Value"), Some("10k"));
+		assert_eq!(symbol.get_property("Datasheet"), Some("~"));
+		assert!(matches!(symbol.content[3], SymbolContent::Symbol(_)));
+		match &symbol.content[2] {
+			SymbolContent::Property(prop) => {
+				assert_eq!(prop.id, Property::DATASHEET_ID);
+				assert!(prop.effects.hide);
+			},
+			content => panic!("unexpected content {content:?}")
+		}
+
+		let removed = symbol.remove_property("Datasheet").unwrap();
+		assert_eq!(removed.value, "~");
+		assert_eq!(symbol.get_property("Datasheet"), None);
+		assert!(symbol.remove_property("Datasheet").is_none());
+	}
+
+	#[test]
+	fn default_footprint_and_datasheet() {
+		let input = r#"(symbol R (in_bom yes) (on_board yes) (property Footprint "Resistor_SMD:R_0402" (id 2) (at 0 0 0) (effects (font (size 1.27 1.27)) hide)) (property Datasheet "https://example.com/r.pdf" (id 3) (at 0 0 0) (effects (font (size 1.27 1.27)) hide)))"#;
+		let symbol: Symbol = serde_sexpr::from_str(input).unwrap();
+		assert_eq!(symbol.default_footprint(), Some("Resistor_SMD:R_0402"));
+		assert_eq!(symbol.datasheet(), Some("https://example.com/r.pdf"));
+
+		let input = r#"(symbol R (in_bom yes) (on_board yes))"#;
+		let mut symbol: Symbol = serde_sexpr::from_str(input).unwrap();
+		assert_eq!(symbol.default_footprint(), None);
+		assert_eq!(symbol.datasheet(), None);
+
+		symbol.set_property("Footprint", "");
+		symbol.set_property("Datasheet", "~");
+		assert_eq!(symbol.default_footprint(), None);
+		assert_eq!(symbol.datasheet(), None);
+	}
+
+	#[test]
+	fn units() {
+		let input = r#"(symbol "LM358" (in_bom yes) (on_board yes) (symbol "LM358_0_1") (symbol "LM358_1_1") (symbol "LM358_2_1"))"#;
+		let symbol: Symbol = serde_sexpr::from_str(input).unwrap();
+		assert_eq!(symbol.unit_count(), 2);
+
+		let unit = symbol.unit(2).unwrap();
+		assert_eq!(unit.id, "LM358_2_1");
+		assert_eq!(unit.unit_number(), Some(2));
+		assert_eq!(unit.body_style(), Some(1));
+		assert_eq!(symbol.unit(1).unwrap().id, "LM358_1_1");
+		assert!(symbol.unit(3).is_none());
+	}
+
+	#[test]
+	fn unit_suffix() {
+		let unit = |id: &str| SymbolUnit {
+			id: id.into(),
+			content: vec![]
+		};
+		assert_eq!(unit("Op_Amp_1_2").unit_number(), Some(1));
+		assert_eq!(unit("Op_Amp_1_2").body_style(), Some(2));
+		assert_eq!(unit("R_1").unit_number(), None);
+		assert_eq!(unit("R_a_1").body_style(), None);
+	}
+
+	#[test]
+	fn pins() {
+		let pin = |ty: &str, number: &str| {
+			format!(
+				r#"(pin {ty} line (at 0 0) (length 2.54) (name "~" (effects (font (size 1.27 1.27)))) (number "{number}" (effects (font (size 1.27 1.27)))))"#
+			)
+		};
+		let input = format!(
+			r#"(symbol "LM358" (in_bom yes) (on_board yes) (symbol "LM358_0_1" {} {}) (symbol "LM358_1_1" {} {} {}) (symbol "LM358_2_1" {} {} {}))"#,
+			pin("power_in", "4"),
+			pin("power_in", "8"),
+			pin("output", "1"),
+			pin("input", "2"),
+			pin("input", "3"),
+			pin("input", "5"),
+			pin("input", "6"),
+			pin("output", "7")
+		);
+		let symbol: Symbol = serde_sexpr::from_str(&input).unwrap();
+		assert_eq!(symbol.pins().count(), 8);
+		assert_eq!(symbol.unit(2).unwrap().pins().count(), 3);
+
+		let pin = symbol.pin_by_number("7").unwrap();
+		assert_eq!(pin.electrical_type, PinElectricalType::Output);
+		assert!(symbol.pin_by_number("9").is_none());
+
+		let power: Vec<_> = symbol
+			.power_pins()
+			.map(|pin| pin.number.number.as_str())
+			.collect();
+		assert_eq!(power, ["4", "8"]);
+	}
 }
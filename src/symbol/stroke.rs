@@ -1,25 +1,20 @@
 use crate::{
-	internal::{tuple, ColorDef},
+	internal::{transparent, tuple, ColorDef},
 	mm, Color
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum StrokeType {
 	Dash,
 	DashDot,
 	Dot,
+	#[default]
 	Default,
 	Solid
 }
 
-impl Default for StrokeType {
-	fn default() -> Self {
-		Self::Default
-	}
-}
-
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, rename = "stroke")]
 pub struct Stroke {
@@ -38,7 +33,7 @@ impl Stroke {
 		Self {
 			width,
 			ty,
-			color: Color::new_alpha(0, 0, 0, 0.0)
+			color: transparent()
 		}
 	}
 }
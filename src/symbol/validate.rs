@@ -0,0 +1,188 @@
+use super::{Pin, Symbol, SymbolContent};
+use crate::{common::Point, mm, Unit};
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// A likely mistake in the pins of a symbol, returned by [`Symbol::validate`].
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum SymbolWarning {
+	#[error("Pin number {0:?} is used by more than one pin")]
+	DuplicatePinNumber(String),
+
+	#[error("Pins {first:?} and {second:?} are both placed at {position:?}")]
+	OverlappingPins {
+		first: String,
+		second: String,
+		position: Point
+	},
+
+	#[error("Pin {number:?} at {position:?} is not on the grid")]
+	OffGrid { number: String, position: Point }
+}
+
+/// The grid KiCad recommends placing symbol pins on.
+const PIN_GRID: f32 = 1.27;
+
+/// The maximum distance of a coordinate from the grid to still be considered on
+/// the grid, to account for rounding errors.
+const GRID_TOLERANCE: f64 = 1e-4;
+
+/// A pin together with the unit and body style it belongs to. Unit and body
+/// style `0` are shared by all units and body styles.
+struct UnitPin<'a> {
+	unit: u32,
+	body_style: u32,
+	pin: &'a Pin
+}
+
+impl UnitPin<'_> {
+	fn shares_body_style(&self, other: &Self) -> bool {
+		self.body_style == 0
+			|| other.body_style == 0
+			|| self.body_style == other.body_style
+	}
+
+	fn shares_unit(&self, other: &Self) -> bool {
+		self.unit == 0 || other.unit == 0 || self.unit == other.unit
+	}
+}
+
+fn on_grid(value: mm, grid: f64) -> bool {
+	let value = value.raw_value() as f64;
+	((value / grid).round() * grid - value).abs() <= GRID_TOLERANCE
+}
+
+impl Symbol {
+	fn unit_pins(&self) -> Vec<UnitPin<'_>> {
+		let mut pins = Vec::new();
+		for content in &self.content {
+			match content {
+				SymbolContent::Pin(pin) => pins.push(UnitPin {
+					unit: 0,
+					body_style: 0,
+					pin
+				}),
+				SymbolContent::Symbol(unit) => {
+					pins.extend(unit.pins().map(|pin| UnitPin {
+						unit: unit.unit_number().unwrap_or(0),
+						body_style: unit.body_style().unwrap_or(0),
+						pin
+					}))
+				},
+				_ => {}
+			}
+		}
+		pins
+	}
+
+	/// Check the pins of this symbol for duplicate pin numbers, pins placed on
+	/// top of each other and pins that are not on the 1.27mm grid. Pins of
+	/// different units or body styles do not conflict with each other, except
+	/// that a pin number may only be used once per body style.
+	pub fn validate(&self) -> Vec<SymbolWarning> {
+		self.validate_with_grid(PIN_GRID.mm())
+	}
+
+	/// Like [`Self::validate`], but check that the pins are placed on the given
+	/// grid instead.
+	pub fn validate_with_grid(&self, grid: mm) -> Vec<SymbolWarning> {
+		let pins = self.unit_pins();
+		let mut warnings = Vec::new();
+
+		let mut duplicates = BTreeSet::new();
+		for (i, a) in pins.iter().enumerate() {
+			for b in &pins[i + 1..] {
+				if !a.shares_body_style(b) {
+					continue;
+				}
+				let number = &a.pin.number.number;
+				if *number == b.pin.number.number && duplicates.insert(number) {
+					warnings.push(SymbolWarning::DuplicatePinNumber(number.clone()));
+				}
+				let position = a.pin.at.point();
+				if a.shares_unit(b) && position == b.pin.at.point() {
+					warnings.push(SymbolWarning::OverlappingPins {
+						first: number.clone(),
+						second: b.pin.number.number.clone(),
+						position
+					});
+				}
+			}
+		}
+
+		let grid = grid.raw_value() as f64;
+		for UnitPin { pin, .. } in &pins {
+			let position = pin.at.point();
+			if !on_grid(position.x, grid) || !on_grid(position.y, grid) {
+				warnings.push(SymbolWarning::OffGrid {
+					number: pin.number.number.clone(),
+					position
+				});
+			}
+		}
+
+		warnings
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn pin(number: &str, x: f32, y: f32) -> String {
+		format!(
+			r#"(pin passive line (at {x} {y} 0) (length 2.54) (name "~" (effects (font (size 1.27 1.27)))) (number "{number}" (effects (font (size 1.27 1.27)))))"#
+		)
+	}
+
+	fn symbol(units: &[(&str, Vec<String>)]) -> Symbol {
+		let units: String = units
+			.iter()
+			.map(|(id, pins)| format!(r#"(symbol "{id}" {})"#, pins.concat()))
+			.collect();
+		let input = format!(r#"(symbol "U" (in_bom yes) (on_board yes) {units})"#);
+		serde_sexpr::from_str(&input).unwrap()
+	}
+
+	#[test]
+	fn valid() {
+		let pins = vec![pin("1", -5.08, 0.0), pin("2", 5.08, 0.0)];
+		assert_eq!(symbol(&[("U_1_1", pins)]).validate(), []);
+	}
+
+	#[test]
+	fn overlapping_pins() {
+		let pins = vec![pin("1", -5.08, 2.54), pin("2", -5.08, 2.54)];
+		assert_eq!(symbol(&[("U_1_1", pins)]).validate(), [
+			SymbolWarning::OverlappingPins {
+				first: "1".to_owned(),
+				second: "2".to_owned(),
+				position: Point::new((-5.08).mm(), 2.54.mm())
+			}
+		]);
+	}
+
+	#[test]
+	fn off_grid_pin() {
+		let pins = vec![pin("1", -5.08, 0.0), pin("2", 5.0, 0.0)];
+		let symbol = symbol(&[("U_1_1", pins)]);
+		assert_eq!(symbol.validate(), [SymbolWarning::OffGrid {
+			number: "2".to_owned(),
+			position: Point::new(5.0.mm(), 0.0.mm())
+		}]);
+		assert_eq!(symbol.validate_with_grid(0.01.mm()), []);
+	}
+
+	#[test]
+	fn duplicate_pin_number() {
+		let symbol = symbol(&[
+			("U_1_1", vec![pin("1", 0.0, 0.0)]),
+			("U_2_1", vec![pin("1", 0.0, 0.0), pin("2", 2.54, 0.0)]),
+			// the De Morgan body style repeats the pins of the normal style
+			("U_2_2", vec![pin("2", 2.54, 0.0)])
+		]);
+		assert_eq!(symbol.validate(), [SymbolWarning::DuplicatePinNumber(
+			"1".to_owned()
+		)]);
+	}
+}
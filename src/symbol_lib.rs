@@ -2,11 +2,20 @@
 //!
 //! This module defines syntax that is used by the symbol library.
 
-use crate::{internal::tuple, symbol::Symbol};
+#[cfg(feature = "json")]
+use crate::internal::json;
+use crate::{
+	error::from_node,
+	internal::{option_tuple, scanner, tuple},
+	symbol::{Symbol, SymbolContent}
+};
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use std::{collections::HashSet, ops::Index, slice, str::FromStr, vec};
+use thiserror::Error;
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(
+	Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize,
+)]
 #[serde(deny_unknown_fields, rename = "version")]
 pub struct Version(u32);
 
@@ -30,14 +39,409 @@ pub struct SymbolLib {
 	#[serde(with = "tuple")]
 	pub generator: String,
 
+	/// The version of the program that generated the file, e.g. `8.0`. Only
+	/// written by KiCad 8 and later.
+	#[serde(with = "option_tuple")]
+	pub generator_version: Option<String>,
+
 	#[serde(default, rename = "")]
 	pub symbols: Vec<Symbol>
 }
 
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum MergeError {
+	#[error("Duplicate symbol names: {}", .0.join(", "))]
+	DuplicateSymbols(Vec<String>)
+}
+
+/// How to resolve symbols with the same name when merging symbol libraries.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strategy {
+	/// Keep the existing symbol and drop the new one.
+	KeepExisting,
+	/// Replace the existing symbol with the new one.
+	Overwrite,
+	/// Keep both symbols, renaming the new one by appending a number.
+	Rename
+}
+
+impl SymbolLib {
+	/// Append all symbols of `other` to this library. If any symbol name exists in
+	/// both libraries, an error is returned and this library is left unchanged.
+	pub fn merge(&mut self, other: SymbolLib) -> Result<(), MergeError> {
+		let duplicates: Vec<String> = other
+			.symbols
+			.iter()
+			.filter(|sym| self.symbols.iter().any(|s| s.id == sym.id))
+			.map(|sym| sym.id.clone())
+			.collect();
+		if !duplicates.is_empty() {
+			return Err(MergeError::DuplicateSymbols(duplicates));
+		}
+		self.merge_with_strategy(other, Strategy::KeepExisting);
+		Ok(())
+	}
+
+	/// Append all symbols of `other` to this library, resolving symbols with the
+	/// same name using `strategy`.
+	pub fn merge_with_strategy(&mut self, mut other: SymbolLib, strategy: Strategy) {
+		self.version = self.version.max(other.version);
+
+		if strategy == Strategy::Rename {
+			let mut names: HashSet<String> =
+				self.symbols.iter().map(|sym| sym.id.clone()).collect();
+			names.extend(other.symbols.iter().map(|sym| sym.id.clone()));
+			for i in 0..other.symbols.len() {
+				let old = other.symbols[i].id.clone();
+				if !self.symbols.iter().any(|sym| sym.id == old) {
+					continue;
+				}
+				let new = (1..)
+					.map(|n| format!("{old}_{n}"))
+					.find(|name| !names.contains(name))
+					.unwrap();
+				names.insert(new.clone());
+				rename_symbol(&mut other.symbols[i], &new);
+				for sym in &mut other.symbols {
+					if sym.extends.as_deref() == Some(old.as_str()) {
+						sym.extends = Some(new.clone());
+					}
+				}
+			}
+		}
+
+		for sym in other.symbols {
+			match self.symbols.iter_mut().find(|s| s.id == sym.id) {
+				Some(existing) if strategy == Strategy::Overwrite => *existing = sym,
+				Some(_) => {},
+				None => self.symbols.push(sym)
+			}
+		}
+	}
+
+	/// Sort the symbols and their properties the way KiCad writes them, so that
+	/// saving the library produces minimal diffs. Symbols are sorted by name,
+	/// with derived symbols following the symbol they extend. See
+	/// [`Symbol::sort_properties`] for the order of the properties.
+	pub fn normalize(&mut self) {
+		self.symbols.sort_by(|a, b| {
+			let key = |sym: &Symbol| {
+				let root = sym.extends.clone().unwrap_or_else(|| sym.id.clone());
+				(root, sym.extends.is_some(), sym.id.clone())
+			};
+			key(a).cmp(&key(b))
+		});
+		for sym in &mut self.symbols {
+			sym.sort_properties();
+		}
+	}
+
+	/// Return the symbol with the given name.
+	pub fn get(&self, name: &str) -> Option<&Symbol> {
+		self.symbols.iter().find(|sym| sym.id == name)
+	}
+
+	/// Return the names of all symbols in this library.
+	pub fn symbol_names(&self) -> Vec<&str> {
+		self.symbols.iter().map(|sym| sym.id.as_str()).collect()
+	}
+
+	/// Find all symbols whose name, value, keywords or description contain
+	/// `query`, ignoring case. Symbols named exactly `query` come first, followed
+	/// by symbols whose name contains it, followed by all other matches.
+	pub fn search(&self, query: &str) -> Vec<&Symbol> {
+		let query = query.to_lowercase();
+		let mut matches: Vec<(u8, &Symbol)> = self
+			.symbols
+			.iter()
+			.filter_map(|sym| search_rank(sym, &query).map(|rank| (rank, sym)))
+			.collect();
+		matches.sort_by_key(|(rank, _)| *rank);
+		matches.into_iter().map(|(_, sym)| sym).collect()
+	}
+}
+
+impl Index<&str> for SymbolLib {
+	type Output = Symbol;
+
+	/// Return the symbol with the given name.
+	///
+	/// ### Panics
+	///
+	/// Panics if the library does not contain a symbol with that name, use
+	/// [`SymbolLib::get`] instead to handle missing symbols.
+	fn index(&self, name: &str) -> &Symbol {
+		self.get(name)
+			.unwrap_or_else(|| panic!("no symbol named {name:?} in library"))
+	}
+}
+
+impl IntoIterator for SymbolLib {
+	type Item = Symbol;
+	type IntoIter = vec::IntoIter<Symbol>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.symbols.into_iter()
+	}
+}
+
+impl<'a> IntoIterator for &'a SymbolLib {
+	type Item = &'a Symbol;
+	type IntoIter = slice::Iter<'a, Symbol>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.symbols.iter()
+	}
+}
+
+/// The properties that are searched in addition to the name of a symbol. KiCad 6
+/// and 7 store the keywords and description as hidden `ki_` properties, KiCad 8
+/// replaced the latter with a `Description` property.
+const SEARCH_PROPERTIES: [&str; 4] =
+	["Value", "ki_keywords", "ki_description", "Description"];
+
+/// Return how well `sym` matches the lowercase `query`, lower is better, or
+/// `None` if it does not match at all.
+fn search_rank(sym: &Symbol, query: &str) -> Option<u8> {
+	let name = sym.id.to_lowercase();
+	if name == query {
+		return Some(0);
+	}
+	if name.contains(query) {
+		return Some(1);
+	}
+	SEARCH_PROPERTIES
+		.iter()
+		.filter_map(|key| sym.get_property(key))
+		.any(|value| value.to_lowercase().contains(query))
+		.then_some(2)
+}
+
+/// Rename a symbol including its units, whose names are prefixed with the name of
+/// the symbol.
+fn rename_symbol(sym: &mut Symbol, new: &str) {
+	for content in &mut sym.content {
+		if let SymbolContent::Symbol(inner) = content {
+			if let Some(suffix) = inner.id.strip_prefix(sym.id.as_str()) {
+				inner.id = format!("{new}{suffix}");
+			}
+		}
+	}
+	sym.id = new.to_owned();
+}
+
 impl FromStr for SymbolLib {
-	type Err = serde_sexpr::de::Error;
+	type Err = crate::Error;
+
+	fn from_str(s: &str) -> Result<Self, crate::Error> {
+		from_node(&scanner::strip_comments(s))
+			.map_err(|err| err.within("kicad_symbol_lib"))
+	}
+}
+
+#[cfg(feature = "json")]
+impl SymbolLib {
+	/// Write this symbol library as JSON.
+	pub fn to_json(&self) -> Result<String, serde_json::Error> {
+		serde_json::to_string(&json::to_value(self)?)
+	}
+
+	/// Parse a symbol library from JSON written by [`SymbolLib::to_json`].
+	pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+		json::from_value(&serde_json::from_str(s)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{sexpr_test_case, symbol::SymbolUnit};
+
+	fn symbol(id: &str) -> Symbol {
+		Symbol {
+			id: id.to_owned(),
+			extends: None,
+			power: false,
+			hide_pin_numbers: false,
+			pin_names: None,
+			exclude_from_sim: None,
+			in_bom: Some(true),
+			on_board: Some(true),
+			content: vec![SymbolContent::Symbol(SymbolUnit {
+				id: format!("{id}_0_1"),
+				content: vec![]
+			})]
+		}
+	}
+
+	fn lib(version: u32, ids: &[&str]) -> SymbolLib {
+		SymbolLib {
+			version: Version(version),
+			generator: "kicad_symbol_editor".to_owned(),
+			generator_version: None,
+			symbols: ids.iter().map(|id| symbol(id)).collect()
+		}
+	}
+
+	fn ids(lib: &SymbolLib) -> Vec<&str> {
+		lib.symbols.iter().map(|sym| sym.id.as_str()).collect()
+	}
+
+	#[test]
+	fn merge() {
+		let mut a = lib(20211014, &["R", "C"]);
+		a.merge(lib(20220914, &["L"])).unwrap();
+		assert_eq!(ids(&a), vec!["R", "C", "L"]);
+		assert_eq!(a.version, Version(20220914));
+	}
+
+	#[test]
+	fn merge_collision() {
+		let mut a = lib(20220914, &["R", "C"]);
+		let err = a.merge(lib(20211014, &["C", "L"])).unwrap_err();
+		assert_eq!(err, MergeError::DuplicateSymbols(vec!["C".to_owned()]));
+		assert_eq!(a, lib(20220914, &["R", "C"]));
+	}
+
+	#[test]
+	fn merge_keep_existing() {
+		let mut a = lib(20211014, &["R", "C"]);
+		let mut b = lib(20211014, &["C", "L"]);
+		b.symbols[0].power = true;
+		a.merge_with_strategy(b, Strategy::KeepExisting);
+		assert_eq!(ids(&a), vec!["R", "C", "L"]);
+		assert!(!a.symbols[1].power);
+	}
+
+	#[test]
+	fn merge_overwrite() {
+		let mut a = lib(20211014, &["R", "C"]);
+		let mut b = lib(20211014, &["C", "L"]);
+		b.symbols[0].power = true;
+		a.merge_with_strategy(b, Strategy::Overwrite);
+		assert_eq!(ids(&a), vec!["R", "C", "L"]);
+		assert!(a.symbols[1].power);
+	}
+
+	#[test]
+	fn merge_rename() {
+		let mut a = lib(20211014, &["R", "C"]);
+		let mut b = lib(20211014, &["C", "C_Small"]);
+		b.symbols[1].extends = Some("C".to_owned());
+		a.merge_with_strategy(b, Strategy::Rename);
+		assert_eq!(ids(&a), vec!["R", "C", "C_1", "C_Small"]);
+		assert_eq!(a.symbols[3].extends.as_deref(), Some("C_1"));
+		match &a.symbols[2].content[0] {
+			SymbolContent::Symbol(inner) => assert_eq!(inner.id, "C_1_0_1"),
+			content => panic!("unexpected content {content:?}")
+		}
+	}
+
+	#[test]
+	fn symbol_names() {
+		assert_eq!(lib(20211014, &["R", "C"]).symbol_names(), vec!["R", "C"]);
+	}
+
+	#[test]
+	fn iterate() {
+		let lib = lib(20211014, &["R", "C"]);
+		let names: Vec<&str> =
+			(&lib).into_iter().map(|sym| sym.id.as_str()).collect();
+		assert_eq!(names, ["R", "C"]);
+
+		let mut count = 0;
+		for sym in &lib {
+			assert!(sym.in_bom == Some(true));
+			count += 1;
+		}
+		assert_eq!(count, 2);
+
+		let owned: Vec<Symbol> = lib.into_iter().collect();
+		assert_eq!(owned[1].id, "C");
+	}
+
+	#[test]
+	fn index() {
+		let lib = lib(20211014, &["R", "C"]);
+		assert_eq!(lib["C"].id, "C");
+		assert_eq!(lib.get("R"), Some(&lib.symbols[0]));
+		assert_eq!(lib.get("L"), None);
+	}
+
+	#[test]
+	#[should_panic(expected = "no symbol named \"L\"")]
+	fn index_missing() {
+		let _ = &lib(20211014, &["R"])["L"];
+	}
+
+	#[test]
+	fn search() {
+		let mut lib = lib(20211014, &["R_Small", "C", "R", "Thermistor"]);
+		lib.symbols[0].set_property("ki_description", "Resistor, small symbol");
+		lib.symbols[2].set_property("ki_description", "Resistor");
+		lib.symbols[3].set_property("ki_keywords", "NTC resistor temperature");
+		lib.symbols[3].set_property("Value", "10k");
+
+		let names = |found: Vec<&Symbol>| -> Vec<String> {
+			found.into_iter().map(|sym| sym.id.clone()).collect()
+		};
+		assert_eq!(names(lib.search("resistor")), vec![
+			"R_Small",
+			"R",
+			"Thermistor"
+		]);
+		assert_eq!(names(lib.search("r")), vec!["R", "R_Small", "Thermistor"]);
+		assert_eq!(names(lib.search("ntc")), vec!["Thermistor"]);
+		assert_eq!(names(lib.search("10K")), vec!["Thermistor"]);
+		assert!(lib.search("inductor").is_empty());
+	}
+
+	#[test]
+	fn normalize() {
+		let mut lib = lib(20211014, &["R", "C_Small", "C", "Amp"]);
+		lib.symbols[1].extends = Some("C".to_owned());
+		let sym = &mut lib.symbols[0];
+		sym.set_property("ki_keywords", "resistor");
+		sym.set_property("Datasheet", "~");
+		sym.set_property("Value", "R");
+		sym.set_property("ki_description", "Resistor");
+		sym.set_property("Reference", "R");
+		sym.set_property("Footprint", "");
+
+		lib.normalize();
+		assert_eq!(ids(&lib), vec!["Amp", "C", "C_Small", "R"]);
+		let sym = &lib.symbols[3];
+		let keys: Vec<&str> = sym
+			.content
+			.iter()
+			.filter_map(|c| match c {
+				SymbolContent::Property(prop) => Some(prop.key.as_str()),
+				_ => None
+			})
+			.collect();
+		assert_eq!(keys, vec![
+			"Reference",
+			"Value",
+			"Footprint",
+			"Datasheet",
+			"ki_keywords",
+			"ki_description"
+		]);
+		assert!(matches!(sym.content.last(), Some(SymbolContent::Symbol(_))));
+	}
+
+	sexpr_test_case! {
+		name: without_generator_version,
+		input: "(kicad_symbol_lib (version 20211014) (generator kicad_symbol_editor))",
+		value: lib(20211014, &[])
+	}
 
-	fn from_str(s: &str) -> Result<Self, serde_sexpr::de::Error> {
-		serde_sexpr::from_str(s)
+	sexpr_test_case! {
+		name: with_generator_version,
+		input: r#"(kicad_symbol_lib (version 20231120) (generator kicad_symbol_editor) (generator_version "8.0"))"#,
+		value: SymbolLib {
+			generator_version: Some("8.0".to_owned()),
+			..lib(20231120, &[])
+		}
 	}
 }
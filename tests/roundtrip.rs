@@ -0,0 +1,151 @@
+use kicad_files::{
+	pcb::{ParseMode, PCB},
+	symbol_lib::SymbolLib
+};
+use std::{fmt::Debug, fs, path::PathBuf};
+
+/// Return the field path and the two lines of the first line that differs
+/// between the pretty-printed debug output of both values.
+fn first_divergence<T>(left: &T, right: &T) -> Option<(String, String, String)>
+where
+	T: Debug
+{
+	let left = format!("{left:#?}");
+	let right = format!("{right:#?}");
+	let mut path: Vec<String> = Vec::new();
+	for (l, r) in left.lines().zip(right.lines()) {
+		let depth = l.len() - l.trim_start().len();
+		path.truncate(depth / 4);
+		if l != r {
+			let field = l.trim().split(':').next().unwrap_or_default();
+			path.push(field.to_owned());
+			return Some((path.join("."), l.trim().to_owned(), r.trim().to_owned()));
+		}
+		if l.ends_with('{') || l.ends_with('[') || l.ends_with('(') {
+			let field = l.trim().split([':', ' ', '(', '{', '[']).next().unwrap();
+			path.push(field.to_owned());
+		}
+	}
+	if left.lines().count() != right.lines().count() {
+		return Some((path.join("."), "<length differs>".to_owned(), String::new()));
+	}
+	None
+}
+
+/// Parse a fixture, write it back, parse the written output again and assert that
+/// both parsed values are equal.
+macro_rules! roundtrip_file {
+	($name:ident : $ty:ty = $path:literal) => {
+		#[test]
+		fn $name() {
+			let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", $path]
+				.iter()
+				.collect();
+			let input = fs::read_to_string(&path).expect("Failed to read fixture");
+			let parsed: $ty = input.parse().expect("Failed to parse fixture");
+			let written =
+				serde_sexpr::to_string(&parsed).expect("Failed to write fixture");
+			let reparsed: $ty = written.parse().expect("Failed to parse output");
+			if let Some((field, left, right)) = first_divergence(&parsed, &reparsed)
+			{
+				panic!(
+					"{} does not round-trip, first divergence in {field}:\n  \
+					 parsed:   {left}\n  reparsed: {right}",
+					path.display()
+				);
+			}
+		}
+	};
+}
+
+/// Return the names of the top-level nodes of an s-expr, i.e. of the children of
+/// its root node.
+fn top_level_nodes(s: &str) -> Vec<&str> {
+	let mut nodes = Vec::new();
+	let mut depth = 0;
+	let mut in_string = false;
+	let mut escaped = false;
+	for (i, c) in s.char_indices() {
+		match c {
+			_ if escaped => escaped = false,
+			'\\' if in_string => escaped = true,
+			'"' => in_string = !in_string,
+			_ if in_string => {},
+			'(' => {
+				depth += 1;
+				if depth == 2 {
+					let name = &s[i + 1..];
+					let end = name
+						.find([' ', '\n', '\t', ')'])
+						.unwrap_or(name.len());
+					nodes.push(&name[..end]);
+				}
+			},
+			')' => depth -= 1,
+			_ => {}
+		}
+	}
+	nodes
+}
+
+#[test]
+fn roundtrip_minnow_pcb() {
+	let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "minnow.kicad_pcb"]
+		.iter()
+		.collect();
+	let input = fs::read_to_string(path).unwrap();
+	let parsed = PCB::from_str_with(&input, ParseMode::Lenient).unwrap();
+	let written = serde_sexpr::to_string(&parsed).unwrap();
+	let nodes = top_level_nodes(&input);
+	assert!(nodes.contains(&"footprint") && nodes.contains(&"gr_text_box"));
+	assert_eq!(top_level_nodes(&written), nodes);
+	let reparsed = PCB::from_str_with(&written, ParseMode::Lenient).unwrap();
+	if let Some((field, left, right)) = first_divergence(&parsed, &reparsed) {
+		panic!("first divergence in {field}:\n  {left}\n  {right}");
+	}
+}
+
+roundtrip_file!(roundtrip_sample_symbol_lib: SymbolLib = "sample.kicad_sym");
+
+#[cfg(feature = "json")]
+#[test]
+fn roundtrip_minnow_pcb_json() {
+	let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "minnow.kicad_pcb"]
+		.iter()
+		.collect();
+	let parsed: PCB = fs::read_to_string(path).unwrap().parse().unwrap();
+	let reparsed = PCB::from_json(&parsed.to_json().unwrap()).unwrap();
+	if let Some((field, left, right)) = first_divergence(&parsed, &reparsed) {
+		panic!("first divergence in {field}:\n  {left}\n  {right}");
+	}
+}
+
+#[test]
+fn divergence_reports_field() {
+	#[derive(Debug)]
+	#[allow(dead_code)]
+	struct Inner {
+		a: u32,
+		b: u32
+	}
+
+	#[derive(Debug)]
+	#[allow(dead_code)]
+	struct Outer {
+		name: &'static str,
+		inner: Inner
+	}
+
+	let left = Outer {
+		name: "x",
+		inner: Inner { a: 1, b: 2 }
+	};
+	let right = Outer {
+		name: "x",
+		inner: Inner { a: 1, b: 3 }
+	};
+	let (field, l, r) = first_divergence(&left, &right).unwrap();
+	assert_eq!(field, "Outer.inner.b");
+	assert_eq!((l.as_str(), r.as_str()), ("b: 2,", "b: 3,"));
+	assert!(first_divergence(&left, &left).is_none());
+}